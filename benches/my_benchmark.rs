@@ -6,9 +6,42 @@ use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 use hierarchical_pathfinding::prelude::*;
 use log::warn;
 
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+/// An Allocator that keeps track of the number of allocated bytes, to measure memory usage
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ALLOCATED.fetch_add(layout.size(), Relaxed);
+        }
+        ptr
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Relaxed);
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATED.fetch_add(new_size, Relaxed);
+            ALLOCATED.fetch_sub(layout.size(), Relaxed);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
 #[derive(Copy, Clone, Debug)]
 pub struct Tile {
-    cost: isize,
+    cost: Option<usize>,
 }
 
 #[derive(Clone)]
@@ -22,7 +55,7 @@ impl Map {
     pub fn new(width: usize, height: usize) -> Self {
         let tile_count = width * height;
         Map {
-            tiles: vec![Tile { cost: 1 }; tile_count],
+            tiles: vec![Tile { cost: Some(1) }; tile_count],
             width,
             height,
         }
@@ -35,8 +68,9 @@ impl Map {
         let mut tiles = Vec::with_capacity(tile_count);
         let mut rng = WyRand::new_seed(4);
         for _ in 0..tile_count {
+            let cost = rng.generate_range(-1_isize..8);
             tiles.push(Tile {
-                cost: rng.generate_range(-1_isize..8),
+                cost: usize::try_from(cost).ok(),
             });
         }
         Map {
@@ -47,14 +81,14 @@ impl Map {
     }
 
     #[allow(unused)]
-    pub fn set_cost(&mut self, x: usize, y: usize, cost: isize) {
+    pub fn set_cost(&mut self, x: usize, y: usize, cost: Option<usize>) {
         let pos = self.get_tile_index(x, y);
         if let Some(pos) = pos {
             self.tiles[pos].cost = cost;
         }
     }
 
-    fn get_tile_cost(&self, x: usize, y: usize) -> isize {
+    fn get_tile_cost(&self, x: usize, y: usize) -> Option<usize> {
         let index = self.get_tile_index(x, y).unwrap();
         self.tiles[index].cost
     }
@@ -68,7 +102,7 @@ impl Map {
         Some(x + y * self.width)
     }
 
-    fn cost_fn(&self) -> impl '_ + Fn((usize, usize)) -> isize {
+    fn cost_fn(&self) -> impl '_ + Fn((usize, usize)) -> Option<usize> {
         move |(x, y)| self.get_tile_cost(x, y)
     }
 }
@@ -91,7 +125,7 @@ fn bench_create_pathcache(c: &mut Criterion) {
     // Log to stdout
     init();
 
-    let chunk_sizes = [32];
+    let chunk_sizes = [16, 32, 64];
     let map_sizes = [128, 1024];
 
     for map_size in map_sizes {
@@ -193,7 +227,7 @@ fn bench_update_pathcache(c: &mut Criterion) {
     // Put a solid wall across our map
     let mut changed = Vec::with_capacity(width);
     for x in 0..width {
-        map.set_cost(x, 8, -1);
+        map.set_cost(x, 8, None);
         changed.push((x, 8));
     }
     #[cfg(feature = "parallel")]
@@ -225,6 +259,62 @@ fn bench_update_pathcache(c: &mut Criterion) {
             BatchSize::SmallInput,
         )
     });
+
+    // A small change that only affects a few Chunks
+    let mut changed = Vec::with_capacity(8);
+    for y in 60..68 {
+        map.set_cost(100, y, None);
+        changed.push((100, y));
+    }
+    let id = format!(
+        "Update cache, Small Change, Single Threaded, Map Size: ({}, {}), Cache Size: {}",
+        width, height, chunk_size
+    );
+    group.bench_function(&id, |b| {
+        b.iter_batched_ref(
+            || pathcache.clone(),
+            |cache| cache.tiles_changed_with_fn_mut(&changed, map.cost_fn()),
+            BatchSize::SmallInput,
+        )
+    });
+
+    // The landmarks are only recalculated by the first search after the change
+    let landmarks = 8;
+    let pathcache = PathCache::new(
+        (width, height),
+        map.cost_fn(),
+        MooreNeighborhood::new(width, height),
+        PathCacheConfig {
+            landmarks,
+            ..PathCacheConfig::with_chunk_size(chunk_size)
+        },
+    );
+    pathcache.find_path((40, 90), (900, 600), map.cost_fn());
+    let id = format!(
+        "Update cache, Small Change, {} Landmarks, Map Size: ({}, {}), Cache Size: {}",
+        landmarks, width, height, chunk_size
+    );
+    group.bench_function(&id, |b| {
+        b.iter_batched_ref(
+            || pathcache.clone(),
+            |cache| cache.tiles_changed_with_fn_mut(&changed, map.cost_fn()),
+            BatchSize::SmallInput,
+        )
+    });
+    let id = format!(
+        "Update cache and Get Path, Small Change, {} Landmarks, Map Size: ({}, {}), Cache Size: {}",
+        landmarks, width, height, chunk_size
+    );
+    group.bench_function(&id, |b| {
+        b.iter_batched_ref(
+            || pathcache.clone(),
+            |cache| {
+                cache.tiles_changed_with_fn_mut(&changed, map.cost_fn());
+                cache.find_path((40, 90), (900, 600), map.cost_fn())
+            },
+            BatchSize::SmallInput,
+        )
+    });
 }
 
 fn bench_get_path(c: &mut Criterion) {
@@ -304,11 +394,146 @@ fn bench_get_path(c: &mut Criterion) {
     }
 }
 
+fn bench_get_short_paths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Get Short Paths");
+    group.sample_size(20);
+
+    let size = 1024;
+    let map = Map::new_random(size, size);
+    let neighborhood = MooreNeighborhood::new(size, size);
+    let chunk_size = 32;
+    let pathcache = PathCache::new(
+        (size, size),
+        map.cost_fn(),
+        neighborhood,
+        PathCacheConfig::with_chunk_size(chunk_size),
+    );
+    let pairs: Vec<_> = (0..200)
+        .map(|i| {
+            let start = (50 + (i * 37) % 900, 50 + (i * 53) % 900);
+            (start, (start.0 + 6, start.1 + 5))
+        })
+        .collect();
+
+    let id = format!(
+        "Get 200 Short Paths, Random Map, Map Size: ({}, {}), Cache Size: {}",
+        size, size, chunk_size
+    );
+    group.bench_function(&id, |b| {
+        b.iter(|| {
+            for &(start, goal) in &pairs {
+                pathcache.find_path(start, goal, map.cost_fn());
+            }
+        })
+    });
+
+    let id = format!(
+        "Get 200 Short Paths with Context, Random Map, Map Size: ({}, {}), Cache Size: {}",
+        size, size, chunk_size
+    );
+    let mut context = SearchContext::new();
+    group.bench_function(&id, |b| {
+        b.iter(|| {
+            for &(start, goal) in &pairs {
+                pathcache.find_path_with_context(start, goal, map.cost_fn(), &mut context);
+            }
+        })
+    });
+}
+
+fn bench_memory_usage(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Memory Usage");
+    group.sample_size(20);
+
+    let (width, height) = (1024, 1024);
+    let chunk_size = 32;
+    let (start, goal) = ((40, 90), (900, 600));
+
+    for (map_name, mut map) in [
+        ("Uniform", Map::new(width, height)),
+        ("Random", Map::new_random(width, height)),
+    ] {
+        // make sure that there is a Path to resolve
+        map.set_cost(start.0, start.1, Some(1));
+        map.set_cost(goal.0, goal.1, Some(1));
+
+        for (config_name, config) in [
+            ("Default", PathCacheConfig::with_chunk_size(chunk_size)),
+            (
+                "Compact Paths",
+                PathCacheConfig {
+                    compact_paths: true,
+                    ..PathCacheConfig::with_chunk_size(chunk_size)
+                },
+            ),
+            (
+                "No Cached Paths",
+                PathCacheConfig {
+                    cache_paths: false,
+                    ..PathCacheConfig::with_chunk_size(chunk_size)
+                },
+            ),
+            (
+                "LRU Paths",
+                PathCacheConfig {
+                    cache_paths: false,
+                    path_lru_budget: 16 << 20,
+                    ..PathCacheConfig::with_chunk_size(chunk_size)
+                },
+            ),
+            (
+                "Landmarks",
+                PathCacheConfig {
+                    landmarks: 8,
+                    ..PathCacheConfig::with_chunk_size(chunk_size)
+                },
+            ),
+            ("Low Mem", PathCacheConfig::LOW_MEM),
+            ("High Performance", PathCacheConfig::HIGH_PERFORMANCE),
+        ] {
+            let chunk_size = config.chunk_size;
+            let before = ALLOCATED.load(Relaxed);
+            let pathcache = PathCache::new(
+                (width, height),
+                map.cost_fn(),
+                MooreNeighborhood::new(width, height),
+                config,
+            );
+            let bytes = ALLOCATED.load(Relaxed).saturating_sub(before);
+            println!(
+                "Memory Usage, {} Map, {}, Map Size: ({}, {}), Cache Size: {}: {:.2} MB",
+                map_name,
+                config_name,
+                width,
+                height,
+                chunk_size,
+                bytes as f64 / (1024.0 * 1024.0)
+            );
+            println!("Memory Report: {}", pathcache.memory_usage());
+
+            // the cost of using the Paths with the different storage options
+            let id = format!(
+                "Resolve Path, {} Map, {}, Map Size: ({}, {}), Cache Size: {}",
+                map_name, config_name, width, height, chunk_size
+            );
+            group.bench_function(&id, |b| {
+                b.iter(|| {
+                    pathcache
+                        .find_path(start, goal, map.cost_fn())
+                        .map(|path| path.resolve(map.cost_fn()))
+                })
+            });
+        }
+    }
+}
+
 criterion_group!(
     benches,
     bench_create_pathcache,
     bench_update_pathcache,
-    bench_get_path
+    bench_get_path,
+    bench_get_short_paths,
+    bench_memory_usage
 );
 criterion_main!(benches);
 
@@ -319,11 +544,11 @@ type Point = (usize, usize);
 pub fn a_star_search<N: Neighborhood>(
     neighborhood: &N,
     mut valid: impl FnMut(Point) -> bool,
-    mut get_cost: impl FnMut(Point) -> isize,
+    mut get_cost: impl FnMut(Point) -> Option<usize>,
     start: Point,
     goal: Point,
 ) -> Option<Vec<Point>> {
-    if get_cost(start) < 0 {
+    if get_cost(start).is_none() {
         return None;
     }
     if start == goal {
@@ -346,11 +571,12 @@ pub fn a_star_search<N: Neighborhood>(
             Ordering::Less => panic!("Binary Heap failed"),
         }
 
-        let delta_cost = get_cost(current_id);
-        if delta_cost < 0 {
+        let delta_cost = if let Some(cost) = get_cost(current_id) {
+            cost
+        } else {
             continue;
-        }
-        let other_cost = current_cost + delta_cost as usize;
+        };
+        let other_cost = current_cost + delta_cost;
 
         all_neighbors.clear();
         neighborhood.get_all_neighbors(current_id, &mut all_neighbors);
@@ -358,7 +584,7 @@ pub fn a_star_search<N: Neighborhood>(
             if !valid(other_id) {
                 continue;
             }
-            if get_cost(other_id) < 0 && other_id != goal {
+            if get_cost(other_id).is_none() && other_id != goal {
                 continue;
             }
 
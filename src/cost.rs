@@ -0,0 +1,261 @@
+//! The types that can be used for the cost of walking over a Tile
+
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display};
+use std::ops::{Add, Sub};
+
+/// A type that can be used for the cost of walking over a Tile and the total cost of a Path.
+///
+/// Implemented for [`u32`], [`u64`], [`usize`] and [`OrderedFloat`].
+///
+/// Costs are expected to be non-negative, since the underlying searches (A* and Dijkstra) cannot
+/// handle negative weights. Costs below `1` (including `0`) are fine: The
+/// [`heuristic`](crate::neighbors::Neighborhood::heuristic) of A* is scaled by the lowest cost of
+/// any Tile, so that it never overestimates the remaining cost.
+///
+/// Costs never wrap around: A Path whose total cost would overflow is treated as if it didn't
+/// exist, and sums that are only used for estimates saturate instead.
+pub trait Cost:
+    Copy + Ord + Debug + Display + Send + Sync + Add<Output = Self> + Sub<Output = Self> + 'static
+{
+    /// The cost of a Path that doesn't go anywhere
+    const ZERO: Self;
+
+    /// Converts a number of steps into a cost.
+    ///
+    /// This is used for the [`heuristic`](crate::neighbors::Neighborhood::heuristic) of a
+    /// Neighborhood and for comparisons with [`chunk_size`](crate::PathCacheConfig::chunk_size).
+    fn from_usize(n: usize) -> Self;
+
+    /// Adds two costs, returning `None` if the result cannot be represented.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    /// Adds two costs, returning the largest representable cost if the result would overflow.
+    #[must_use]
+    fn saturating_add(self, rhs: Self) -> Self;
+
+    /// Multiplies the cost with a non-negative `factor`, rounding down and returning the largest
+    /// representable cost if the result would overflow.
+    ///
+    /// This is used to turn the number of steps of the
+    /// [`heuristic`](crate::neighbors::Neighborhood::heuristic) into a cost, based on the lowest
+    /// cost of any Tile, and for the [`heuristic_weight`](crate::PathCacheConfig::heuristic_weight).
+    #[must_use]
+    fn scale(self, factor: f64) -> Self;
+}
+
+macro_rules! impl_cost {
+    ($($t: ty),*) => {
+        $(
+            impl Cost for $t {
+                const ZERO: Self = 0;
+
+                fn from_usize(n: usize) -> Self {
+                    <$t>::try_from(n).unwrap_or(<$t>::MAX)
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$t>::saturating_add(self, rhs)
+                }
+
+                #[allow(clippy::cast_lossless)]
+                fn scale(self, factor: f64) -> Self {
+                    <$t>::try_from(scale_exact(self as u128, factor)).unwrap_or(<$t>::MAX)
+                }
+            }
+        )*
+    };
+}
+impl_cost!(u32, u64, usize);
+
+/// Calculates `value * factor` rounded down, or `u128::MAX` if the result would overflow.
+///
+/// Converting `value` to `f64` would round it once it exceeds 2^53, possibly upwards, so `factor`
+/// is split into its integer mantissa and exponent instead. `value` must be below 2^64.
+#[allow(clippy::cast_possible_truncation)]
+fn scale_exact(value: u128, factor: f64) -> u128 {
+    if value == 0 || factor.is_nan() || factor <= 0.0 {
+        return 0;
+    }
+    if factor.is_infinite() {
+        return u128::MAX;
+    }
+    // factor == mantissa * 2^exponent
+    let bits = factor.to_bits();
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = u128::from(bits & ((1 << 52) - 1));
+    let (mantissa, exponent) = if biased_exponent == 0 {
+        (fraction, -1074) // subnormal
+    } else {
+        (fraction | 1 << 52, biased_exponent - 1075)
+    };
+    // value < 2^64 and mantissa < 2^53, so the product fits
+    let product = value * mantissa;
+    let shift = exponent.unsigned_abs();
+    if exponent < 0 {
+        product.checked_shr(shift).unwrap_or(0)
+    } else if product.leading_zeros() < shift {
+        u128::MAX
+    } else {
+        product << shift
+    }
+}
+
+/// A wrapper around [`f64`] that implements [`Ord`], so that it can be used as a [`Cost`].
+///
+/// The ordering is the one of [`f64::total_cmp`]. An infinite sum counts as an overflow, so
+/// [`f64::INFINITY`] can not be used as the cost of a Tile (use `None` for solid Tiles instead).
+///
+/// ## Examples
+/// Basic usage:
+/// ```
+/// # use hierarchical_pathfinding::prelude::*;
+/// # let mut grid = [
+/// #     [0, 2, 0, 0, 0],
+/// #     [0, 2, 2, 2, 2],
+/// #     [0, 1, 0, 0, 0],
+/// #     [0, 1, 0, 2, 0],
+/// #     [0, 0, 0, 2, 0],
+/// # ];
+/// # let (width, height) = (grid[0].len(), grid.len());
+/// const COST_MAP: [Option<OrderedFloat>; 3] = [
+///     Some(OrderedFloat(1.0)),
+///     Some(OrderedFloat(2.5)),
+///     None,
+/// ];
+///
+/// let pathfinding = PathCache::new(
+///     (width, height),
+///     |(x, y)| COST_MAP[grid[y][x]],
+///     ManhattanNeighborhood::new(width, height),
+///     PathCacheConfig::with_chunk_size(3),
+/// );
+///
+/// let path = pathfinding
+///     .find_path((0, 0), (4, 4), |(x, y)| COST_MAP[grid[y][x]])
+///     .unwrap();
+/// assert_eq!(path.cost(), OrderedFloat(9.5));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OrderedFloat(pub f64);
+
+impl Cost for OrderedFloat {
+    const ZERO: Self = OrderedFloat(0.0);
+
+    #[allow(clippy::cast_precision_loss)]
+    fn from_usize(n: usize) -> Self {
+        OrderedFloat(n as f64)
+    }
+
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        let sum = self.0 + rhs.0;
+        if sum.is_finite() {
+            Some(OrderedFloat(sum))
+        } else {
+            None
+        }
+    }
+
+    fn saturating_add(self, rhs: Self) -> Self {
+        OrderedFloat((self.0 + rhs.0).min(f64::MAX))
+    }
+
+    fn scale(self, factor: f64) -> Self {
+        OrderedFloat((self.0 * factor).min(f64::MAX))
+    }
+}
+
+impl PartialEq for OrderedFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for OrderedFloat {}
+
+impl PartialOrd for OrderedFloat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for OrderedFloat {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Add for OrderedFloat {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        OrderedFloat(self.0 + rhs.0)
+    }
+}
+impl Sub for OrderedFloat {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        OrderedFloat(self.0 - rhs.0)
+    }
+}
+
+impl From<f64> for OrderedFloat {
+    fn from(value: f64) -> Self {
+        OrderedFloat(value)
+    }
+}
+
+impl Display for OrderedFloat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordered_float() {
+        let mut values = [2.5, -0.0, 0.0, 1.0].map(OrderedFloat);
+        values.sort();
+        assert_eq!(values.map(|v| v.0), [-0.0, 0.0, 1.0, 2.5]);
+
+        assert_eq!(OrderedFloat(1.5) + OrderedFloat(2.0), OrderedFloat(3.5));
+        assert_eq!(OrderedFloat::from_usize(3), OrderedFloat(3.0));
+        assert_eq!(format!("{}", OrderedFloat(1.5)), "1.5");
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(Cost::checked_add(u32::MAX - 1, 1), Some(u32::MAX));
+        assert_eq!(Cost::checked_add(u32::MAX, 1), None);
+        assert_eq!(Cost::saturating_add(usize::MAX, 5), usize::MAX);
+
+        let max = OrderedFloat(f64::MAX);
+        assert_eq!(max.checked_add(OrderedFloat(1.0)), Some(max)); // precision loss, not overflow
+        assert_eq!(max.checked_add(max), None);
+        assert_eq!(max.saturating_add(max), max);
+    }
+
+    #[test]
+    fn scale() {
+        assert_eq!(Cost::scale(3u32, 2.5), 7);
+        assert_eq!(Cost::scale(3usize, 0.0), 0);
+        assert_eq!(Cost::scale(u64::MAX / 2, 3.0), u64::MAX);
+        assert_eq!(Cost::scale(7u32, f64::INFINITY), u32::MAX);
+        assert_eq!(Cost::scale(7u32, f64::NAN), 0);
+        assert_eq!(Cost::scale(7u32, f64::MIN_POSITIVE), 0);
+        assert_eq!(Cost::scale(1u64 << 40, 2f64.powi(-40)), 1);
+        assert_eq!(Cost::scale(u64::MAX, 1.0), u64::MAX);
+
+        // above 2^53, converting the cost to f64 would round it up
+        let large = (1u64 << 60) - 1;
+        assert_eq!(Cost::scale(large, 1.0), large);
+        assert_eq!(Cost::scale(large, 0.5), large / 2);
+        assert_eq!(Cost::scale(large, 1.5), large + large / 2);
+        assert_eq!(OrderedFloat(0.1).scale(4.0), OrderedFloat(0.4));
+        assert_eq!(OrderedFloat(f64::MAX).scale(2.0), OrderedFloat(f64::MAX));
+    }
+}
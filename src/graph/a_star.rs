@@ -1,25 +1,82 @@
 use super::*;
-use crate::neighbors::Neighborhood;
+use crate::{cost::Cost, grid::SearchProgress, neighbors::Neighborhood};
 
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
 
-pub(crate) fn a_star_search<N: Neighborhood>(
-    nodes: &NodeList,
+pub(crate) fn a_star_search<N: Neighborhood, C: Cost>(
+    buffers: &mut SearchBuffers<NodeID, C>,
+    nodes: &NodeList<C>,
     start: NodeID,
     goal: NodeID,
     neighborhood: &N,
-    size_hint: usize,
-) -> Option<Path<NodeID>> {
+    landmarks: Option<&Landmarks<C>>,
+    heuristic: Heuristic<C>,
+) -> Option<Path<NodeID, C>> {
     if start == goal {
-        return Some(Path::from_slice(&[start, start], 0));
+        return Some(Path::from_slice(&[start, start], C::ZERO));
     }
-    let mut visited = NodeIDMap::with_capacity(size_hint);
-    let mut next = BinaryHeap::with_capacity(size_hint / 2);
-    next.push(HeuristicElement(start, 0, 0));
-    visited.insert(start, (0, start));
+    a_star_begin(buffers, nodes, start);
+    let mut budget = usize::MAX;
+    let progress = a_star_continue(
+        buffers,
+        nodes,
+        start,
+        goal,
+        neighborhood,
+        landmarks,
+        heuristic,
+        &mut budget,
+    );
+    match progress {
+        SearchProgress::Done(path) => path,
+        SearchProgress::InProgress => unreachable!("the search was not limited"),
+    }
+}
+
+/// Prepares `buffers` for an A* search from `start` that is performed by [`a_star_continue`]
+pub(crate) fn a_star_begin<C: Cost>(
+    buffers: &mut SearchBuffers<NodeID, C>,
+    nodes: &NodeList<C>,
+    start: NodeID,
+) {
+    let SearchBuffers {
+        visited,
+        a_star_next: next,
+        ..
+    } = buffers.reset((0, 0), (nodes.id_bound(), 1));
+    next.push(HeuristicElement(start, C::ZERO, C::ZERO));
+    visited.insert(start, (C::ZERO, start));
+}
+
+/// Continues the A* search in `buffers` until it is done or `budget` Nodes were expanded.
+///
+/// `budget` is reduced by the number of expanded Nodes.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn a_star_continue<N: Neighborhood, C: Cost>(
+    buffers: &mut SearchBuffers<NodeID, C>,
+    nodes: &NodeList<C>,
+    start: NodeID,
+    goal: NodeID,
+    neighborhood: &N,
+    landmarks: Option<&Landmarks<C>>,
+    heuristic: Heuristic<C>,
+    budget: &mut usize,
+) -> SearchProgress<NodeID, C> {
+    let SearchBuffers {
+        visited,
+        a_star_next: next,
+        ..
+    } = buffers;
 
-    while let Some(HeuristicElement(current_id, current_cost, _)) = next.pop() {
+    let goal_pos = nodes[goal].pos;
+
+    loop {
+        if *budget == 0 {
+            return SearchProgress::InProgress;
+        }
+        let Some(HeuristicElement(current_id, current_cost, _)) = next.pop() else {
+            break;
+        };
         if current_id == goal {
             break;
         }
@@ -28,11 +85,15 @@ pub(crate) fn a_star_search<N: Neighborhood>(
             Ordering::Equal => {}
             Ordering::Less => panic!("Binary Heap failed"),
         }
+        *budget -= 1;
 
         let current = &nodes[current_id];
 
         for (&other_id, path) in current.edges.iter() {
-            let other_cost = current_cost + path.cost();
+            let Some(other_cost) = current_cost.checked_add(path.cost()) else {
+                // any Path through here would overflow => treat as unreachable
+                continue;
+            };
             let other = &nodes[other_id];
 
             let mut needs_visit = true;
@@ -48,20 +109,117 @@ pub(crate) fn a_star_search<N: Neighborhood>(
             }
 
             if needs_visit {
-                let heuristic = neighborhood.heuristic(current.pos, other.pos);
+                // the weight is applied to the best lower bound, so that it also speeds up the
+                // searches that are guided by the landmarks
+                let mut bound = heuristic.bound(neighborhood.heuristic(other.pos, goal_pos));
+                if let Some(landmarks) = landmarks {
+                    bound = bound.max(landmarks.lower_bound(other_id, goal));
+                }
+                let estimate = heuristic.weighted(bound);
                 next.push(HeuristicElement(
                     other_id,
                     other_cost,
-                    other_cost + heuristic,
+                    other_cost.saturating_add(estimate),
                 ));
             }
         }
     }
 
     if !visited.contains_key(&goal) {
-        return None;
+        return SearchProgress::Done(None);
+    }
+
+    let steps = {
+        let mut steps = vec![];
+        let mut current = goal;
+
+        while current != start {
+            steps.push(current);
+            let (_, prev) = visited[&current];
+            current = prev;
+        }
+        steps.push(start);
+        steps.reverse();
+        steps
+    };
+
+    SearchProgress::Done(Some(Path::new(steps, visited[&goal].0)))
+}
+
+/// Finds the Node that minimizes the cost of reaching it from `start` plus the cost that
+/// `goal_cost` returns for it.
+///
+/// `goal_cost` returns the remaining cost to a goal and some associated data for every Node that
+/// can reach a goal, and `heuristic` is a lower bound for that remaining cost from any Node. Only
+/// totals below `bound` are considered. Returns the Path to the best Node, the total cost and
+/// the data.
+pub(crate) fn a_star_to_any<C: Cost, T>(
+    buffers: &mut SearchBuffers<NodeID, C>,
+    nodes: &NodeList<C>,
+    start: NodeID,
+    mut bound: Option<C>,
+    mut heuristic: impl FnMut(NodeID) -> C,
+    mut goal_cost: impl FnMut(NodeID) -> Option<(C, T)>,
+) -> Option<(Path<NodeID, C>, C, T)> {
+    let SearchBuffers {
+        visited,
+        a_star_next: next,
+        ..
+    } = buffers.reset((0, 0), (nodes.id_bound(), 1));
+    next.push(HeuristicElement(start, C::ZERO, heuristic(start)));
+    visited.insert(start, (C::ZERO, start));
+
+    let mut best = None;
+
+    while let Some(HeuristicElement(current_id, current_cost, estimate)) = next.pop() {
+        if bound.is_some_and(|bound| estimate >= bound) {
+            // every remaining Node is at least as expensive
+            break;
+        }
+        match current_cost.cmp(&visited[&current_id].0) {
+            Ordering::Greater => continue,
+            Ordering::Equal => {}
+            Ordering::Less => panic!("Binary Heap failed"),
+        }
+
+        if let Some((rest, data)) = goal_cost(current_id) {
+            if let Some(total) = current_cost.checked_add(rest) {
+                if bound.is_none_or(|bound| total < bound) {
+                    bound = Some(total);
+                    best = Some((current_id, total, data));
+                }
+            }
+        }
+
+        for (&other_id, path) in nodes[current_id].edges.iter() {
+            let Some(other_cost) = current_cost.checked_add(path.cost()) else {
+                // any Path through here would overflow => treat as unreachable
+                continue;
+            };
+
+            let mut needs_visit = true;
+            if let Some((prev_cost, prev_id)) = visited.get_mut(&other_id) {
+                if *prev_cost > other_cost {
+                    *prev_cost = other_cost;
+                    *prev_id = current_id;
+                } else {
+                    needs_visit = false;
+                }
+            } else {
+                visited.insert(other_id, (other_cost, current_id));
+            }
+
+            if needs_visit {
+                next.push(HeuristicElement(
+                    other_id,
+                    other_cost,
+                    other_cost.saturating_add(heuristic(other_id)),
+                ));
+            }
+        }
     }
 
+    let (goal, total, data) = best?;
     let steps = {
         let mut steps = vec![];
         let mut current = goal;
@@ -76,5 +234,5 @@ pub(crate) fn a_star_search<N: Neighborhood>(
         steps
     };
 
-    Some(Path::new(steps, visited[&goal].0))
+    Some((Path::new(steps, visited[&goal].0), total, data))
 }
@@ -0,0 +1,234 @@
+use super::{dijkstra_edge_lists, Element, NodeID, NodeIDMap, NodeIDSet, NodeList, Path};
+use crate::cost::Cost;
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// The maximum number of Nodes that a witness search looks at before giving up.
+///
+/// Giving up early only adds shortcuts that might not be needed, so this trades a slightly bigger
+/// hierarchy for a faster construction.
+const WITNESS_SETTLE_LIMIT: usize = 64;
+
+/// A Contraction Hierarchy over the Nodes of a [`NodeList`]
+///
+/// The Nodes are contracted one after another, adding shortcuts between their neighbors wherever
+/// the contracted Node was part of the only cheapest Path. Afterwards, the cheapest Path between
+/// any two Nodes only ever goes "up" the hierarchy and then "down" again, so a search from both
+/// ends only has to look at a tiny part of the Graph.
+#[derive(Clone, Debug)]
+pub(crate) struct ContractionHierarchy<C> {
+    /// the edges from every Node to Nodes that were contracted after it, as `(target, cost)`
+    up: Vec<Vec<(NodeID, C)>>,
+    /// the edges to every Node from Nodes that were contracted after it, as `(source, cost)`
+    down: Vec<Vec<(NodeID, C)>>,
+    /// the Node that is skipped by the shortcut between two Nodes
+    middles: hashbrown::HashMap<(NodeID, NodeID), NodeID>,
+}
+
+/// The edges of the Graph that is left during the construction
+struct Remaining<C> {
+    /// the outgoing edges of every Node
+    out: Vec<NodeIDMap<C>>,
+    /// the incoming edges of every Node
+    incoming: Vec<NodeIDMap<C>>,
+}
+
+impl<C: Cost> Remaining<C> {
+    /// Finds the shortcuts that are needed to contract `node`, as `(source, target, cost)`
+    fn shortcuts(&self, node: NodeID) -> Vec<(NodeID, NodeID, C)> {
+        let mut shortcuts = vec![];
+        for (&source, &in_cost) in &self.incoming[node] {
+            let targets = self.out[node]
+                .iter()
+                .filter(|(&target, _)| target != source)
+                .filter_map(|(&target, &out_cost)| {
+                    in_cost.checked_add(out_cost).map(|cost| (target, cost))
+                })
+                .collect::<Vec<_>>();
+            let Some(max_cost) = targets.iter().map(|(_, cost)| *cost).max() else {
+                continue;
+            };
+
+            let witnesses = self.witness_search(source, node, max_cost);
+            for (target, cost) in targets {
+                if witnesses.get(&target).is_none_or(|&witness| witness > cost) {
+                    shortcuts.push((source, target, cost));
+                }
+            }
+        }
+        shortcuts
+    }
+
+    /// Finds the cost of the Paths from `start` that don't go through `ignored`, up to `max_cost`
+    fn witness_search(&self, start: NodeID, ignored: NodeID, max_cost: C) -> NodeIDMap<C> {
+        let mut costs = NodeIDMap::default();
+        let mut settled = NodeIDSet::default();
+        let mut next = BinaryHeap::new();
+        costs.insert(start, C::ZERO);
+        next.push(Element(start, C::ZERO));
+
+        while let Some(Element(current, current_cost)) = next.pop() {
+            if !settled.insert(current) {
+                continue;
+            }
+            if settled.len() > WITNESS_SETTLE_LIMIT {
+                break;
+            }
+            for (&other, &cost) in &self.out[current] {
+                if other == ignored {
+                    continue;
+                }
+                let other_cost = match current_cost.checked_add(cost) {
+                    Some(cost) if cost <= max_cost => cost,
+                    _ => continue,
+                };
+                if costs.get(&other).is_none_or(|&prev| prev > other_cost) {
+                    costs.insert(other, other_cost);
+                    next.push(Element(other, other_cost));
+                }
+            }
+        }
+        costs
+    }
+
+    /// The priority of contracting `node`. Nodes with a lower priority are contracted first.
+    fn priority(&self, node: NodeID, num_shortcuts: usize, contracted_neighbors: usize) -> isize {
+        let removed = self.out[node].len() + self.incoming[node].len();
+        num_shortcuts as isize - removed as isize + contracted_neighbors as isize
+    }
+}
+
+impl<C: Cost> ContractionHierarchy<C> {
+    /// Contracts all Nodes of `nodes`
+    pub fn new(nodes: &NodeList<C>) -> ContractionHierarchy<C> {
+        let size = nodes.id_bound();
+        let mut remaining = Remaining {
+            out: vec![NodeIDMap::default(); size],
+            incoming: vec![NodeIDMap::default(); size],
+        };
+        for (id, node) in nodes.iter() {
+            for (&other_id, path) in node.edges.iter() {
+                remaining.out[id].insert(other_id, path.cost());
+                remaining.incoming[other_id].insert(id, path.cost());
+            }
+        }
+
+        let mut hierarchy = ContractionHierarchy {
+            up: vec![vec![]; size],
+            down: vec![vec![]; size],
+            middles: hashbrown::HashMap::new(),
+        };
+
+        let mut contracted_neighbors = vec![0; size];
+        let mut queue = BinaryHeap::new();
+        for (id, _) in nodes.iter() {
+            let num_shortcuts = remaining.shortcuts(id).len();
+            queue.push(Reverse((remaining.priority(id, num_shortcuts, 0), id)));
+        }
+
+        while let Some(Reverse((_, node))) = queue.pop() {
+            // the priority might have changed since the Node was added, so it is checked again
+            // before contracting the Node (lazy updates)
+            let shortcuts = remaining.shortcuts(node);
+            let priority = remaining.priority(node, shortcuts.len(), contracted_neighbors[node]);
+            if let Some(Reverse((next_priority, _))) = queue.peek() {
+                if priority > *next_priority {
+                    queue.push(Reverse((priority, node)));
+                    continue;
+                }
+            }
+
+            let out = std::mem::take(&mut remaining.out[node]);
+            let incoming = std::mem::take(&mut remaining.incoming[node]);
+            for &target in out.keys() {
+                remaining.incoming[target].remove(&node);
+                contracted_neighbors[target] += 1;
+            }
+            for &source in incoming.keys() {
+                remaining.out[source].remove(&node);
+                contracted_neighbors[source] += 1;
+            }
+            hierarchy.up[node] = out.into_iter().collect();
+            hierarchy.down[node] = incoming.into_iter().collect();
+
+            for (source, target, cost) in shortcuts {
+                let existing = remaining.out[source].get(&target);
+                if existing.is_none_or(|&existing| existing > cost) {
+                    remaining.out[source].insert(target, cost);
+                    remaining.incoming[target].insert(source, cost);
+                    hierarchy.middles.insert((source, target), node);
+                }
+            }
+        }
+
+        hierarchy
+    }
+
+    /// Finds the cheapest Path from `start` to `goal`, in the same format as [`a_star_search`]
+    pub fn path(&self, start: NodeID, goal: NodeID) -> Option<Path<NodeID, C>> {
+        if start == goal {
+            return Some(Path::from_slice(&[start, start], C::ZERO));
+        }
+        if start >= self.up.len() || goal >= self.up.len() {
+            return None;
+        }
+
+        let forward = dijkstra_edge_lists(&self.up, start);
+        let backward = dijkstra_edge_lists(&self.down, goal);
+
+        let (meeting, cost) = forward
+            .iter()
+            .filter_map(|(&id, &(forward_cost, _))| {
+                let (backward_cost, _) = backward.get(&id)?;
+                forward_cost
+                    .checked_add(*backward_cost)
+                    .map(|cost| (id, cost))
+            })
+            .min_by_key(|&(_, cost)| cost)?;
+
+        // the Nodes on the way up from start and back down to goal, which may be shortcuts
+        let mut hierarchy_path = vec![meeting];
+        let mut current = meeting;
+        while current != start {
+            current = forward[&current].1;
+            hierarchy_path.push(current);
+        }
+        hierarchy_path.reverse();
+        let mut current = meeting;
+        while current != goal {
+            current = backward[&current].1;
+            hierarchy_path.push(current);
+        }
+
+        let mut steps = vec![start];
+        let mut stack = vec![];
+        for pair in hierarchy_path.windows(2) {
+            stack.push((pair[0], pair[1]));
+            while let Some((a, b)) = stack.pop() {
+                if let Some(&middle) = self.middles.get(&(a, b)) {
+                    stack.push((middle, b));
+                    stack.push((a, middle));
+                } else {
+                    steps.push(b);
+                }
+            }
+        }
+
+        Some(Path::new(steps, cost))
+    }
+
+    /// The number of bytes allocated for the hierarchy
+    pub fn heap_size(&self) -> usize {
+        let edges = self
+            .up
+            .iter()
+            .chain(&self.down)
+            .map(|edges| edges.capacity() * size_of::<(NodeID, C)>())
+            .sum::<usize>();
+        let lists = (self.up.capacity() + self.down.capacity()) * size_of::<Vec<(NodeID, C)>>();
+        // one control byte per bucket
+        let middles = self.middles.capacity() * (size_of::<((NodeID, NodeID), NodeID)>() + 1);
+        edges + lists + middles
+    }
+}
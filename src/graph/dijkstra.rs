@@ -1,19 +1,22 @@
 use super::*;
+use crate::cost::Cost;
 
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
 
-pub(crate) fn dijkstra_search(
-    nodes: &NodeList,
+pub(crate) fn dijkstra_search<C: Cost>(
+    buffers: &mut SearchBuffers<NodeID, C>,
+    nodes: &NodeList<C>,
     start: NodeID,
     goals: &[NodeID],
     only_closest_goal: bool,
-    size_hint: usize,
-) -> NodeIDMap<Path<NodeID>> {
-    let mut visited = NodeIDMap::with_capacity(size_hint);
-    let mut next = BinaryHeap::with_capacity(size_hint / 2);
-    next.push(Element(start, 0));
-    visited.insert(start, (0, start));
+) -> NodeIDMap<Path<NodeID, C>> {
+    let SearchBuffers {
+        visited,
+        dijkstra_next: next,
+        ..
+    } = buffers.reset((0, 0), (nodes.id_bound(), 1));
+    next.push(Element(start, C::ZERO));
+    visited.insert(start, (C::ZERO, start));
 
     let mut remaining_goals: NodeIDSet = goals.iter().copied().collect();
 
@@ -36,7 +39,10 @@ pub(crate) fn dijkstra_search(
         let current = &nodes[current_id];
 
         for (&other_id, path) in current.edges.iter() {
-            let other_cost = current_cost + path.cost();
+            let Some(other_cost) = current_cost.checked_add(path.cost()) else {
+                // any Path through here would overflow => treat as unreachable
+                continue;
+            };
 
             let mut needs_visit = true;
             if let Some((prev_cost, prev_id)) = visited.get_mut(&other_id) {
@@ -77,3 +83,184 @@ pub(crate) fn dijkstra_search(
 
     goal_data
 }
+
+/// Finds the cost of every Node that can be reached from one of the `starts` with a total cost of
+/// at most `max_cost`, or of every reachable Node if `max_cost` is `None`.
+///
+/// `starts` contains the Nodes to start from and the cost of reaching them. Returns the cost of
+/// every reached Node and the Node before it on the cheapest Path, which is the Node itself for
+/// the `starts`.
+pub(crate) fn dijkstra_within<C: Cost>(
+    buffers: &mut SearchBuffers<NodeID, C>,
+    nodes: &NodeList<C>,
+    starts: &[(NodeID, C)],
+    max_cost: Option<C>,
+) -> NodeIDMap<(C, NodeID)> {
+    let SearchBuffers {
+        visited,
+        dijkstra_next: next,
+        ..
+    } = buffers.reset((0, 0), (nodes.id_bound(), 1));
+
+    for &(start, cost) in starts {
+        if max_cost.is_none_or(|max| cost <= max)
+            && visited.get(&start).is_none_or(|(prev, _)| *prev > cost)
+        {
+            visited.insert(start, (cost, start));
+            next.push(Element(start, cost));
+        }
+    }
+
+    let mut reached = NodeIDMap::default();
+
+    while let Some(Element(current_id, current_cost)) = next.pop() {
+        match current_cost.cmp(&visited[&current_id].0) {
+            Ordering::Greater => continue,
+            Ordering::Equal => {}
+            Ordering::Less => panic!("Binary Heap failed"),
+        }
+        reached.insert(current_id, visited[&current_id]);
+
+        for (&other_id, path) in nodes[current_id].edges.iter() {
+            let other_cost = match current_cost.checked_add(path.cost()) {
+                Some(cost) if max_cost.is_none_or(|max| cost <= max) => cost,
+                // too expensive, or any Path through here would overflow
+                _ => continue,
+            };
+
+            let mut needs_visit = true;
+            if let Some((prev_cost, prev_id)) = visited.get_mut(&other_id) {
+                if *prev_cost > other_cost {
+                    *prev_cost = other_cost;
+                    *prev_id = current_id;
+                } else {
+                    needs_visit = false;
+                }
+            } else {
+                visited.insert(other_id, (other_cost, current_id));
+            }
+
+            if needs_visit {
+                next.push(Element(other_id, other_cost));
+            }
+        }
+    }
+
+    reached
+}
+
+/// Finds the cheapest Path to `goal` from any of the `starts`, which contain the Nodes to start
+/// from and the cost of reaching them.
+///
+/// The cost of the returned Path only contains the edges between the Nodes, not the cost of the
+/// start it begins at.
+pub(crate) fn dijkstra_from_any<C: Cost>(
+    buffers: &mut SearchBuffers<NodeID, C>,
+    nodes: &NodeList<C>,
+    starts: &[(NodeID, C)],
+    goal: NodeID,
+) -> Option<Path<NodeID, C>> {
+    let SearchBuffers {
+        visited,
+        dijkstra_next: next,
+        ..
+    } = buffers.reset((0, 0), (nodes.id_bound(), 1));
+
+    for &(start, cost) in starts {
+        if visited.get(&start).is_none_or(|(prev, _)| *prev > cost) {
+            visited.insert(start, (cost, start));
+            next.push(Element(start, cost));
+        }
+    }
+
+    let mut found = false;
+    while let Some(Element(current_id, current_cost)) = next.pop() {
+        match current_cost.cmp(&visited[&current_id].0) {
+            Ordering::Greater => continue,
+            Ordering::Equal => {}
+            Ordering::Less => panic!("Binary Heap failed"),
+        }
+        if current_id == goal {
+            found = true;
+            break;
+        }
+
+        for (&other_id, path) in nodes[current_id].edges.iter() {
+            let Some(other_cost) = current_cost.checked_add(path.cost()) else {
+                // any Path through here would overflow => treat as unreachable
+                continue;
+            };
+
+            let mut needs_visit = true;
+            if let Some((prev_cost, prev_id)) = visited.get_mut(&other_id) {
+                if *prev_cost > other_cost {
+                    *prev_cost = other_cost;
+                    *prev_id = current_id;
+                } else {
+                    needs_visit = false;
+                }
+            } else {
+                visited.insert(other_id, (other_cost, current_id));
+            }
+
+            if needs_visit {
+                next.push(Element(other_id, other_cost));
+            }
+        }
+    }
+    if !found {
+        return None;
+    }
+
+    let mut steps = vec![goal];
+    let mut cost = C::ZERO;
+    let mut current = goal;
+    loop {
+        let (_, prev) = visited[&current];
+        if prev == current {
+            // reached one of the starts
+            break;
+        }
+        cost = cost.saturating_add(nodes[prev].edges[&current].cost());
+        steps.push(prev);
+        current = prev;
+    }
+    steps.reverse();
+
+    Some(Path::new(steps, cost))
+}
+
+/// Finds the cost of every Node that can be reached from `start` by following `edges`, and the
+/// Node before it.
+///
+/// `edges[id]` contains the `(target, cost)` pairs of the edges that start at `id`, which allows
+/// searching Graphs other than the [`NodeList`] itself, like one with all edges reversed.
+pub(crate) fn dijkstra_edge_lists<C: Cost>(
+    edges: &[Vec<(NodeID, C)>],
+    start: NodeID,
+) -> NodeIDMap<(C, NodeID)> {
+    let mut visited = NodeIDMap::default();
+    let mut next = std::collections::BinaryHeap::new();
+    visited.insert(start, (C::ZERO, start));
+    next.push(Element(start, C::ZERO));
+
+    while let Some(Element(current, current_cost)) = next.pop() {
+        if current_cost > visited[&current].0 {
+            continue;
+        }
+        for &(other, cost) in &edges[current] {
+            let Some(other_cost) = current_cost.checked_add(cost) else {
+                // any Path through here would overflow => treat as unreachable
+                continue;
+            };
+            if visited
+                .get(&other)
+                .is_none_or(|&(prev, _)| prev > other_cost)
+            {
+                visited.insert(other, (other_cost, current));
+                next.push(Element(other, other_cost));
+            }
+        }
+    }
+    visited
+}
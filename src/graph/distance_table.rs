@@ -0,0 +1,93 @@
+use super::{dijkstra_within, NodeID, NodeList, Path, SearchBuffers};
+use crate::cost::Cost;
+
+/// The cheapest Paths between all pairs of Nodes in a [`NodeList`]
+///
+/// Stores the cost of every Path and the Node before its goal, so a Path can be looked up without
+/// searching the Graph. This needs `O(n²)` Memory for `n` Nodes, which is why it is only useful
+/// for small Graphs.
+#[derive(Clone, Debug)]
+pub(crate) struct DistanceTable<C> {
+    /// the number of rows and columns, which is the `id_bound` of the Nodes
+    size: usize,
+    /// `entries[start * size + goal]` is the cost of the cheapest Path from `start` to `goal` and
+    /// the Node before `goal` on that Path
+    entries: Vec<Option<(C, NodeID)>>,
+}
+
+impl<C: Cost> DistanceTable<C> {
+    /// Calculates the cheapest Paths between all Nodes, using multiple threads if possible
+    pub fn new(nodes: &NodeList<C>) -> DistanceTable<C> {
+        let size = nodes.id_bound();
+        let mut entries = vec![None; size * size];
+
+        let mut present = vec![false; size];
+        for (id, _) in nodes.iter() {
+            present[id] = true;
+        }
+
+        let fill_row = |buffers: &mut SearchBuffers<NodeID, C>,
+                        (start, row): (NodeID, &mut [Option<(C, NodeID)>])| {
+            if !present[start] {
+                return;
+            }
+            for (goal, entry) in dijkstra_within(buffers, nodes, &[(start, C::ZERO)], None) {
+                row[goal] = Some(entry);
+            }
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            entries
+                .par_chunks_mut(size.max(1))
+                .enumerate()
+                .for_each_init(SearchBuffers::default, fill_row);
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            let mut buffers = SearchBuffers::default();
+            for row in entries.chunks_mut(size.max(1)).enumerate() {
+                fill_row(&mut buffers, row);
+            }
+        }
+
+        DistanceTable { size, entries }
+    }
+
+    fn entry(&self, start: NodeID, goal: NodeID) -> Option<(C, NodeID)> {
+        if start >= self.size || goal >= self.size {
+            return None;
+        }
+        self.entries[start * self.size + goal]
+    }
+
+    /// The cost of the cheapest Path from `start` to `goal`
+    pub fn cost(&self, start: NodeID, goal: NodeID) -> Option<C> {
+        self.entry(start, goal).map(|(cost, _)| cost)
+    }
+
+    /// The cheapest Path from `start` to `goal`, in the same format as [`a_star_search`]
+    pub fn path(&self, start: NodeID, goal: NodeID) -> Option<Path<NodeID, C>> {
+        if start == goal {
+            return Some(Path::from_slice(&[start, start], C::ZERO));
+        }
+        let (cost, _) = self.entry(start, goal)?;
+
+        let mut steps = vec![goal];
+        let mut current = goal;
+        while current != start {
+            let (_, prev) = self.entry(start, current)?;
+            current = prev;
+            steps.push(current);
+        }
+        steps.reverse();
+
+        Some(Path::new(steps, cost))
+    }
+
+    /// The number of bytes allocated for the table
+    pub fn heap_size(&self) -> usize {
+        self.entries.capacity() * size_of::<Option<(C, NodeID)>>()
+    }
+}
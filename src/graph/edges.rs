@@ -0,0 +1,78 @@
+use crate::{path::PathSegment, NodeID};
+
+/// The edges of a [`Node`](super::Node), sorted by the ID of the target Node.
+///
+/// Nodes only have a handful of edges, so a sorted `Vec` is both smaller and faster than a
+/// `HashMap`.
+#[derive(Clone, Debug)]
+pub(crate) struct Edges<C> {
+    edges: Vec<(NodeID, PathSegment<C>)>,
+}
+
+impl<C> Edges<C> {
+    pub fn new() -> Edges<C> {
+        Edges { edges: vec![] }
+    }
+
+    fn position(&self, id: NodeID) -> Result<usize, usize> {
+        self.edges.binary_search_by_key(&id, |(other, _)| *other)
+    }
+
+    pub fn get(&self, id: NodeID) -> Option<&PathSegment<C>> {
+        self.position(id).ok().map(|index| &self.edges[index].1)
+    }
+
+    pub fn contains_key(&self, id: NodeID) -> bool {
+        self.position(id).is_ok()
+    }
+
+    pub fn insert(&mut self, id: NodeID, path: PathSegment<C>) {
+        match self.position(id) {
+            Ok(index) => self.edges[index].1 = path,
+            Err(index) => self.edges.insert(index, (id, path)),
+        }
+    }
+
+    pub fn remove(&mut self, id: NodeID) {
+        if let Ok(index) = self.position(id) {
+            self.edges.remove(index);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.edges.clear();
+    }
+
+    /// The number of bytes allocated for the edges, without the Paths they contain
+    pub fn heap_size(&self) -> usize {
+        self.edges.capacity() * size_of::<(NodeID, PathSegment<C>)>()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&NodeID, &PathSegment<C>)> {
+        self.edges.iter().map(|(id, path)| (id, path))
+    }
+}
+
+impl<C> std::ops::Index<&NodeID> for Edges<C> {
+    type Output = PathSegment<C>;
+    #[track_caller]
+    fn index(&self, id: &NodeID) -> &PathSegment<C> {
+        self.get(*id).expect("Node has no edge to the given Node")
+    }
+}
+
+impl<C> IntoIterator for Edges<C> {
+    type Item = (NodeID, PathSegment<C>);
+    type IntoIter = std::vec::IntoIter<(NodeID, PathSegment<C>)>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.edges.into_iter()
+    }
+}
+
+impl<C> FromIterator<(NodeID, PathSegment<C>)> for Edges<C> {
+    fn from_iter<I: IntoIterator<Item = (NodeID, PathSegment<C>)>>(iter: I) -> Self {
+        let mut edges: Vec<_> = iter.into_iter().collect();
+        edges.sort_unstable_by_key(|(id, _)| *id);
+        Edges { edges }
+    }
+}
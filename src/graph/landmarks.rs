@@ -0,0 +1,113 @@
+use super::{dijkstra_edge_lists, dijkstra_within, NodeID, NodeList, SearchBuffers};
+use crate::cost::Cost;
+
+/// Landmark Nodes for the ALT (A*, Landmarks, Triangle inequality) heuristic
+///
+/// Stores the cost of the cheapest Path from every landmark to every Node and back. Because of
+/// the triangle inequality, the difference between the costs of two Nodes to or from the same
+/// landmark is a lower bound for the cost between them, which follows the walls of the Grid
+/// instead of being a straight line like [`Neighborhood::heuristic`](crate::neighbors::Neighborhood::heuristic).
+#[derive(Clone, Debug)]
+pub(crate) struct Landmarks<C> {
+    /// the number of Nodes per landmark, which is the `id_bound` of the Nodes
+    size: usize,
+    /// `from[i * size + id]` is the cost from the `i`th landmark to the Node `id`
+    from: Vec<Option<C>>,
+    /// `to[i * size + id]` is the cost from the Node `id` to the `i`th landmark
+    to: Vec<Option<C>>,
+}
+
+impl<C: Cost> Landmarks<C> {
+    /// Selects up to `count` landmarks among `nodes` and calculates their costs.
+    ///
+    /// The landmarks are chosen to be as far away from each other as possible, since those give
+    /// the best bounds for the Nodes between them.
+    pub fn new(buffers: &mut SearchBuffers<NodeID, C>, nodes: &NodeList<C>, count: usize) -> Self {
+        let size = nodes.id_bound();
+        let mut landmarks = Landmarks {
+            size,
+            from: vec![],
+            to: vec![],
+        };
+        let ids = nodes.iter().map(|(id, _)| id).collect::<Vec<_>>();
+        let Some(&first) = ids.first() else {
+            return landmarks;
+        };
+
+        let mut reversed = vec![vec![]; size];
+        for (id, node) in nodes.iter() {
+            for (&other_id, path) in node.edges.iter() {
+                reversed[other_id].push((id, path.cost()));
+            }
+        }
+
+        // the cost from the closest landmark to every Node, or `None` if no landmark reaches it
+        let mut closest: Vec<Option<C>> = vec![None; size];
+        let costs = dijkstra_within(buffers, nodes, &[(first, C::ZERO)], None);
+        let mut next = farthest(&ids, |id| costs.get(&id).map(|&(cost, _)| cost));
+
+        for _ in 0..count.min(ids.len()) {
+            let landmark = next;
+
+            let from = dijkstra_within(buffers, nodes, &[(landmark, C::ZERO)], None);
+            let to = dijkstra_edge_lists(&reversed, landmark);
+
+            let offset = landmarks.from.len();
+            landmarks.from.resize(offset + size, None);
+            landmarks.to.resize(offset + size, None);
+            for (id, (cost, _)) in from {
+                landmarks.from[offset + id] = Some(cost);
+                closest[id] = Some(closest[id].map_or(cost, |prev| prev.min(cost)));
+            }
+            for (id, (cost, _)) in to {
+                landmarks.to[offset + id] = Some(cost);
+            }
+
+            next = farthest(&ids, |id| closest[id]);
+        }
+
+        landmarks
+    }
+
+    /// A lower bound for the cost of the cheapest Path from `id` to `goal`
+    pub fn lower_bound(&self, id: NodeID, goal: NodeID) -> C {
+        let mut bound = C::ZERO;
+        if id >= self.size || goal >= self.size {
+            return bound;
+        }
+        for offset in (0..self.from.len()).step_by(self.size.max(1)) {
+            // landmark -> id -> goal is not cheaper than landmark -> goal
+            if let (Some(to_goal), Some(to_id)) = (self.from[offset + goal], self.from[offset + id])
+            {
+                if to_goal > to_id {
+                    bound = bound.max(to_goal - to_id);
+                }
+            }
+            // id -> goal -> landmark is not cheaper than id -> landmark
+            if let (Some(from_id), Some(from_goal)) = (self.to[offset + id], self.to[offset + goal])
+            {
+                if from_id > from_goal {
+                    bound = bound.max(from_id - from_goal);
+                }
+            }
+        }
+        bound
+    }
+
+    /// The number of bytes allocated for the costs
+    pub fn heap_size(&self) -> usize {
+        (self.from.capacity() + self.to.capacity()) * size_of::<Option<C>>()
+    }
+}
+
+/// Finds the Node with the highest cost, preferring Nodes without any cost
+fn farthest<C: Cost>(ids: &[NodeID], cost: impl Fn(NodeID) -> Option<C>) -> NodeID {
+    ids.iter()
+        .copied()
+        .max_by_key(|&id| match cost(id) {
+            // not reached by any landmark yet
+            None => (true, C::ZERO),
+            Some(cost) => (false, cost),
+        })
+        .expect("farthest requires at least one Node")
+}
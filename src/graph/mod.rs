@@ -4,12 +4,27 @@ pub(crate) use node_list::NodeList;
 mod node;
 pub(crate) use node::Node;
 
+mod edges;
+pub(crate) use edges::Edges;
+
 mod a_star;
-pub(crate) use a_star::a_star_search;
+pub(crate) use a_star::{a_star_begin, a_star_continue, a_star_search, a_star_to_any};
 
 mod dijkstra;
-pub(crate) use dijkstra::dijkstra_search;
+pub(crate) use dijkstra::{
+    dijkstra_edge_lists, dijkstra_from_any, dijkstra_search, dijkstra_within,
+};
+
+mod contraction;
+pub(crate) use contraction::ContractionHierarchy;
+
+mod landmarks;
+pub(crate) use landmarks::Landmarks;
+
+mod distance_table;
+pub(crate) use distance_table::DistanceTable;
 
-use crate::grid::{Element, HeuristicElement};
+use crate::grid::{Element, Heuristic, HeuristicElement};
 use crate::path::Path;
+use crate::search_context::SearchBuffers;
 use crate::{NodeID, NodeIDMap, NodeIDSet};
@@ -1,18 +1,19 @@
-use crate::{path::PathSegment, NodeIDMap, Point};
+use super::Edges;
+use crate::Point;
 
 #[derive(Clone, Debug)]
-pub(crate) struct Node {
+pub(crate) struct Node<C> {
     pub pos: Point,
-    pub walk_cost: usize,
-    pub edges: NodeIDMap<PathSegment>,
+    pub walk_cost: C,
+    pub edges: Edges<C>,
 }
 
-impl Node {
-    pub fn new(pos: Point, walk_cost: usize) -> Node {
+impl<C> Node<C> {
+    pub fn new(pos: Point, walk_cost: C) -> Node<C> {
         Node {
             pos,
             walk_cost,
-            edges: NodeIDMap::default(),
+            edges: Edges::new(),
         }
     }
 }
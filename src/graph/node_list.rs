@@ -1,13 +1,13 @@
 use super::{Node, NodeID, NodeIDMap, NodeIDSet};
-use crate::{path::PathSegment, Point, PointMap};
+use crate::{cost::Cost, path::PathSegment, Point, PointMap};
 
 #[derive(Clone, Debug)]
-pub(crate) struct NodeList {
-    nodes: slab::Slab<Node>,
+pub(crate) struct NodeList<C> {
+    nodes: slab::Slab<Node<C>>,
     pos_map: PointMap<NodeID>,
 }
 
-impl NodeList {
+impl<C: Cost> NodeList<C> {
     pub fn new() -> Self {
         Self {
             nodes: slab::Slab::default(),
@@ -15,19 +15,24 @@ impl NodeList {
         }
     }
 
-    pub fn len(&self) -> usize {
-        self.pos_map.len()
+    /// An upper bound for all [`NodeID`]s currently in use
+    pub fn id_bound(&self) -> usize {
+        self.nodes.capacity()
     }
 
-    pub fn add_node(&mut self, pos: Point, walk_cost: usize) -> NodeID {
+    pub fn pos_map_capacity(&self) -> usize {
+        self.pos_map.capacity()
+    }
+
+    pub fn add_node(&mut self, pos: Point, walk_cost: C) -> NodeID {
         let id = self.nodes.insert(Node::new(pos, walk_cost));
         self.pos_map.insert(pos, id);
         id
     }
 
-    pub fn add_edge(&mut self, src: NodeID, target: NodeID, path: PathSegment) {
+    pub fn add_edge(&mut self, src: NodeID, target: NodeID, path: PathSegment<C>) {
         let src_node = &self[src];
-        if let Some(existing) = src_node.edges.get(&target) {
+        if let Some(existing) = src_node.edges.get(target) {
             if existing.cost() == path.cost() {
                 return;
             }
@@ -49,12 +54,12 @@ impl NodeList {
     pub fn remove_node(&mut self, id: NodeID) {
         let node = self.nodes.remove(id);
         for (other_id, _) in node.edges {
-            self[other_id].edges.remove(&id);
+            self[other_id].edges.remove(id);
         }
         self.pos_map.remove(&node.pos);
     }
 
-    pub fn iter(&self) -> slab::Iter<Node> {
+    pub fn iter(&self) -> slab::Iter<Node<C>> {
         self.nodes.iter()
     }
 
@@ -62,7 +67,7 @@ impl NodeList {
         self.pos_map.get(&pos).copied()
     }
 
-    pub fn absorb(&mut self, other: NodeList) -> NodeIDSet {
+    pub fn absorb(&mut self, other: NodeList<C>) -> NodeIDSet {
         let mut ret = NodeIDSet::default();
         let mut map = NodeIDMap::default();
 
@@ -86,16 +91,16 @@ impl NodeList {
 }
 
 use std::ops::{Index, IndexMut};
-impl Index<NodeID> for NodeList {
-    type Output = Node;
+impl<C> Index<NodeID> for NodeList<C> {
+    type Output = Node<C>;
     #[track_caller]
-    fn index(&self, index: NodeID) -> &Node {
+    fn index(&self, index: NodeID) -> &Node<C> {
         &self.nodes[index]
     }
 }
-impl IndexMut<NodeID> for NodeList {
+impl<C> IndexMut<NodeID> for NodeList<C> {
     #[track_caller]
-    fn index_mut(&mut self, index: NodeID) -> &mut Node {
+    fn index_mut(&mut self, index: NodeID) -> &mut Node<C> {
         &mut self.nodes[index]
     }
 }
@@ -103,18 +108,24 @@ impl IndexMut<NodeID> for NodeList {
 #[test]
 fn absorb() {
     let mut nodes = NodeList::new();
-    let zero_id = nodes.add_node((0, 0), 0);
+    let zero_id = nodes.add_node((0, 0), 0usize);
     let one_id = nodes.add_node((1, 1), 1);
     let two_id = nodes.add_node((2, 2), 2);
     nodes.add_edge(
         zero_id,
         one_id,
-        PathSegment::new(super::Path::from_slice(&[], 0), true),
+        PathSegment::new(
+            super::Path::from_slice(&[], 0),
+            &crate::PathCacheConfig::default(),
+        ),
     );
     nodes.add_edge(
         two_id,
         zero_id,
-        PathSegment::new(super::Path::from_slice(&[], 2), true),
+        PathSegment::new(
+            super::Path::from_slice(&[], 2),
+            &crate::PathCacheConfig::default(),
+        ),
     );
 
     let mut new_nodes = NodeList::new();
@@ -123,7 +134,10 @@ fn absorb() {
     new_nodes.add_edge(
         ten_id,
         eleven_id,
-        PathSegment::new(super::Path::from_slice(&[], 10), true),
+        PathSegment::new(
+            super::Path::from_slice(&[], 10),
+            &crate::PathCacheConfig::default(),
+        ),
     );
 
     nodes.absorb(new_nodes);
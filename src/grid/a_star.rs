@@ -1,31 +1,88 @@
-use super::{HeuristicElement, Path};
-use crate::{neighbors::Neighborhood, Point, PointMap};
+use super::{in_bounds, Heuristic, HeuristicElement, Path, SearchProgress};
+use crate::{cost::Cost, neighbors::Neighborhood, search_context::SearchBuffers, Point};
 
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
 
-pub fn a_star_search<N: Neighborhood>(
+#[allow(clippy::too_many_arguments)]
+pub fn a_star_search<N: Neighborhood, C: Cost>(
+    buffers: &mut SearchBuffers<Point, C>,
     neighborhood: &N,
-    mut valid: impl FnMut(Point) -> bool,
-    mut get_cost: impl FnMut(Point) -> isize,
+    base: Point,
+    size: (usize, usize),
+    mut get_cost: impl FnMut(Point) -> Option<C>,
     start: Point,
     goal: Point,
-    size_hint: usize,
-) -> Option<Path<Point>> {
-    if get_cost(start) < 0 {
-        return None;
-    }
+    heuristic: Heuristic<C>,
+) -> Option<Path<Point, C>> {
+    // cannot start on a wall
+    get_cost(start)?;
     if start == goal {
-        return Some(Path::from_slice(&[start, start], 0));
+        return Some(Path::from_slice(&[start, start], C::ZERO));
+    }
+    a_star_begin(buffers, base, size, start);
+    let mut budget = usize::MAX;
+    let progress = a_star_continue(
+        buffers,
+        neighborhood,
+        base,
+        size,
+        get_cost,
+        start,
+        goal,
+        heuristic,
+        &mut budget,
+    );
+    match progress {
+        SearchProgress::Done(path) => path,
+        SearchProgress::InProgress => unreachable!("the search was not limited"),
     }
-    let mut visited = PointMap::with_capacity(size_hint);
-    let mut next = BinaryHeap::with_capacity(size_hint / 2);
-    next.push(HeuristicElement(start, 0, 0));
-    visited.insert(start, (0, start));
+}
+
+/// Prepares `buffers` for an A* search from `start` that is performed by [`a_star_continue`]
+pub fn a_star_begin<C: Cost>(
+    buffers: &mut SearchBuffers<Point, C>,
+    base: Point,
+    size: (usize, usize),
+    start: Point,
+) {
+    let SearchBuffers {
+        visited,
+        a_star_next: next,
+        ..
+    } = buffers.reset(base, size);
+    next.push(HeuristicElement(start, C::ZERO, C::ZERO));
+    visited.insert(start, (C::ZERO, start));
+}
 
-    let mut all_neighbors = vec![];
+/// Continues the A* search in `buffers` until it is done or `budget` Tiles were expanded.
+///
+/// `budget` is reduced by the number of expanded Tiles.
+#[allow(clippy::too_many_arguments)]
+pub fn a_star_continue<N: Neighborhood, C: Cost>(
+    buffers: &mut SearchBuffers<Point, C>,
+    neighborhood: &N,
+    base: Point,
+    size: (usize, usize),
+    mut get_cost: impl FnMut(Point) -> Option<C>,
+    start: Point,
+    goal: Point,
+    heuristic: Heuristic<C>,
+    budget: &mut usize,
+) -> SearchProgress<Point, C> {
+    let SearchBuffers {
+        visited,
+        a_star_next: next,
+        neighbors: all_neighbors,
+        ..
+    } = buffers;
 
-    while let Some(HeuristicElement(current_id, current_cost, _)) = next.pop() {
+    loop {
+        if *budget == 0 {
+            return SearchProgress::InProgress;
+        }
+        let Some(HeuristicElement(current_id, current_cost, _)) = next.pop() else {
+            break;
+        };
         if current_id == goal {
             break;
         }
@@ -34,20 +91,22 @@ pub fn a_star_search<N: Neighborhood>(
             Ordering::Equal => {}
             Ordering::Less => panic!("Binary Heap failed"),
         }
-
-        let delta_cost = get_cost(current_id);
-        if delta_cost < 0 {
+        *budget -= 1;
+        let Some(delta_cost) = get_cost(current_id) else {
             continue;
-        }
-        let other_cost = current_cost + delta_cost as usize;
+        };
+        let Some(other_cost) = current_cost.checked_add(delta_cost) else {
+            // any Path through here would overflow => treat as unreachable
+            continue;
+        };
 
         all_neighbors.clear();
-        neighborhood.get_all_neighbors(current_id, &mut all_neighbors);
+        neighborhood.get_all_neighbors(current_id, all_neighbors);
         for &other_id in all_neighbors.iter() {
-            if !valid(other_id) {
+            if !in_bounds(other_id, base, size) {
                 continue;
             }
-            if get_cost(other_id) < 0 && other_id != goal {
+            if get_cost(other_id).is_none() && other_id != goal {
                 continue;
             }
 
@@ -64,18 +123,18 @@ pub fn a_star_search<N: Neighborhood>(
             }
 
             if needs_visit {
-                let heuristic = neighborhood.heuristic(other_id, goal);
+                let estimate = heuristic.estimate(neighborhood.heuristic(other_id, goal));
                 next.push(HeuristicElement(
                     other_id,
                     other_cost,
-                    other_cost + heuristic,
+                    other_cost.saturating_add(estimate),
                 ));
             }
         }
     }
 
     if !visited.contains_key(&goal) {
-        return None;
+        return SearchProgress::Done(None);
     }
 
     let steps = {
@@ -92,7 +151,7 @@ pub fn a_star_search<N: Neighborhood>(
         steps
     };
 
-    Some(Path::new(steps, visited[&goal].0))
+    SearchProgress::Done(Some(Path::new(steps, visited[&goal].0)))
 }
 
 #[cfg(test)]
@@ -116,16 +175,25 @@ mod tests {
 
         let neighborhood = ManhattanNeighborhood::new(width, height);
 
-        const COST_MAP: [isize; 3] = [1, 10, -1];
+        const COST_MAP: [Option<usize>; 3] = [Some(1), Some(10), None];
 
-        fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + FnMut(Point) -> isize {
+        fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + FnMut(Point) -> Option<usize> {
             move |(x, y)| COST_MAP[grid[y][x]]
         }
 
         let start = (0, 0);
         let goal = (2, 0);
 
-        let path = a_star_search(&neighborhood, |_| true, cost_fn(&grid), start, goal, 40);
+        let path = a_star_search(
+            &mut SearchBuffers::default(),
+            &neighborhood,
+            (0, 0),
+            (width, height),
+            cost_fn(&grid),
+            start,
+            goal,
+            Heuristic::exact(1),
+        );
 
         assert!(path.is_none());
     }
@@ -147,19 +215,100 @@ mod tests {
 
         let neighborhood = ManhattanNeighborhood::new(width, height);
 
-        const COST_MAP: [isize; 3] = [1, 10, -1];
+        const COST_MAP: [Option<usize>; 3] = [Some(1), Some(10), None];
 
-        fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + FnMut(Point) -> isize {
+        fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + FnMut(Point) -> Option<usize> {
             move |(x, y)| COST_MAP[grid[y][x]]
         }
 
         let start = (0, 0);
         let goal = (4, 4);
-        let path = a_star_search(&neighborhood, |_| true, cost_fn(&grid), start, goal, 40);
+        let path = a_star_search(
+            &mut SearchBuffers::default(),
+            &neighborhood,
+            (0, 0),
+            (width, height),
+            cost_fn(&grid),
+            start,
+            goal,
+            Heuristic::exact(1),
+        );
 
         assert!(path.is_some());
         let path = path.unwrap();
 
         assert_eq!(path.cost(), 12);
     }
+
+    #[test]
+    fn overflow() {
+        use crate::prelude::*;
+
+        let neighborhood = ManhattanNeighborhood::new(4, 1);
+        let cost_fn = |_| Some(usize::MAX / 2);
+        let mut buffers = SearchBuffers::default();
+
+        let path = a_star_search(
+            &mut buffers,
+            &neighborhood,
+            (0, 0),
+            (4, 1),
+            cost_fn,
+            (0, 0),
+            (2, 0),
+            Heuristic::exact(1),
+        );
+        assert_eq!(path.unwrap().cost(), usize::MAX - 1);
+
+        let path = a_star_search(
+            &mut buffers,
+            &neighborhood,
+            (0, 0),
+            (4, 1),
+            cost_fn,
+            (0, 0),
+            (3, 0),
+            Heuristic::exact(1),
+        );
+        assert!(path.is_none());
+    }
+
+    #[test]
+    fn weighted() {
+        use crate::prelude::*;
+
+        let (width, height) = (20, 20);
+        let neighborhood = ManhattanNeighborhood::new(width, height);
+        let cost_fn = |(x, y): Point| match (x % 6, y % 5) {
+            (3, 0..=3) => None,
+            _ => Some(1 + (x * 3 + y * 7) % 5),
+        };
+        let mut buffers = SearchBuffers::default();
+
+        for goal in [(19, 19), (5, 17), (18, 2)] {
+            let search = |buffers: &mut SearchBuffers<Point, usize>, weight| {
+                a_star_search(
+                    buffers,
+                    &neighborhood,
+                    (0, 0),
+                    (width, height),
+                    cost_fn,
+                    (0, 0),
+                    goal,
+                    Heuristic {
+                        min_cost: 1,
+                        weight,
+                    },
+                )
+                .unwrap()
+            };
+            let optimal = search(&mut buffers, 1.0).cost();
+            for weight in [1.5, 2.0, 5.0] {
+                let path = search(&mut buffers, weight);
+                assert_eq!(path[path.len() - 1], goal);
+                assert!(path.cost() >= optimal);
+                assert!(path.cost() as f32 <= optimal as f32 * weight);
+            }
+        }
+    }
 }
@@ -1,32 +1,37 @@
-use super::{Element, Path};
-use crate::{neighbors::Neighborhood, Point, PointMap, PointSet};
+use super::{in_bounds, Element, Path};
+use crate::{
+    cost::Cost, neighbors::Neighborhood, search_context::SearchBuffers, Point, PointMap, PointSet,
+};
 
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
 
-pub fn dijkstra_search<N: Neighborhood>(
+#[allow(clippy::too_many_arguments)]
+pub fn dijkstra_search<N: Neighborhood, C: Cost>(
+    buffers: &mut SearchBuffers<Point, C>,
     neighborhood: &N,
-    mut valid: impl FnMut(Point) -> bool,
-    mut get_cost: impl FnMut(Point) -> isize,
+    base: Point,
+    size: (usize, usize),
+    mut get_cost: impl FnMut(Point) -> Option<C>,
     start: Point,
     goals: &[Point],
     only_closest_goal: bool,
-    size_hint: usize,
-) -> PointMap<Path<Point>> {
-    if get_cost(start) < 0 {
+) -> PointMap<Path<Point, C>> {
+    if get_cost(start).is_none() {
         return PointMap::default();
     }
-    let mut visited = PointMap::with_capacity(size_hint);
-    let mut next = BinaryHeap::with_capacity(size_hint / 2);
-    next.push(Element(start, 0));
-    visited.insert(start, (0, start));
+    let SearchBuffers {
+        visited,
+        dijkstra_next: next,
+        neighbors: all_neighbors,
+        ..
+    } = buffers.reset(base, size);
+    next.push(Element(start, C::ZERO));
+    visited.insert(start, (C::ZERO, start));
 
     let mut remaining_goals: PointSet = goals.iter().copied().collect();
 
     let mut goal_costs = PointMap::with_capacity(goals.len());
 
-    let mut all_neighbors = vec![];
-
     while let Some(Element(current_id, current_cost)) = next.pop() {
         match current_cost.cmp(&visited[&current_id].0) {
             Ordering::Greater => continue,
@@ -41,19 +46,21 @@ pub fn dijkstra_search<N: Neighborhood>(
             }
         }
 
-        let delta_cost = get_cost(current_id);
-        if delta_cost < 0 {
+        let Some(delta_cost) = get_cost(current_id) else {
             continue;
-        }
-        let other_cost = current_cost + delta_cost as usize;
+        };
+        let Some(other_cost) = current_cost.checked_add(delta_cost) else {
+            // any Path through here would overflow => treat as unreachable
+            continue;
+        };
 
         all_neighbors.clear();
-        neighborhood.get_all_neighbors(current_id, &mut all_neighbors);
+        neighborhood.get_all_neighbors(current_id, all_neighbors);
         for &other_id in all_neighbors.iter() {
-            if !valid(other_id) {
+            if !in_bounds(other_id, base, size) {
                 continue;
             }
-            if get_cost(other_id) < 0 && !remaining_goals.contains(&other_id) {
+            if get_cost(other_id).is_none() && !remaining_goals.contains(&other_id) {
                 continue;
             }
 
@@ -97,6 +104,194 @@ pub fn dijkstra_search<N: Neighborhood>(
     goal_data
 }
 
+/// Finds all Points within the area at `base` that can be reached from `start` with a cost of at
+/// most `max_cost`.
+///
+/// Returns the cost of every reachable Point and the Point before it on the cheapest Path, and
+/// `true` if a Point outside of the area would have been reachable as well.
+pub fn dijkstra_within<N: Neighborhood, C: Cost>(
+    buffers: &mut SearchBuffers<Point, C>,
+    neighborhood: &N,
+    base: Point,
+    size: (usize, usize),
+    mut get_cost: impl FnMut(Point) -> Option<C>,
+    start: Point,
+    max_cost: C,
+) -> (PointMap<(C, Point)>, bool) {
+    let mut reached = PointMap::default();
+    let mut cut_off = false;
+    if get_cost(start).is_none() {
+        return (reached, cut_off);
+    }
+    let SearchBuffers {
+        visited,
+        dijkstra_next: next,
+        neighbors: all_neighbors,
+        ..
+    } = buffers.reset(base, size);
+    next.push(Element(start, C::ZERO));
+    visited.insert(start, (C::ZERO, start));
+
+    while let Some(Element(current_id, current_cost)) = next.pop() {
+        match current_cost.cmp(&visited[&current_id].0) {
+            Ordering::Greater => continue,
+            Ordering::Equal => {}
+            Ordering::Less => panic!("Binary Heap failed"),
+        }
+        reached.insert(current_id, visited[&current_id]);
+
+        let Some(delta_cost) = get_cost(current_id) else {
+            continue;
+        };
+        let Some(other_cost) = current_cost.checked_add(delta_cost) else {
+            // any Path through here would overflow => treat as unreachable
+            continue;
+        };
+        if other_cost > max_cost {
+            continue;
+        }
+
+        all_neighbors.clear();
+        neighborhood.get_all_neighbors(current_id, all_neighbors);
+        for &other_id in all_neighbors.iter() {
+            if get_cost(other_id).is_none() {
+                continue;
+            }
+            if !in_bounds(other_id, base, size) {
+                cut_off = true;
+                continue;
+            }
+
+            let mut needs_visit = true;
+            if let Some((prev_cost, prev_id)) = visited.get_mut(&other_id) {
+                if *prev_cost > other_cost {
+                    *prev_cost = other_cost;
+                    *prev_id = current_id;
+                } else {
+                    needs_visit = false;
+                }
+            } else {
+                visited.insert(other_id, (other_cost, current_id));
+            }
+
+            if needs_visit {
+                next.push(Element(other_id, other_cost));
+            }
+        }
+    }
+
+    (reached, cut_off)
+}
+
+/// Finds the closest Point within the area at `base` for which `is_goal` returns `true`, from any
+/// of the `starts`, which contain the Points to start from and the cost of reaching them.
+///
+/// Returns the goal and the Path to it, if the goal can be reached with a total cost of at most
+/// `max_cost`. The cost of the Path only contains the steps on the Grid, not the cost of the start
+/// it begins at. Goals may be solid, so `is_goal` is also called for the solid neighbors of every
+/// visited Point.
+#[allow(clippy::too_many_arguments)]
+pub fn dijkstra_from_any<N: Neighborhood, C: Cost>(
+    buffers: &mut SearchBuffers<Point, C>,
+    neighborhood: &N,
+    base: Point,
+    size: (usize, usize),
+    mut get_cost: impl FnMut(Point) -> Option<C>,
+    starts: &[(Point, C)],
+    mut is_goal: impl FnMut(Point) -> bool,
+    max_cost: C,
+) -> Option<(Point, Path<Point, C>)> {
+    let SearchBuffers {
+        visited,
+        dijkstra_next: next,
+        neighbors: all_neighbors,
+        ..
+    } = buffers.reset(base, size);
+
+    for &(start, cost) in starts {
+        if get_cost(start).is_none() {
+            continue;
+        }
+        if visited.get(&start).is_none_or(|(prev, _)| *prev > cost) {
+            visited.insert(start, (cost, start));
+            next.push(Element(start, cost));
+        }
+    }
+
+    let mut found = None;
+
+    while let Some(Element(current_id, current_cost)) = next.pop() {
+        match current_cost.cmp(&visited[&current_id].0) {
+            Ordering::Greater => continue,
+            Ordering::Equal => {}
+            Ordering::Less => panic!("Binary Heap failed"),
+        }
+        if current_cost > max_cost {
+            // only the starts can be more expensive, and the other ones are even further away
+            break;
+        }
+        if is_goal(current_id) {
+            found = Some(current_id);
+            break;
+        }
+
+        let Some(delta_cost) = get_cost(current_id) else {
+            continue;
+        };
+        let Some(other_cost) = current_cost.checked_add(delta_cost) else {
+            // any Path through here would overflow => treat as unreachable
+            continue;
+        };
+        if other_cost > max_cost {
+            continue;
+        }
+
+        all_neighbors.clear();
+        neighborhood.get_all_neighbors(current_id, all_neighbors);
+        for &other_id in all_neighbors.iter() {
+            if !in_bounds(other_id, base, size) {
+                continue;
+            }
+            if get_cost(other_id).is_none() && !is_goal(other_id) {
+                continue;
+            }
+
+            let mut needs_visit = true;
+            if let Some((prev_cost, prev_id)) = visited.get_mut(&other_id) {
+                if *prev_cost > other_cost {
+                    *prev_cost = other_cost;
+                    *prev_id = current_id;
+                } else {
+                    needs_visit = false;
+                }
+            } else {
+                visited.insert(other_id, (other_cost, current_id));
+            }
+
+            if needs_visit {
+                next.push(Element(other_id, other_cost));
+            }
+        }
+    }
+
+    let goal = found?;
+    let mut steps = vec![goal];
+    let mut current = goal;
+    loop {
+        let (_, prev) = visited[&current];
+        if prev == current {
+            // reached one of the starts
+            break;
+        }
+        steps.push(prev);
+        current = prev;
+    }
+    steps.reverse();
+
+    let cost = visited[&goal].0 - visited[&current].0;
+    Some((goal, Path::new(steps, cost)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,9 +313,9 @@ mod tests {
 
         let neighborhood = ManhattanNeighborhood::new(width, height);
 
-        const COST_MAP: [isize; 3] = [1, 10, -1];
+        const COST_MAP: [Option<usize>; 3] = [Some(1), Some(10), None];
 
-        fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + FnMut(Point) -> isize {
+        fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + FnMut(Point) -> Option<usize> {
             move |(x, y)| COST_MAP[grid[y][x]]
         }
 
@@ -128,13 +323,14 @@ mod tests {
         let goals = [(4, 4), (2, 0)];
 
         let paths = dijkstra_search(
+            &mut SearchBuffers::default(),
             &neighborhood,
-            |_| true,
+            (0, 0),
+            (width, height),
             cost_fn(&grid),
             start,
             &goals,
             false,
-            40,
         );
 
         // (4, 4) is reachable
@@ -143,4 +339,82 @@ mod tests {
         // (2, 0) is not reachable
         assert!(!paths.contains_key(&goals[1]));
     }
+
+    #[test]
+    fn from_any() {
+        use crate::prelude::*;
+
+        // 0 = empty, 1 = swamp, 2 = wall
+        let grid = [
+            [0, 2, 0, 0, 0],
+            [0, 2, 2, 2, 2],
+            [0, 1, 0, 0, 0],
+            [0, 1, 0, 2, 0],
+            [0, 0, 0, 2, 0],
+        ];
+        let (width, height) = (grid[0].len(), grid.len());
+        let neighborhood = ManhattanNeighborhood::new(width, height);
+        let cost_fn = |(x, y): Point| [Some(1usize), Some(10), None][grid[y][x]];
+
+        // reaching (0, 4) from (4, 2) costs 6 + 9, which is more than 4 + 10 from (0, 0)
+        let starts = [((0, 0), 10), ((4, 2), 9)];
+        let (goal, path) = dijkstra_from_any(
+            &mut SearchBuffers::default(),
+            &neighborhood,
+            (0, 0),
+            (width, height),
+            cost_fn,
+            &starts,
+            |pos| pos == (0, 4) || pos == (2, 2),
+            usize::MAX,
+        )
+        .unwrap();
+        assert_eq!(goal, (2, 2));
+        assert_eq!(path.cost(), 2);
+        assert_eq!(
+            path.iter().copied().collect::<Vec<_>>(),
+            [(4, 2), (3, 2), (2, 2)]
+        );
+
+        let (goal, path) = dijkstra_from_any(
+            &mut SearchBuffers::default(),
+            &neighborhood,
+            (0, 0),
+            (width, height),
+            cost_fn,
+            &starts,
+            |pos| pos == (0, 4),
+            usize::MAX,
+        )
+        .unwrap();
+        assert_eq!(goal, (0, 4));
+        assert_eq!(path.cost(), 4);
+        assert_eq!(path[0], (0, 0));
+
+        // the cost of the start counts towards max_cost
+        let found = dijkstra_from_any(
+            &mut SearchBuffers::default(),
+            &neighborhood,
+            (0, 0),
+            (width, height),
+            cost_fn,
+            &starts,
+            |pos| pos == (0, 4),
+            13,
+        );
+        assert!(found.is_none());
+
+        // walled off from both starts
+        let found = dijkstra_from_any(
+            &mut SearchBuffers::default(),
+            &neighborhood,
+            (0, 0),
+            (width, height),
+            cost_fn,
+            &starts,
+            |pos| pos == (2, 0),
+            usize::MAX,
+        );
+        assert!(found.is_none());
+    }
 }
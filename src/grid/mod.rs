@@ -1,34 +1,91 @@
 mod a_star;
-pub(crate) use a_star::a_star_search;
+pub(crate) use a_star::{a_star_begin, a_star_continue, a_star_search};
 
 mod dijkstra;
-pub(crate) use dijkstra::dijkstra_search;
+pub(crate) use dijkstra::{dijkstra_from_any, dijkstra_search, dijkstra_within};
 
-use crate::path::{Cost, Path};
+use crate::cost::Cost;
+use crate::path::Path;
+use crate::Point;
 
 use std::cmp::Ordering;
 
-#[derive(PartialEq, Eq)]
-pub(crate) struct HeuristicElement<Id>(pub Id, pub Cost, pub Cost);
-impl<Id: Eq> PartialOrd for HeuristicElement<Id> {
+/// The progress of a search that can be continued later
+pub(crate) enum SearchProgress<Id, C> {
+    /// the search ran out of expansions before it was done
+    InProgress,
+    /// the search is done, with the Path to the goal if there is one
+    Done(Option<Path<Id, C>>),
+}
+
+/// Turns the number of steps from the [`heuristic`](crate::neighbors::Neighborhood::heuristic)
+/// of a Neighborhood into an estimate of the cost of walking them.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Heuristic<C> {
+    /// the lowest cost of any Tile, which is the least that a single step can cost
+    pub min_cost: C,
+    /// see [`heuristic_weight`](crate::PathCacheConfig::heuristic_weight)
+    pub weight: f32,
+}
+
+impl<C: Cost> Heuristic<C> {
+    /// An unweighted Heuristic, for searches that have to find the cheapest Path
+    pub fn exact(min_cost: C) -> Heuristic<C> {
+        Heuristic {
+            min_cost,
+            weight: 1.0,
+        }
+    }
+
+    /// A lower bound for the cost of walking `steps` Tiles
+    #[allow(clippy::cast_precision_loss)]
+    pub fn bound(&self, steps: usize) -> C {
+        self.min_cost.scale(steps as f64)
+    }
+
+    /// Applies the weight to a lower bound of the remaining cost
+    #[allow(clippy::float_cmp)]
+    pub fn weighted(&self, bound: C) -> C {
+        // exactly `1.0` is the default, which shouldn't lose precision by scaling
+        if self.weight == 1.0 {
+            bound
+        } else {
+            bound.scale(f64::from(self.weight))
+        }
+    }
+
+    /// The weighted estimate for walking `steps` Tiles
+    pub fn estimate(&self, steps: usize) -> C {
+        self.weighted(self.bound(steps))
+    }
+}
+
+/// Checks if `point` is within the rectangle at `base` with the given size
+fn in_bounds(point: Point, base: Point, (w, h): (usize, usize)) -> bool {
+    point.0 >= base.0 && point.0 < base.0 + w && point.1 >= base.1 && point.1 < base.1 + h
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HeuristicElement<Id, C>(pub Id, pub C, pub C);
+impl<Id: Eq, C: Ord> PartialOrd for HeuristicElement<Id, C> {
     fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
         Some(self.cmp(rhs))
     }
 }
-impl<Id: Eq> Ord for HeuristicElement<Id> {
+impl<Id: Eq, C: Ord> Ord for HeuristicElement<Id, C> {
     fn cmp(&self, rhs: &Self) -> Ordering {
         rhs.2.cmp(&self.2)
     }
 }
 
-#[derive(PartialEq, Eq)]
-pub(crate) struct Element<Id>(pub Id, pub Cost);
-impl<Id: Eq> PartialOrd for Element<Id> {
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Element<Id, C>(pub Id, pub C);
+impl<Id: Eq, C: Ord> PartialOrd for Element<Id, C> {
     fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
         Some(self.cmp(rhs))
     }
 }
-impl<Id: Eq> Ord for Element<Id> {
+impl<Id: Eq, C: Ord> Ord for Element<Id, C> {
     fn cmp(&self, rhs: &Self) -> Ordering {
         rhs.1.cmp(&self.1)
     }
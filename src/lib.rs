@@ -51,7 +51,8 @@
 //!
 //! This crate provides an implementation of a Hierarchical Pathfinding Algorithm for any generic Grid.
 //! Paths can be searched using either A* for a Path to a single Tile, or Dijkstra for searching
-//! multiple Targets. It handles solid walls in the Grid and actually finding a Path to a wall.
+//! multiple Targets. It handles solid walls in the Grid and actually finding a Path to a wall, or
+//! up to a wall with [`GoalMode`].
 //!
 //! # Examples
 //! ##### Creating the Cache
@@ -70,9 +71,9 @@
 //! let (width, height) = (grid[0].len(), grid.len());
 //!
 //! let cost_map = [
-//!     1,  // empty
-//!     10, // swamp
-//!     -1, // wall = solid
+//!     Some(1),  // empty
+//!     Some(10), // swamp
+//!     None,     // wall = solid
 //! ];
 //! ```
 //! Now for creating the [`PathCache`]:
@@ -85,10 +86,10 @@
 //! #     [0, 0, 0, 2, 0],
 //! # ];
 //! # let (width, height) = (grid[0].len(), grid.len());
-//! # let cost_map = [
-//! #     1,  // empty
-//! #     10, // swamp
-//! #     -1, // wall. Negative number == solid
+//! # let cost_map: [Option<usize>; 3] = [
+//! #     Some(1),  // empty
+//! #     Some(10), // swamp
+//! #     None,     // wall. None == solid
 //! # ];
 //! use hierarchical_pathfinding::prelude::*;
 //!
@@ -101,7 +102,7 @@
 //! ```
 //! The [`PathCache`] never takes the actual Grid, to allow for any storage format to be used
 //! (`Array`, `Vec`, `HashMap`, `kd-tree`, ...). Instead, it takes a callback function that
-//! indicates, how "expensive" walking across a Tile is (negative numbers for solid obstacles).
+//! indicates, how "expensive" walking across a Tile is (`None` for solid obstacles).
 //!
 //! Unfortunately, it is necessary to provide this function to every method of `PathCache`, since
 //! storing it would make the Grid immutable. See also [Updating the `PathCache`](#updating-the-pathcache).
@@ -119,10 +120,10 @@
 //! # ];
 //! # let (width, height) = (grid[0].len(), grid.len());
 //! # type Grid = [[usize; 5]; 5];
-//! const COST_MAP: [isize; 3] = [1, 10, -1]; // now const for ownership reasons
+//! const COST_MAP: [Option<usize>; 3] = [Some(1), Some(10), None]; // now const for ownership reasons
 //!
 //! // only borrows the Grid when called
-//! fn cost_fn(grid: &Grid) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
+//! fn cost_fn(grid: &Grid) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
 //!     move |(x, y)| COST_MAP[grid[y][x]]
 //! }
 //!
@@ -149,8 +150,8 @@
 //! #     [0, 0, 0, 2, 0],
 //! # ];
 //! # let (width, height) = (grid[0].len(), grid.len());
-//! # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-//! #     move |(x, y)| [1, 10, -1][grid[y][x]]
+//! # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+//! #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
 //! # }
 //! # let mut pathfinding = PathCache::new(
 //! #     (width, height),
@@ -187,8 +188,8 @@
 //! #     [0, 0, 0, 2, 0],
 //! # ];
 //! # let (width, height) = (grid[0].len(), grid.len());
-//! # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-//! #     move |(x, y)| [1, 10, -1][grid[y][x]]
+//! # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+//! #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
 //! # }
 //! # let mut pathfinding = PathCache::new(
 //! #     (width, height),
@@ -246,8 +247,8 @@
 //! #     [0, 0, 0, 2, 0],
 //! # ];
 //! # let (width, height) = (grid[0].len(), grid.len());
-//! # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-//! #     move |(x, y)| [1, 10, -1][grid[y][x]]
+//! # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+//! #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
 //! # }
 //! # let mut pathfinding = PathCache::new(
 //! #     (width, height),
@@ -303,8 +304,8 @@
 //! #     [0, 0, 0, 2, 0],
 //! # ];
 //! # let (width, height) = (grid[0].len(), grid.len());
-//! # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-//! #     move |(x, y)| [1, 10, -1][grid[y][x]]
+//! # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+//! #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
 //! # }
 //! # let mut pathfinding = PathCache::new(
 //! #     (width, height),
@@ -335,8 +336,8 @@
 //! #     [0, 0, 0, 2, 0],
 //! # ];
 //! # let (width, height) = (grid[0].len(), grid.len());
-//! # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-//! #     move |(x, y)| [1, 10, -1][grid[y][x]]
+//! # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+//! #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
 //! # }
 //! # let mut pathfinding = PathCache::new(
 //! #     (width, height),
@@ -377,8 +378,8 @@
 //! #     [0, 0, 0, 2, 0],
 //! # ];
 //! # let (width, height) = (grid[0].len(), grid.len());
-//! # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-//! #     move |(x, y)| [1, 10, -1][grid[y][x]]
+//! # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+//! #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
 //! # }
 //!
 //! let mut pathfinding = PathCache::new(
@@ -398,7 +399,7 @@
 //! Enabled by default.
 //!
 //! The parallel feature causes [`PathCache`] creation and updates to be multithreaded using [Rayon](https://crates.io/crates/rayon), making them significantly faster.
-//! This feature has no effect on the speed of finding paths (_yet_).
+//! When finding Paths, only [`find_paths`](PathCache::find_paths) with many goals benefits from this feature.
 //!
 //! ##### log
 //! Disabled by default.
@@ -427,10 +428,17 @@ type NodeIDMap<V> = hashbrown::HashMap<NodeID, V>;
 type NodeIDSet = hashbrown::HashSet<NodeID>;
 
 mod path_cache;
-pub use self::path_cache::{PathCache, PathCacheConfig};
+pub use self::path_cache::{
+    GoalMode, MemoryReport, PathCache, PathCacheConfig, PathQuery, QueryStatus, Region,
+};
+
+pub mod cost;
 
 mod path;
 
+mod search_context;
+pub use self::search_context::SearchContext;
+
 mod utils;
 pub(crate) use utils::*;
 
@@ -441,14 +449,15 @@ mod grid;
 
 /// Internal stuff that is returned by other function
 pub mod internals {
-    pub use crate::path::AbstractPath;
+    pub use crate::path::{AbstractPath, SegmentInfo};
     pub use crate::path_cache::{CacheInspector, NodeInspector};
 }
 
 /// The prelude for this crate.
 pub mod prelude {
     pub use crate::{
+        cost::{Cost, OrderedFloat},
         neighbors::{ManhattanNeighborhood, MooreNeighborhood, Neighborhood},
-        PathCache, PathCacheConfig,
+        GoalMode, PathCache, PathCacheConfig, Region, SearchContext,
     };
 }
@@ -1,5 +1,11 @@
-use super::{Cost, Path, PathSegment};
-use crate::{grid, neighbors::Neighborhood, Point};
+use super::{LruHandle, Path, PathSegment};
+use crate::{
+    cost::Cost,
+    grid::{self, Heuristic},
+    neighbors::Neighborhood,
+    search_context::SearchBuffers,
+    Point,
+};
 
 /// A Path that may not be fully calculated yet.
 ///
@@ -7,7 +13,8 @@ use crate::{grid, neighbors::Neighborhood, Point};
 /// [`config.cache_paths`](crate::PathCacheConfig::cache_paths) may be set to false, there might by
 /// segments of this Path that are not yet calculated. In those cases, since the cost function is
 /// required to resolve those sections, it is necessary to call
-/// [`safe_next()`](AbstractPath::safe_next) or [`resolve()`](AbstractPath::resolve).
+/// [`safe_next()`](AbstractPath::safe_next) or [`resolve()`](AbstractPath::resolve), or to
+/// calculate them beforehand with [`resolve_segments_ahead()`](AbstractPath::resolve_segments_ahead).
 /// Otherwise it is possible to treat this as an `Iterator<Item = Point>`.
 ///
 /// **Warning: Calling `next()` on an `AbstractPath` with unknown segments will panic as soon as those
@@ -18,26 +25,55 @@ use crate::{grid, neighbors::Neighborhood, Point};
 ///
 /// **You have been warned**
 #[derive(Debug, Clone)]
-pub struct AbstractPath<N: Neighborhood> {
+pub struct AbstractPath<N: Neighborhood, C: Cost = usize> {
     neighborhood: N,
-    total_cost: Cost,
+    grid_size: (usize, usize),
+    /// the lowest cost of any Tile, which scales the heuristic when resolving segments
+    min_cost: C,
+    total_cost: C,
     total_length: usize,
-    path: Vec<PathSegment>,
+    path: Vec<PathSegment<C>>,
     end: Point,
+    /// the last Point that was returned by `next()`, or the start of the Path
+    position: Point,
     current_index: (usize, usize),
+    /// the number of segments and Points within the last remaining segment that were taken from
+    /// the back of the Path
+    back_index: (usize, usize),
     steps_taken: usize,
+    lru: Option<LruHandle<C>>,
 }
 
-impl<N: Neighborhood> AbstractPath<N> {
+/// Information about a segment of an [`AbstractPath`]
+///
+/// See [`segments`](AbstractPath::segments) for details and an example.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentInfo<C> {
+    /// The first Point of the segment
+    pub start: Point,
+    /// The last Point of the segment, which is the `start` of the next segment
+    pub end: Point,
+    /// The cost of walking from `start` to `end`
+    pub cost: C,
+    /// The number of steps from `start` to `end`
+    pub length: usize,
+    /// `false` if the Points of the segment still have to be calculated (see
+    /// [`resolve_segments_ahead`](AbstractPath::resolve_segments_ahead))
+    pub is_known: bool,
+}
+
+impl<N: Neighborhood, C: Cost> AbstractPath<N, C> {
     /// Returns the total cost of this Path.
     /// This value is always known and requires no further calculations.
-    pub fn cost(&self) -> Cost {
+    pub fn cost(&self) -> C {
         self.total_cost
     }
 
     /// Returns the total length of this Path.
     ///
-    /// This value is always known and requires no further calculations.
+    /// This value is always known and requires no further calculations. Resolving an unknown
+    /// segment may find a different Path of the same cost, so the length can change when
+    /// [`config.cache_paths`](crate::PathCacheConfig::cache_paths) is set to `false`.
     ///
     /// Note that the [`len`](AbstractPath::len) method is provided by [`ExactSizeIterator`] and tells
     /// the remaining elements, whereas the one stays constant.
@@ -48,22 +84,219 @@ impl<N: Neighborhood> AbstractPath<N> {
     /// A variant of [`Iterator::next()`](#impl-Iterator) that can resolve unknown segments
     /// of the Path. Use this method instead of `next()` when
     /// [`config.cache_paths`](crate::PathCacheConfig::cache_paths) is set to `false`.
-    pub fn safe_next(&mut self, get_cost: impl FnMut(Point) -> isize) -> Option<Point> {
+    pub fn safe_next(&mut self, get_cost: impl FnMut(Point) -> Option<C>) -> Option<Point> {
         self.internal_next(Some(get_cost))
     }
-    fn internal_next<F: FnMut(Point) -> isize>(&mut self, get_cost: Option<F>) -> Option<Point> {
-        if self.current_index.0 >= self.path.len() {
+    fn internal_next<F: FnMut(Point) -> Option<C>>(
+        &mut self,
+        get_cost: Option<F>,
+    ) -> Option<Point> {
+        if self.len() == 0 || self.current_index.0 >= self.path.len() {
+            return None;
+        }
+        let index = self.current_index;
+        let path = self.known_segment(index.0, get_cost);
+        let len = path.len();
+        let ret = Some(path[index.1]);
+        self.position = path[index.1];
+
+        self.current_index.1 += 1;
+        if self.current_index.1 >= len {
+            self.current_index.0 += 1;
+            self.current_index.1 = 1;
+        }
+
+        self.steps_taken += 1;
+
+        ret
+    }
+
+    /// A variant of [`DoubleEndedIterator::next_back()`](#impl-DoubleEndedIterator) that can
+    /// resolve unknown segments of the Path. Use this method instead of `next_back()` when
+    /// [`config.cache_paths`](crate::PathCacheConfig::cache_paths) is set to `false`.
+    pub fn safe_next_back(&mut self, get_cost: impl FnMut(Point) -> Option<C>) -> Option<Point> {
+        self.internal_next_back(Some(get_cost))
+    }
+    fn internal_next_back<F: FnMut(Point) -> Option<C>>(
+        &mut self,
+        get_cost: Option<F>,
+    ) -> Option<Point> {
+        if self.len() == 0 {
             return None;
         }
-        let mut current = &self.path[self.current_index.0];
-        if let PathSegment::Unknown { start, end, .. } = *current {
+        let (segments, points) = self.back_index;
+        let path = self.known_segment(self.path.len() - 1 - segments, get_cost);
+        let len = path.len();
+        // the first Point of a segment is the last Point of the previous one
+        let ret = Some(path[len - 1 - points]);
+
+        self.back_index.1 += 1;
+        if self.back_index.1 >= len - 1 {
+            self.back_index.0 += 1;
+            self.back_index.1 = 0;
+        }
+
+        self.steps_taken += 1;
+
+        ret
+    }
+
+    /// Returns the Point that [`next()`](#impl-Iterator) would return, without advancing the
+    /// Path.
+    ///
+    /// ## Panics
+    /// Panics if the Point is part of a segment that is not known yet. Use
+    /// [`resolve_segments_ahead`](AbstractPath::resolve_segments_ahead) first in those cases.
+    pub fn peek(&mut self) -> Option<Point> {
+        self.point_at(0)
+    }
+
+    /// Returns the Point that [`nth(k)`](#impl-Iterator) would return, without advancing the
+    /// Path.
+    ///
+    /// `point_at(0)` is the next Point of the Path, and `point_at(path.len() - 1)` the last one.
+    ///
+    /// ## Panics
+    /// Panics if the Point is part of a segment that is not known yet. Use
+    /// [`resolve_segments_ahead`](AbstractPath::resolve_segments_ahead) first in those cases.
+    ///
+    /// ## Examples
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let (width, height) = (20, 20);
+    /// # let cost_fn = |_| Some(1usize);
+    /// # let pathfinding = PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn,
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    /// let mut path = pathfinding.find_path((0, 0), (19, 0), cost_fn).unwrap();
+    ///
+    /// assert_eq!(path.peek(), Some((1, 0)));
+    /// assert_eq!(path.point_at(4), Some((5, 0)));
+    /// assert_eq!(path.point_at(18), Some((19, 0)));
+    /// assert_eq!(path.point_at(19), None);
+    ///
+    /// // nothing was consumed
+    /// assert_eq!(path.next(), Some((1, 0)));
+    ///
+    /// // walking back home
+    /// assert_eq!(path.next_back(), Some((19, 0)));
+    /// assert_eq!(path.next_back(), Some((18, 0)));
+    /// assert_eq!(path.len(), 16);
+    /// ```
+    pub fn point_at(&mut self, k: usize) -> Option<Point> {
+        if k >= self.len() {
+            return None;
+        }
+        let (mut segment, mut index) = (self.current_index.0, self.current_index.1 + k);
+        while index >= self.path[segment].len() {
+            index -= self.path[segment].len() - 1;
+            segment += 1;
+        }
+        let path = self.known_segment::<fn(Point) -> Option<C>>(segment, None);
+        Some(path[index])
+    }
+
+    /// Returns all remaining Points of the Path, without advancing it.
+    ///
+    /// ## Panics
+    /// Panics if the remaining Path is not fully known. Use
+    /// [`resolve_segments_ahead`](AbstractPath::resolve_segments_ahead) first in those cases.
+    pub fn remaining_points(&self) -> Vec<Point> {
+        self.clone().collect()
+    }
+
+    /// Provides an iterator over the segments of the Path.
+    ///
+    /// Every segment except for the first and last one connects two Nodes of the `PathCache`.
+    /// This includes the segments that were already iterated over.
+    ///
+    /// ## Examples
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let (width, height) = (20, 20);
+    /// # let cost_fn = |_| Some(1usize);
+    /// let pathfinding = PathCache::new(
+    ///     (width, height),
+    ///     cost_fn,
+    ///     ManhattanNeighborhood::new(width, height),
+    ///     PathCacheConfig {
+    ///         cache_paths: false,
+    ///         a_star_fallback: false,
+    ///         ..PathCacheConfig::with_chunk_size(3)
+    ///     },
+    /// );
+    /// let path = pathfinding.find_path((0, 0), (19, 19), cost_fn).unwrap();
+    ///
+    /// let waypoints: Vec<_> = path.segments().map(|segment| segment.end).collect();
+    /// assert_eq!(waypoints.last(), Some(&(19, 19)));
+    ///
+    /// let total: usize = path.segments().map(|segment| segment.cost).sum();
+    /// assert_eq!(total, path.cost());
+    /// assert!(path.segments().any(|segment| !segment.is_known));
+    /// ```
+    pub fn segments(&self) -> impl Iterator<Item = SegmentInfo<C>> + '_ {
+        self.path.iter().map(|segment| SegmentInfo {
+            start: segment.start(),
+            end: segment.end(),
+            cost: segment.cost(),
+            length: segment.len() - 1,
+            is_known: !matches!(segment, PathSegment::Unknown { .. }),
+        })
+    }
+
+    /// Makes sure that the segment at `index` is calculated and decoded and returns its Path
+    fn known_segment<F: FnMut(Point) -> Option<C>>(
+        &mut self,
+        index: usize,
+        get_cost: Option<F>,
+    ) -> &Path<Point, C> {
+        match &self.path[index] {
+            PathSegment::Known(_) => {}
+            PathSegment::Compact(path) => {
+                self.path[index] = PathSegment::Known(path.to_path());
+            }
+            PathSegment::Unknown { .. } => {
+                self.resolve_segment(
+                    index,
+                    get_cost.expect("Tried calling next() on a Path that is not fully known. Use safe_next() instead."),
+                );
+            }
+        }
+        match &self.path[index] {
+            PathSegment::Known(path) => path,
+            PathSegment::Compact(_) | PathSegment::Unknown { .. } => {
+                unreachable!()
+            }
+        }
+    }
+
+    /// Calculates the segment at `index` if it is unknown
+    fn resolve_segment(&mut self, index: usize, get_cost: impl FnMut(Point) -> Option<C>) {
+        let (start, end, cost, len) = match self.path[index] {
+            PathSegment::Unknown {
+                start,
+                end,
+                cost,
+                len,
+            } => (start, end, cost, len),
+            PathSegment::Known(_) | PathSegment::Compact(_) => return,
+        };
+        let cached = self.lru.as_ref().and_then(|lru| lru.get(start, end, cost));
+        let path = if let Some(path) = cached {
+            path
+        } else {
             let path = grid::a_star_search(
+                &mut SearchBuffers::default(),
                 &self.neighborhood,
-                |_| true,
-                get_cost.expect("Tried calling next() on a Path that is not fully known. Use safe_next() instead."),
+                (0, 0),
+                self.grid_size,
+                get_cost,
                 start,
                 end,
-                self.neighborhood.heuristic(start, end) * 2
+                Heuristic::exact(self.min_cost),
             )
             .unwrap_or_else(|| {
                 panic!(
@@ -71,37 +304,166 @@ impl<N: Neighborhood> AbstractPath<N> {
                     start, end
                 )
             });
-
-            self.path[self.current_index.0] = PathSegment::Known(path);
-            current = &self.path[self.current_index.0];
-
-            self.current_index.1 = 1; // paths include start and end, but we are already at start
-        }
-
-        let path = match current {
-            PathSegment::Known(path) => path,
-            PathSegment::Unknown { .. } => {
-                unreachable!()
+            if let Some(lru) = &self.lru {
+                lru.insert(start, end, path.clone());
             }
+            path
         };
+        // the search may find a different Path of the same cost with more or fewer steps
+        self.total_length = self.total_length - len + path.len();
+        self.path[index] = PathSegment::Known(path);
+    }
 
-        let ret = Some(path[self.current_index.1]);
-        self.current_index.1 += 1;
-        if self.current_index.1 >= path.len() {
-            self.current_index.0 += 1;
-            self.current_index.1 = 1;
+    /// Calculates the next `n` unknown segments of the Path ahead of time.
+    ///
+    /// This allows resolving the Path in one place where the cost function is available, and
+    /// iterating over it with [`next()`](#impl-Iterator) somewhere else. Use
+    /// [`is_fully_known`](AbstractPath::is_fully_known) to check if there are any unknown
+    /// segments left, or pass `usize::MAX` as `n` to resolve all of them.
+    ///
+    /// ## Examples
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let (width, height) = (20, 20);
+    /// # let cost_fn = |_| Some(1usize);
+    /// let pathfinding = PathCache::new(
+    ///     (width, height),
+    ///     cost_fn,
+    ///     ManhattanNeighborhood::new(width, height),
+    ///     PathCacheConfig {
+    ///         cache_paths: false,
+    ///         a_star_fallback: false,
+    ///         ..PathCacheConfig::with_chunk_size(3)
+    ///     },
+    /// );
+    ///
+    /// let mut path = pathfinding.find_path((0, 0), (19, 19), cost_fn).unwrap();
+    /// assert!(!path.is_fully_known());
+    ///
+    /// path.resolve_segments_ahead(usize::MAX, cost_fn);
+    /// assert!(path.is_fully_known());
+    ///
+    /// // can be iterated without the cost function
+    /// let points: Vec<_> = path.collect();
+    /// assert_eq!(points.last(), Some(&(19, 19)));
+    /// ```
+    pub fn resolve_segments_ahead(
+        &mut self,
+        n: usize,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+    ) {
+        let unknown: Vec<usize> = (self.current_index.0..self.path.len())
+            .filter(|&index| matches!(self.path[index], PathSegment::Unknown { .. }))
+            .take(n)
+            .collect();
+        for index in unknown {
+            self.resolve_segment(index, &mut get_cost);
         }
+    }
 
-        self.steps_taken += 1;
+    /// Returns `true` if the remaining Path has no unknown segments, meaning that it can be
+    /// iterated with [`next()`](#impl-Iterator) without panicking.
+    ///
+    /// This is always the case if [`config.cache_paths`](crate::PathCacheConfig::cache_paths) is
+    /// set to `true`.
+    pub fn is_fully_known(&self) -> bool {
+        self.path[self.current_index.0.min(self.path.len())..]
+            .iter()
+            .all(|segment| !matches!(segment, PathSegment::Unknown { .. }))
+    }
 
-        ret
+    /// Provides an iterator over the remaining Points of the Path, together with the cost of
+    /// walking there from the current position.
+    ///
+    /// Unknown segments are resolved as needed. The last cost is the same as
+    /// [`cost()`](AbstractPath::cost) if the Path was not iterated over yet.
+    ///
+    /// ## Examples
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let (width, height) = (20, 20);
+    /// let cost_fn = |(x, _): (usize, usize)| Some(x + 1);
+    /// # let pathfinding = PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn,
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    /// let path = pathfinding.find_path((0, 0), (3, 0), cost_fn).unwrap();
+    ///
+    /// // the cost of a step is the cost of the Tile that is left
+    /// let costs: Vec<_> = path.clone().cumulative_costs(cost_fn).collect();
+    /// assert_eq!(costs, vec![((1, 0), 1), ((2, 0), 3), ((3, 0), 6)]);
+    /// assert_eq!(costs.last().unwrap().1, path.cost());
+    /// ```
+    pub fn cumulative_costs(
+        mut self,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> impl Iterator<Item = (Point, C)> {
+        let mut total = C::ZERO;
+        std::iter::from_fn(move || {
+            let previous = self.position;
+            let next = self.safe_next(&mut get_cost)?;
+            // the Path from a Tile to itself doesn't leave that Tile
+            if next != previous {
+                total = total.saturating_add(get_cost(previous)?);
+            }
+            Some((next, total))
+        })
+    }
+
+    /// Shortens the Path to the part that can be walked from the current position with a cost
+    /// of at most `max_cost`.
+    ///
+    /// Unknown segments are resolved as needed, so the returned Path is always fully known. Its
+    /// [`cost()`](AbstractPath::cost) is the cost of the part that fits within the budget.
+    ///
+    /// ## Examples
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let (width, height) = (20, 20);
+    /// let cost_fn = |(x, _): (usize, usize)| Some(x + 1);
+    /// # let pathfinding = PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn,
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig {
+    /// #         cache_paths: false,
+    /// #         ..PathCacheConfig::with_chunk_size(3)
+    /// #     },
+    /// # );
+    /// let path = pathfinding.find_path((0, 0), (9, 0), cost_fn).unwrap();
+    ///
+    /// // the steps cost 1, 2, 3, 4, ...
+    /// let reachable = path.truncate_to_budget(8, cost_fn);
+    /// assert_eq!(reachable.cost(), 6);
+    /// assert_eq!(reachable.collect::<Vec<_>>(), vec![(1, 0), (2, 0), (3, 0)]);
+    /// ```
+    #[must_use]
+    pub fn truncate_to_budget(
+        self,
+        max_cost: C,
+        get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> AbstractPath<N, C> {
+        let neighborhood = self.neighborhood.clone();
+        let grid_size = self.grid_size;
+        let mut points = vec![self.position];
+        let mut cost = C::ZERO;
+        for (point, total) in self
+            .cumulative_costs(get_cost)
+            .take_while(|(_, total)| *total <= max_cost)
+        {
+            points.push(point);
+            cost = total;
+        }
+        AbstractPath::from_known_path(neighborhood, grid_size, Path::new(points, cost))
     }
 
     /// Resolves all unknown sections of the Path.
     ///
     /// if [`config.cache_paths`](crate::PathCacheConfig::cache_paths) is set to true,
     /// then calling this method is similar to calling `path.collect::<Vec<_>>()`.
-    pub fn resolve(mut self, mut get_cost: impl FnMut(Point) -> isize) -> Vec<Point> {
+    pub fn resolve(mut self, mut get_cost: impl FnMut(Point) -> Option<C>) -> Vec<Point> {
         let mut result = Vec::with_capacity(self.len());
 
         while let Some(pos) = self.safe_next(&mut get_cost) {
@@ -110,50 +472,71 @@ impl<N: Neighborhood> AbstractPath<N> {
         result
     }
 
-    pub(crate) fn new(neighborhood: N, end: Point) -> AbstractPath<N> {
+    pub(crate) fn new(
+        neighborhood: N,
+        grid_size: (usize, usize),
+        min_cost: C,
+        end: Point,
+    ) -> AbstractPath<N, C> {
         AbstractPath {
             neighborhood,
-            total_cost: 0,
+            grid_size,
+            min_cost,
+            total_cost: C::ZERO,
             total_length: 0,
             path: vec![],
             end,
+            position: end,
             current_index: (0, 1),
+            back_index: (0, 0),
             steps_taken: 0,
+            lru: None,
         }
     }
 
-    pub(crate) fn from_known_path(neighborhood: N, path: Path<Point>) -> AbstractPath<N> {
+    pub(crate) fn from_known_path(
+        neighborhood: N,
+        grid_size: (usize, usize),
+        path: Path<Point, C>,
+    ) -> AbstractPath<N, C> {
         let end = path[path.len() - 1];
         AbstractPath {
             total_cost: path.cost(),
             total_length: path.len() - 1,
+            position: path[0],
             path: vec![PathSegment::Known(path)],
-            ..AbstractPath::new(neighborhood, end)
+            // a fully known Path never has to resolve a segment
+            ..AbstractPath::new(neighborhood, grid_size, C::ZERO, end)
         }
     }
 
-    pub(crate) fn add_path_segment(&mut self, path: PathSegment) -> &mut Self {
+    /// Stores the Paths of unknown segments in `lru` once they are resolved
+    pub(crate) fn set_lru(&mut self, lru: LruHandle<C>) {
+        self.lru = Some(lru);
+    }
+
+    pub(crate) fn add_path_segment(&mut self, path: PathSegment<C>) -> &mut Self {
         assert!(
             self.end == path.start(),
             "Added disconnected PathSegment: expected {:?}, got {:?}",
             self.end,
             path.start()
         );
-        self.total_cost += path.cost();
+        self.total_cost = self.total_cost.saturating_add(path.cost());
         self.total_length += path.len() - 1;
         self.end = path.end();
         self.path.push(path);
         self
     }
 
-    pub(crate) fn add_path(&mut self, path: Path<Point>) -> &mut Self {
+    pub(crate) fn add_path(&mut self, path: Path<Point, C>) -> &mut Self {
         assert!(
             self.end == path[0],
             "Added disconnected Path: expected {:?}, got {:?}",
             self.end,
             path[0]
         );
-        self.total_cost += path.cost();
+        self.total_cost = self.total_cost.saturating_add(path.cost());
         self.total_length += path.len() - 1;
         self.end = path[path.len() - 1];
         self.path.push(PathSegment::Known(path));
@@ -161,21 +544,21 @@ impl<N: Neighborhood> AbstractPath<N> {
     }
 
     #[allow(dead_code)]
-    pub(crate) fn add_node(&mut self, node: Point, cost: Cost, len: usize) -> &mut Self {
+    pub(crate) fn add_node(&mut self, node: Point, cost: C, len: usize) -> &mut Self {
         self.path.push(PathSegment::Unknown {
             start: self.end,
             end: node,
             cost,
             len,
         });
-        self.total_cost += cost;
+        self.total_cost = self.total_cost.saturating_add(cost);
         self.total_length += len;
         self.end = node;
         self
     }
 }
 
-impl<N: Neighborhood> Iterator for AbstractPath<N> {
+impl<N: Neighborhood, C: Cost> Iterator for AbstractPath<N, C> {
     type Item = Point;
     /// See [`Iterator::next`]
     ///
@@ -183,7 +566,7 @@ impl<N: Neighborhood> Iterator for AbstractPath<N> {
     /// Panics if a segment of the Path is not known because [`config.cache_paths`](crate::PathCacheConfig::cache_paths)
     /// is set to `false`. Use [`safe_next`](AbstractPath::safe_next) in those cases.
     fn next(&mut self) -> Option<Point> {
-        self.internal_next::<fn((usize, usize)) -> isize>(None)
+        self.internal_next::<fn(Point) -> Option<C>>(None)
     }
     fn size_hint(&self) -> (usize, Option<usize>) {
         let len = self.total_length - self.steps_taken;
@@ -192,10 +575,22 @@ impl<N: Neighborhood> Iterator for AbstractPath<N> {
     fn count(self) -> usize {
         self.len()
     }
-    fn last(self) -> Option<Point> {
-        Some(self.end)
+    fn last(mut self) -> Option<Point> {
+        if self.len() == 0 {
+            None
+        } else if self.back_index == (0, 0) {
+            Some(self.end)
+        } else {
+            self.next_back()
+        }
     }
     fn nth(&mut self, step: usize) -> Option<Point> {
+        if step >= self.len() {
+            self.steps_taken = self.total_length;
+            self.current_index = (self.path.len(), 1);
+            return None;
+        }
+        self.steps_taken += step;
         self.current_index.1 += step;
         while self.current_index.0 < self.path.len() {
             let current_len = self.path[self.current_index.0].len();
@@ -208,8 +603,18 @@ impl<N: Neighborhood> Iterator for AbstractPath<N> {
         self.next()
     }
 }
-impl<N: Neighborhood> ExactSizeIterator for AbstractPath<N> {}
-impl<N: Neighborhood> std::iter::FusedIterator for AbstractPath<N> {}
+impl<N: Neighborhood, C: Cost> DoubleEndedIterator for AbstractPath<N, C> {
+    /// See [`DoubleEndedIterator::next_back`]
+    ///
+    /// ## Panics
+    /// Panics if a segment of the Path is not known because [`config.cache_paths`](crate::PathCacheConfig::cache_paths)
+    /// is set to `false`. Use [`safe_next_back`](AbstractPath::safe_next_back) in those cases.
+    fn next_back(&mut self) -> Option<Point> {
+        self.internal_next_back::<fn(Point) -> Option<C>>(None)
+    }
+}
+impl<N: Neighborhood, C: Cost> ExactSizeIterator for AbstractPath<N, C> {}
+impl<N: Neighborhood, C: Cost> std::iter::FusedIterator for AbstractPath<N, C> {}
 
 #[cfg(test)]
 mod tests {
@@ -220,7 +625,8 @@ mod tests {
         let neigh = crate::neighbors::ManhattanNeighborhood::new(100, 100);
         let mut path = AbstractPath::from_known_path(
             neigh,
-            Path::from_slice(&[(99, 99), (0, 0), (1, 1), (2, 2), (3, 3)], 3),
+            (100, 100),
+            Path::from_slice(&[(99, 99), (0, 0), (1, 1), (2, 2), (3, 3)], 3usize),
         );
         path.add_path(Path::from_slice(&[(3, 3), (4, 4), (5, 5)], 5));
         assert_eq!(path.next(), Some((0, 0)));
@@ -239,9 +645,10 @@ mod tests {
         let neigh = crate::neighbors::ManhattanNeighborhood::new(100, 100);
         let mut path = AbstractPath::from_known_path(
             neigh,
+            (100, 100),
             Path::from_slice(
                 &[(99, 99), (0, 0), (1, 1), (2, 2), (3, 3), (4, 4), (5, 5)],
-                3,
+                3usize,
             ),
         );
         {
@@ -266,12 +673,113 @@ mod tests {
         assert_eq!(path.nth(100), None);
     }
 
+    #[test]
+    fn next_back() {
+        let neigh = crate::neighbors::ManhattanNeighborhood::new(100, 100);
+        let mut path = AbstractPath::from_known_path(
+            neigh,
+            (100, 100),
+            Path::from_slice(&[(99, 99), (0, 0), (1, 1), (2, 2), (3, 3)], 3usize),
+        );
+        path.add_path(Path::from_slice(&[(3, 3), (4, 4), (5, 5)], 5));
+
+        let forward = path.clone().collect::<Vec<_>>();
+        let mut backward = path.clone().rev().collect::<Vec<_>>();
+        backward.reverse();
+        assert_eq!(forward, backward);
+
+        assert_eq!(path.next_back(), Some((5, 5)));
+        assert_eq!(path.next(), Some((0, 0)));
+        assert_eq!(path.next_back(), Some((4, 4)));
+        assert_eq!(path.len(), 3);
+        assert_eq!(path.clone().last(), Some((3, 3)));
+        assert_eq!(path.next_back(), Some((3, 3)));
+        assert_eq!(path.next_back(), Some((2, 2)));
+        assert_eq!(path.next(), Some((1, 1)));
+        assert_eq!(path.len(), 0);
+        assert_eq!(path.next(), None);
+        assert_eq!(path.next_back(), None);
+        assert_eq!(path.clone().last(), None);
+    }
+
+    #[test]
+    fn point_at() {
+        let neigh = crate::neighbors::ManhattanNeighborhood::new(100, 100);
+        let mut path = AbstractPath::from_known_path(
+            neigh,
+            (100, 100),
+            Path::from_slice(&[(99, 99), (0, 0), (1, 1), (2, 2), (3, 3)], 3usize),
+        );
+        path.add_path(Path::from_slice(&[(3, 3), (4, 4), (5, 5)], 5));
+
+        let points = path.remaining_points();
+        for (k, &point) in points.iter().enumerate() {
+            assert_eq!(path.point_at(k), Some(point));
+            assert_eq!(path.clone().nth(k), Some(point));
+        }
+        assert_eq!(path.point_at(points.len()), None);
+
+        assert_eq!(path.peek(), Some((0, 0)));
+        path.nth(2);
+        assert_eq!(path.peek(), Some((3, 3)));
+        assert_eq!(path.remaining_points(), vec![(3, 3), (4, 4), (5, 5)]);
+        path.next_back();
+        assert_eq!(path.point_at(1), Some((4, 4)));
+        assert_eq!(path.point_at(2), None);
+        assert_eq!(path.remaining_points(), vec![(3, 3), (4, 4)]);
+    }
+
+    #[test]
+    fn segments() {
+        use crate::prelude::*;
+        let cost_fn = |(x, y): (usize, usize)| {
+            if x == 7 && y < 20 {
+                None
+            } else {
+                Some(1 + x % 3)
+            }
+        };
+        let pathfinding = PathCache::new(
+            (25, 25),
+            cost_fn,
+            MooreNeighborhood::new(25, 25),
+            PathCacheConfig {
+                cache_paths: false,
+                ..PathCacheConfig::with_chunk_size(4)
+            },
+        );
+        let mut path = pathfinding.find_path((0, 0), (24, 2), cost_fn).unwrap();
+        let expected = path.clone().resolve(cost_fn);
+
+        let segments = path.segments().collect::<Vec<_>>();
+        assert_eq!(segments[0].start, (0, 0));
+        assert_eq!(segments.last().unwrap().end, (24, 2));
+        for pair in segments.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+        assert_eq!(
+            segments.iter().map(|s| s.length).sum::<usize>(),
+            path.length()
+        );
+        assert_eq!(segments.iter().map(|s| s.cost).sum::<usize>(), path.cost());
+        assert!(segments.iter().any(|s| !s.is_known));
+
+        let mut backward = vec![];
+        while let Some(p) = path.safe_next_back(cost_fn) {
+            backward.push(p);
+        }
+        backward.reverse();
+        assert_eq!(backward, expected);
+        assert!(path.segments().all(|s| s.is_known));
+    }
+
     #[test]
     fn size_hint_and_len() {
         let neigh = crate::neighbors::ManhattanNeighborhood::new(100, 100);
         let mut path = AbstractPath::from_known_path(
             neigh,
-            Path::from_slice(&[(99, 99), (0, 0), (1, 1), (2, 2), (3, 3)], 3),
+            (100, 100),
+            Path::from_slice(&[(99, 99), (0, 0), (1, 1), (2, 2), (3, 3)], 3usize),
         );
         path.add_path(Path::from_slice(&[(3, 3), (4, 4), (5, 5)], 5));
         assert_eq!(path.size_hint(), (6, Some(6)));
@@ -312,7 +820,8 @@ mod tests {
         let neigh = crate::neighbors::ManhattanNeighborhood::new(100, 100);
         let mut path = AbstractPath::from_known_path(
             neigh,
-            Path::from_slice(&[(99, 99), (0, 0), (1, 1), (2, 2), (3, 3)], 3),
+            (100, 100),
+            Path::from_slice(&[(99, 99), (0, 0), (1, 1), (2, 2), (3, 3)], 3usize),
         );
         path.add_path(Path::from_slice(&[(3, 3), (4, 4), (5, 5)], 5));
 
@@ -333,21 +842,169 @@ mod tests {
             println!("w: {:?}", w);
             let pathfinding = PathCache::new(
                 (w, w),
-                |_| 1,
+                |_| Some(1usize),
                 MooreNeighborhood::new(w, w),
                 PathCacheConfig::default(),
             );
-            let path = pathfinding.find_path((0, 0), (w - 1, w - 1), |_| 1);
+            let path = pathfinding.find_path((0, 0), (w - 1, w - 1), |_| Some(1));
             assert!(path.is_some());
 
-            let pathfinding = crate::PathCache::new(
+            let pathfinding = PathCache::new(
                 (w, w),
-                |_| 1,
+                |_| Some(1usize),
                 ManhattanNeighborhood::new(w, w),
                 PathCacheConfig::default(),
             );
-            let path = pathfinding.find_path((0, 0), (w - 1, w - 1), |_| 1);
+            let path = pathfinding.find_path((0, 0), (w - 1, w - 1), |_| Some(1));
             assert!(path.is_some());
         }
     }
+
+    #[test]
+    fn resolve_segments_ahead() {
+        use crate::prelude::*;
+        let cost_fn = |(x, y): (usize, usize)| {
+            if x == 7 && y < 20 {
+                None
+            } else {
+                Some(1 + x % 3)
+            }
+        };
+        let pathfinding = PathCache::new(
+            (25, 25),
+            cost_fn,
+            MooreNeighborhood::new(25, 25),
+            PathCacheConfig {
+                cache_paths: false,
+                ..PathCacheConfig::with_chunk_size(4)
+            },
+        );
+        let path = pathfinding.find_path((0, 0), (24, 2), cost_fn).unwrap();
+        let expected = path.clone().resolve(cost_fn);
+        assert!(!path.is_fully_known());
+
+        // step by step
+        let mut stepped = path.clone();
+        let mut points = vec![];
+        while !stepped.is_fully_known() {
+            // iterate up to the next unknown segment, then resolve it
+            let next_unknown = (stepped.current_index.0..stepped.path.len())
+                .find(|&i| matches!(stepped.path[i], PathSegment::Unknown { .. }))
+                .unwrap();
+            while stepped.current_index.0 < next_unknown {
+                points.push(stepped.next().unwrap());
+            }
+            stepped.resolve_segments_ahead(1, cost_fn);
+        }
+        points.extend(stepped);
+        assert_eq!(points, expected);
+
+        // all at once
+        let mut resolved = path;
+        resolved.resolve_segments_ahead(usize::MAX, cost_fn);
+        assert!(resolved.is_fully_known());
+        assert_eq!(resolved.collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn resolved_length() {
+        use crate::prelude::*;
+        let grid: [[usize; 9]; 6] = [
+            [0, 3, 2, 1, 0, 0, 3, 0, 2],
+            [1, 2, 1, 0, 3, 1, 3, 1, 1],
+            [2, 2, 0, 3, 0, 1, 0, 0, 2],
+            [3, 2, 1, 1, 1, 3, 3, 2, 3],
+            [0, 3, 1, 1, 0, 0, 3, 3, 1],
+            [1, 1, 3, 3, 1, 1, 3, 2, 3],
+        ];
+        let cost_fn = |(x, y): (usize, usize)| Some(grid[y][x]).filter(|&cost| cost > 0);
+        let pathfinding = PathCache::new(
+            (9, 6),
+            cost_fn,
+            MooreNeighborhood::new(9, 6),
+            PathCacheConfig {
+                cache_paths: false,
+                a_star_fallback: false,
+                ..PathCacheConfig::with_chunk_size(3)
+            },
+        );
+        let path = pathfinding.find_path((8, 2), (3, 2), cost_fn).unwrap();
+
+        // the resolved segments take more steps than the ones that were cached
+        let mut resolved = path.clone();
+        resolved.resolve_segments_ahead(usize::MAX, cost_fn);
+        assert!(resolved.length() > path.length());
+
+        let points = path.clone().resolve(cost_fn);
+        assert_eq!(points.last(), Some(&(3, 2)));
+        assert_eq!(points.len(), resolved.length());
+
+        let mut backward = vec![];
+        let mut path = path;
+        while let Some(point) = path.safe_next_back(cost_fn) {
+            backward.push(point);
+        }
+        assert_eq!(backward.first(), Some(&(3, 2)));
+        assert_eq!(backward.len(), points.len());
+    }
+
+    #[test]
+    fn truncate_to_budget() {
+        use crate::prelude::*;
+        let cost_fn = |(x, y): (usize, usize)| {
+            if x == 7 && y < 20 {
+                None
+            } else {
+                Some(1 + x % 3)
+            }
+        };
+        let pathfinding = PathCache::new(
+            (25, 25),
+            cost_fn,
+            MooreNeighborhood::new(25, 25),
+            PathCacheConfig {
+                cache_paths: false,
+                ..PathCacheConfig::with_chunk_size(4)
+            },
+        );
+        let mut path = pathfinding.find_path((0, 0), (24, 2), cost_fn).unwrap();
+        let expected = path.clone().resolve(cost_fn);
+
+        let costs = path.clone().cumulative_costs(cost_fn).collect::<Vec<_>>();
+        assert_eq!(costs.iter().map(|(p, _)| *p).collect::<Vec<_>>(), expected);
+        assert_eq!(costs.last().unwrap().1, path.cost());
+        assert!(costs.windows(2).all(|w| w[0].1 < w[1].1));
+
+        let everything = path.clone().truncate_to_budget(path.cost(), cost_fn);
+        assert_eq!(everything.cost(), path.cost());
+        assert_eq!(everything.collect::<Vec<_>>(), expected);
+
+        let nothing = path.clone().truncate_to_budget(0, cost_fn);
+        assert_eq!(nothing.len(), 0);
+        assert_eq!(nothing.cost(), 0);
+
+        // a Path that doesn't go anywhere
+        let stay = pathfinding.find_path((3, 3), (3, 3), cost_fn).unwrap();
+        let costs_of_stay = stay.clone().cumulative_costs(cost_fn).collect::<Vec<_>>();
+        assert_eq!(costs_of_stay, vec![((3, 3), 0)]);
+        let stay = stay.truncate_to_budget(0, cost_fn);
+        assert_eq!(stay.cost(), 0);
+        assert_eq!(stay.collect::<Vec<_>>(), vec![(3, 3)]);
+
+        // the costs start at the current position
+        path.safe_next(cost_fn);
+        path.safe_next(cost_fn);
+        let offset = costs[1].1;
+        let half = path.cost() / 2;
+        let truncated = path.truncate_to_budget(half, cost_fn);
+        let reachable = costs[2..]
+            .iter()
+            .take_while(|(_, cost)| cost - offset <= half)
+            .collect::<Vec<_>>();
+        assert_eq!(truncated.cost(), reachable.last().unwrap().1 - offset);
+        assert_eq!(
+            truncated.collect::<Vec<_>>(),
+            reachable.iter().map(|(p, _)| *p).collect::<Vec<_>>()
+        );
+    }
 }
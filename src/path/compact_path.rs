@@ -0,0 +1,196 @@
+use super::Path;
+use crate::{cost::Cost, Point};
+
+use std::sync::Arc;
+
+/// The steps that can be encoded in a [`CompactPath`], indexed by their code
+const DIRECTIONS: [(isize, isize); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+/// The number of bits used to store one step
+const BITS_PER_STEP: usize = 3;
+
+/// A Path on the Grid that only stores its first Point and the direction of every step.
+///
+/// Every step takes up 3 bits instead of the 16 bytes of a full Point, but the Path has to be
+/// decoded with [`to_path`](CompactPath::to_path) before it can be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactPath<C> {
+    start: Point,
+    end: Point,
+    steps: Arc<[u8]>,
+    len: usize,
+    cost: C,
+    is_reversed: bool,
+}
+
+impl<C: Cost> CompactPath<C> {
+    /// Encodes `path`.
+    ///
+    /// Returns `None` if the Path contains steps that are not between neighboring Tiles (as
+    /// defined by a [`MooreNeighborhood`](crate::neighbors::MooreNeighborhood)).
+    pub fn from_path(path: &Path<Point, C>) -> Option<CompactPath<C>> {
+        if path.is_empty() {
+            return None;
+        }
+        let mut steps = vec![0u8; ((path.len() - 1) * BITS_PER_STEP).div_ceil(8)];
+        let mut points = path.iter();
+        let start = *points.next()?;
+        let mut prev = start;
+        for (i, &p) in points.enumerate() {
+            let delta = (offset(prev.0, p.0)?, offset(prev.1, p.1)?);
+            let (code, _) = (0u16..).zip(DIRECTIONS).find(|(_, d)| *d == delta)?;
+
+            let bit = i * BITS_PER_STEP;
+            let [low, high] = (code << (bit % 8)).to_le_bytes();
+            steps[bit / 8] |= low;
+            if high != 0 {
+                steps[bit / 8 + 1] |= high;
+            }
+            prev = p;
+        }
+        Some(CompactPath {
+            start,
+            end: prev,
+            steps: steps.into(),
+            len: path.len(),
+            cost: path.cost(),
+            is_reversed: false,
+        })
+    }
+
+    /// Decodes the Path
+    pub fn to_path(&self) -> Path<Point, C> {
+        let mut points = Vec::with_capacity(self.len);
+        let mut current = self.start;
+        points.push(current);
+        for i in 0..self.len - 1 {
+            let (dx, dy) = DIRECTIONS[self.step(i)];
+            current = (
+                current.0.wrapping_add_signed(dx),
+                current.1.wrapping_add_signed(dy),
+            );
+            points.push(current);
+        }
+        if self.is_reversed {
+            points.reverse();
+        }
+        Path::new(points, self.cost)
+    }
+
+    /// The code of the step with the given index
+    fn step(&self, index: usize) -> usize {
+        let bit = index * BITS_PER_STEP;
+        let low = u16::from(self.steps[bit / 8]);
+        let high = u16::from(self.steps.get(bit / 8 + 1).copied().unwrap_or(0));
+        usize::from(((high << 8 | low) >> (bit % 8)) & 0b111)
+    }
+
+    pub fn cost(&self) -> C {
+        self.cost
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The number of bytes allocated for the steps of this Path
+    pub fn heap_size(&self) -> usize {
+        // the reference counts of the Arc are stored in the same allocation
+        2 * size_of::<usize>() + self.steps.len()
+    }
+
+    pub fn start(&self) -> Point {
+        if self.is_reversed {
+            self.end
+        } else {
+            self.start
+        }
+    }
+
+    pub fn end(&self) -> Point {
+        if self.is_reversed {
+            self.start
+        } else {
+            self.end
+        }
+    }
+
+    pub fn reversed(&self, start_cost: C, end_cost: C) -> CompactPath<C> {
+        CompactPath {
+            steps: self.steps.clone(),
+            cost: (self.cost - start_cost).saturating_add(end_cost),
+            is_reversed: !self.is_reversed,
+            ..*self
+        }
+    }
+}
+
+/// The difference between two neighboring coordinates
+fn offset(from: usize, to: usize) -> Option<isize> {
+    if to == from {
+        Some(0)
+    } else if to == from + 1 {
+        Some(1)
+    } else if to + 1 == from {
+        Some(-1)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let points = [
+            (5, 5),
+            (6, 5),
+            (7, 6),
+            (7, 7),
+            (6, 8),
+            (5, 8),
+            (4, 7),
+            (4, 6),
+            (5, 5),
+            (5, 4),
+        ];
+        let path = Path::from_slice(&points, 9usize);
+        let compact = CompactPath::from_path(&path).unwrap();
+        assert_eq!(compact.len(), points.len());
+        assert_eq!(compact.start(), (5, 5));
+        assert_eq!(compact.end(), (5, 4));
+        assert_eq!(compact.to_path(), points.to_vec());
+        assert_eq!(compact.to_path().cost(), 9);
+
+        let reversed = compact.reversed(1, 2);
+        assert_eq!(reversed.start(), (5, 4));
+        assert_eq!(reversed.end(), (5, 5));
+        assert_eq!(reversed.cost(), 10);
+        let mut expected = points.to_vec();
+        expected.reverse();
+        assert_eq!(reversed.to_path(), expected);
+
+        let reversed_path = path.reversed(1, 2);
+        let compact = CompactPath::from_path(&reversed_path).unwrap();
+        assert_eq!(compact.to_path(), expected);
+    }
+
+    #[test]
+    fn invalid_steps() {
+        let path = Path::from_slice(&[(0, 0), (2, 0)], 1usize);
+        assert!(CompactPath::from_path(&path).is_none());
+
+        let path = Path::from_slice(&[(0, 0), (0, 0)], 1usize);
+        assert!(CompactPath::from_path(&path).is_none());
+    }
+}
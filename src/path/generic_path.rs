@@ -1,17 +1,17 @@
-use super::Cost;
+use crate::cost::Cost;
 
 use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Path<P> {
+pub struct Path<P, C> {
     path: Arc<[P]>,
-    cost: Cost,
+    cost: C,
     is_reversed: bool,
 }
 
 #[allow(dead_code)]
-impl<P> Path<P> {
-    pub fn new(path: Vec<P>, cost: Cost) -> Path<P> {
+impl<P, C: Copy> Path<P, C> {
+    pub fn new(path: Vec<P>, cost: C) -> Path<P, C> {
         Path {
             path: path.into(),
             cost,
@@ -19,7 +19,7 @@ impl<P> Path<P> {
         }
     }
 
-    pub fn from_slice(path: &[P], cost: Cost) -> Path<P>
+    pub fn from_slice(path: &[P], cost: C) -> Path<P, C>
     where
         P: Clone,
     {
@@ -30,7 +30,7 @@ impl<P> Path<P> {
         }
     }
 
-    pub fn cost(&self) -> Cost {
+    pub fn cost(&self) -> C {
         self.cost
     }
 
@@ -42,14 +42,32 @@ impl<P> Path<P> {
         self.path.is_empty()
     }
 
-    pub fn reversed(&self, start_cost: Cost, end_cost: Cost) -> Path<P> {
+    /// The number of bytes allocated for the Points of this Path
+    pub fn heap_size(&self) -> usize {
+        // the reference counts of the Arc are stored in the same allocation
+        2 * size_of::<usize>() + self.path.len() * size_of::<P>()
+    }
+
+    pub fn reversed(&self, start_cost: C, end_cost: C) -> Path<P, C>
+    where
+        C: Cost,
+    {
         Path {
             path: self.path.clone(),
-            cost: self.cost - start_cost + end_cost,
+            cost: (self.cost - start_cost).saturating_add(end_cost),
             is_reversed: !self.is_reversed,
         }
     }
 
+    /// The same Path with a different cost
+    pub fn with_cost(&self, cost: C) -> Path<P, C> {
+        Path {
+            path: self.path.clone(),
+            cost,
+            is_reversed: self.is_reversed,
+        }
+    }
+
     /// Returns an Iterator over the Path
     pub fn iter(&self) -> Iter<P> {
         Iter {
@@ -61,7 +79,7 @@ impl<P> Path<P> {
 
 use std::ops::Index;
 
-impl<P> Index<usize> for Path<P> {
+impl<P, C> Index<usize> for Path<P, C> {
     type Output = P;
     fn index(&self, index: usize) -> &P {
         let index = if self.is_reversed {
@@ -105,14 +123,14 @@ impl<P> DoubleEndedIterator for Iter<'_, P> {
 impl<P> ExactSizeIterator for Iter<'_, P> {}
 impl<P> std::iter::FusedIterator for Iter<'_, P> {}
 
-impl<P: PartialEq> PartialEq<Vec<P>> for Path<P> {
+impl<P: PartialEq, C: Copy> PartialEq<Vec<P>> for Path<P, C> {
     fn eq(&self, rhs: &Vec<P>) -> bool {
         // we can't just use slice's eq because self might be reversed
         self.len() == rhs.len() && self.iter().zip(rhs.iter()).all(|(a, b)| a == b)
     }
 }
 
-impl<'a, P: PartialEq> PartialEq<&'a [P]> for Path<P> {
+impl<'a, P: PartialEq, C: Copy> PartialEq<&'a [P]> for Path<P, C> {
     fn eq(&self, rhs: &&'a [P]) -> bool {
         // we can't just use slice's eq because self might be reversed
         self.len() == rhs.len() && self.iter().zip(rhs.iter()).all(|(a, b)| a == b)
@@ -121,20 +139,20 @@ impl<'a, P: PartialEq> PartialEq<&'a [P]> for Path<P> {
 
 use std::cmp::Ordering;
 
-impl<P: Eq> Ord for Path<P> {
-    fn cmp(&self, other: &Path<P>) -> Ordering {
+impl<P: Eq, C: Ord> Ord for Path<P, C> {
+    fn cmp(&self, other: &Path<P, C>) -> Ordering {
         self.cost.cmp(&other.cost)
     }
 }
 
-impl<P: PartialEq> PartialOrd for Path<P> {
-    fn partial_cmp(&self, other: &Path<P>) -> Option<Ordering> {
+impl<P: PartialEq, C: Ord> PartialOrd for Path<P, C> {
+    fn partial_cmp(&self, other: &Path<P, C>) -> Option<Ordering> {
         Some(self.cost.cmp(&other.cost))
     }
 }
 
 use std::fmt;
-impl<P: fmt::Display> fmt::Display for Path<P> {
+impl<P: fmt::Display, C: fmt::Display> fmt::Display for Path<P, C> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         write!(fmt, "Path[Cost = {}]: ", self.cost)?;
         if self.path.is_empty() {
@@ -0,0 +1,225 @@
+use super::Path;
+use crate::{cost::Cost, Point};
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// The Paths between two Nodes (in either direction)
+type Key = (Point, Point);
+
+/// A size-limited cache of the Paths between Nodes that were resolved while iterating over an
+/// [`AbstractPath`](super::AbstractPath).
+///
+/// Used when [`cache_paths`](crate::PathCacheConfig::cache_paths) is `false` and
+/// [`path_lru_budget`](crate::PathCacheConfig::path_lru_budget) is not `0`. Once the stored
+/// Paths take up more than the budget, the least recently used ones are evicted.
+#[derive(Debug)]
+pub(crate) struct PathLru<C> {
+    entries: hashbrown::HashMap<Key, (Path<Point, C>, u64)>,
+    /// the keys of `entries`, ordered by the time of their last use
+    order: BTreeMap<u64, Key>,
+    tick: u64,
+    bytes: usize,
+    budget: usize,
+    /// incremented whenever entries are invalidated, so that Paths which were created before
+    /// that can't insert outdated segments
+    generation: u64,
+}
+
+impl<C: Cost> PathLru<C> {
+    fn new(budget: usize) -> PathLru<C> {
+        PathLru {
+            entries: hashbrown::HashMap::default(),
+            order: BTreeMap::new(),
+            tick: 0,
+            bytes: 0,
+            budget,
+            generation: 0,
+        }
+    }
+
+    fn key(start: Point, end: Point) -> Key {
+        (start.min(end), start.max(end))
+    }
+
+    fn entry_size(path: &Path<Point, C>) -> usize {
+        path.heap_size() + size_of::<(Key, (Path<Point, C>, u64))>() + size_of::<(u64, Key)>()
+    }
+
+    fn get(&mut self, start: Point, end: Point, cost: C) -> Option<Path<Point, C>> {
+        let key = Self::key(start, end);
+        let (path, last_used) = self.entries.get_mut(&key)?;
+
+        self.order.remove(last_used);
+        self.tick += 1;
+        *last_used = self.tick;
+        self.order.insert(self.tick, key);
+
+        // the cost of a Path depends on its direction, so the one of the segment is used
+        let path = if path[0] == start {
+            path.with_cost(cost)
+        } else {
+            path.reversed(path.cost(), cost)
+        };
+        Some(path)
+    }
+
+    fn insert(&mut self, start: Point, end: Point, path: Path<Point, C>) {
+        let size = Self::entry_size(&path);
+        if size > self.budget {
+            return;
+        }
+        let key = Self::key(start, end);
+        self.remove(key);
+
+        while self.bytes + size > self.budget {
+            let (_, oldest) = self
+                .order
+                .pop_first()
+                .expect("Internal Error #1 in PathLru. Please report this");
+            self.remove(oldest);
+        }
+
+        self.tick += 1;
+        self.bytes += size;
+        self.entries.insert(key, (path, self.tick));
+        self.order.insert(self.tick, key);
+    }
+
+    fn remove(&mut self, key: Key) {
+        if let Some((path, last_used)) = self.entries.remove(&key) {
+            self.order.remove(&last_used);
+            self.bytes -= Self::entry_size(&path);
+        }
+    }
+}
+
+/// A [`PathLru`] that is shared between a [`PathCache`](crate::PathCache) and the
+/// [`AbstractPath`](super::AbstractPath)s it creates.
+///
+/// Cloning a `SharedPathLru` creates a new, empty cache with the same budget, because clones of a
+/// `PathCache` can be changed independently.
+#[derive(Debug)]
+pub(crate) struct SharedPathLru<C>(Arc<Mutex<PathLru<C>>>);
+
+impl<C: Cost> SharedPathLru<C> {
+    pub fn new(budget: usize) -> SharedPathLru<C> {
+        SharedPathLru(Arc::new(Mutex::new(PathLru::new(budget))))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PathLru<C>> {
+        // the cache is never left in an inconsistent state, so a panic on another thread
+        // doesn't matter
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// A handle for an `AbstractPath` to access the cache
+    pub fn handle(&self) -> LruHandle<C> {
+        LruHandle {
+            lru: self.0.clone(),
+            generation: self.lock().generation,
+        }
+    }
+
+    /// Looks up the Path between two Nodes with the given cost
+    pub fn get(&self, start: Point, end: Point, cost: C) -> Option<Path<Point, C>> {
+        self.lock().get(start, end, cost)
+    }
+
+    /// Removes all Paths that start or end at a Point for which `outdated` returns `true`
+    pub fn invalidate(&self, mut outdated: impl FnMut(Point) -> bool) {
+        let mut lru = self.lock();
+        lru.generation += 1;
+        let keys: Vec<Key> = lru
+            .entries
+            .keys()
+            .filter(|(a, b)| outdated(*a) || outdated(*b))
+            .copied()
+            .collect();
+        for key in keys {
+            lru.remove(key);
+        }
+    }
+
+    /// The number of bytes used by the stored Paths
+    pub fn bytes(&self) -> usize {
+        self.lock().bytes
+    }
+}
+
+impl<C: Cost> Clone for SharedPathLru<C> {
+    fn clone(&self) -> Self {
+        SharedPathLru::new(self.lock().budget)
+    }
+}
+
+/// The access of an [`AbstractPath`](super::AbstractPath) to a [`SharedPathLru`]
+#[derive(Debug, Clone)]
+pub(crate) struct LruHandle<C> {
+    lru: Arc<Mutex<PathLru<C>>>,
+    generation: u64,
+}
+
+impl<C: Cost> LruHandle<C> {
+    fn lock(&self) -> MutexGuard<'_, PathLru<C>> {
+        self.lru.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn get(&self, start: Point, end: Point, cost: C) -> Option<Path<Point, C>> {
+        let mut lru = self.lock();
+        if lru.generation != self.generation {
+            return None;
+        }
+        lru.get(start, end, cost)
+    }
+
+    pub fn insert(&self, start: Point, end: Point, path: Path<Point, C>) {
+        let mut lru = self.lock();
+        if lru.generation == self.generation {
+            lru.insert(start, end, path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eviction() {
+        let path = |x: usize| Path::from_slice(&[(x, 0), (x, 1), (x, 2)], 2usize);
+        let size = PathLru::entry_size(&path(0));
+        let lru = SharedPathLru::new(size * 2);
+        let handle = lru.handle();
+
+        handle.insert((0, 0), (0, 2), path(0));
+        handle.insert((1, 0), (1, 2), path(1));
+        assert_eq!(lru.bytes(), size * 2);
+
+        // using the first Path makes the second one the least recently used
+        assert!(lru.get((0, 0), (0, 2), 2).is_some());
+        handle.insert((2, 0), (2, 2), path(2));
+        assert_eq!(lru.bytes(), size * 2);
+        assert!(lru.get((1, 0), (1, 2), 2).is_none());
+        assert!(lru.get((0, 0), (0, 2), 2).is_some());
+        assert!(lru.get((2, 0), (2, 2), 2).is_some());
+
+        // reversed lookup
+        let reversed = lru.get((2, 2), (2, 0), 5).unwrap();
+        assert_eq!(reversed, vec![(2, 2), (2, 1), (2, 0)]);
+        assert_eq!(reversed.cost(), 5);
+
+        lru.invalidate(|p| p.0 == 2);
+        assert!(lru.get((2, 0), (2, 2), 2).is_none());
+        assert_eq!(lru.bytes(), size);
+
+        // handles from before the invalidation can't insert anymore
+        handle.insert((3, 0), (3, 2), path(3));
+        assert!(lru.get((3, 0), (3, 2), 2).is_none());
+        lru.handle().insert((3, 0), (3, 2), path(3));
+        assert!(lru.get((3, 0), (3, 2), 2).is_some());
+
+        let clone = lru.clone();
+        assert_eq!(clone.bytes(), 0);
+    }
+}
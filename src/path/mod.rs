@@ -1,10 +1,14 @@
 mod abstract_path;
-pub use abstract_path::AbstractPath;
+pub use abstract_path::{AbstractPath, SegmentInfo};
+
+mod compact_path;
+pub use compact_path::CompactPath;
 
 mod generic_path;
 pub use generic_path::*;
 
+mod lru;
+pub(crate) use lru::{LruHandle, SharedPathLru};
+
 mod path_segment;
 pub use path_segment::PathSegment;
-
-pub type Cost = usize;
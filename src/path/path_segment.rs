@@ -1,22 +1,30 @@
-use super::{Cost, Path};
-use crate::Point;
+use super::{CompactPath, Path};
+use crate::{cost::Cost, PathCacheConfig, Point};
 
 #[derive(Clone, Debug)]
-pub enum PathSegment {
-    Known(Path<Point>),
+pub enum PathSegment<C> {
+    Known(Path<Point, C>),
+    /// A known Path that has to be decoded before it can be used
+    Compact(CompactPath<C>),
     Unknown {
         start: Point,
         end: Point,
-        cost: Cost,
+        cost: C,
         len: usize,
     },
 }
 
 use self::PathSegment::*;
 
-impl PathSegment {
-    pub fn new(path: Path<Point>, known: bool) -> PathSegment {
-        if known {
+impl<C: Cost> PathSegment<C> {
+    /// Creates a segment that stores as much of `path` as `config` allows
+    pub fn new(path: Path<Point, C>, config: &PathCacheConfig) -> PathSegment<C> {
+        if config.cache_paths {
+            if config.compact_paths {
+                if let Some(compact) = CompactPath::from_path(&path) {
+                    return Compact(compact);
+                }
+            }
             Known(path)
         } else {
             Unknown {
@@ -28,9 +36,10 @@ impl PathSegment {
         }
     }
 
-    pub fn cost(&self) -> Cost {
+    pub fn cost(&self) -> C {
         match *self {
             Known(ref path) => path.cost(),
+            Compact(ref path) => path.cost(),
             Unknown { cost, .. } => cost,
         }
     }
@@ -38,13 +47,24 @@ impl PathSegment {
     pub fn len(&self) -> usize {
         match *self {
             Known(ref path) => path.len(),
+            Compact(ref path) => path.len(),
             Unknown { len, .. } => len,
         }
     }
 
+    /// The number of bytes allocated for the Path of this segment
+    pub fn heap_size(&self) -> usize {
+        match *self {
+            Known(ref path) => path.heap_size(),
+            Compact(ref path) => path.heap_size(),
+            Unknown { .. } => 0,
+        }
+    }
+
     pub fn start(&self) -> Point {
         match *self {
             Known(ref path) => path[0],
+            Compact(ref path) => path.start(),
             Unknown { start, .. } => start,
         }
     }
@@ -52,13 +72,15 @@ impl PathSegment {
     pub fn end(&self) -> Point {
         match *self {
             Known(ref path) => path[path.len() - 1],
+            Compact(ref path) => path.end(),
             Unknown { end, .. } => end,
         }
     }
 
-    pub fn reversed(&self, start_cost: Cost, end_cost: Cost) -> PathSegment {
+    pub fn reversed(&self, start_cost: C, end_cost: C) -> PathSegment<C> {
         match *self {
             Known(ref path) => Known(path.reversed(start_cost, end_cost)),
+            Compact(ref path) => Compact(path.reversed(start_cost, end_cost)),
             Unknown {
                 start,
                 end,
@@ -67,7 +89,7 @@ impl PathSegment {
             } => Unknown {
                 start: end,
                 end: start,
-                cost: cost + end_cost - start_cost,
+                cost: (cost - start_cost).saturating_add(end_cost),
                 len,
             },
         }
@@ -1,7 +1,10 @@
 use crate::{
-    graph::{self, Node, NodeList},
+    cost::Cost,
+    graph::{self, ContractionHierarchy, DistanceTable, Landmarks, Node, NodeList},
+    grid::Heuristic,
     neighbors::Neighborhood,
-    path::{AbstractPath, Cost, Path, PathSegment},
+    path::{AbstractPath, Path, PathSegment, SharedPathLru},
+    search_context::{SearchBuffers, SearchContext},
     *,
 };
 
@@ -24,44 +27,145 @@ macro_rules! re_trace {
 }
 
 mod cache_config;
-pub use cache_config::PathCacheConfig;
+pub use cache_config::{GoalMode, PathCacheConfig};
 
 mod chunk;
 use chunk::Chunk;
 
-enum CostFnWrapper<F1, F2>
-where
-    F1: Sync + Fn(Point) -> isize,
-    F2: FnMut(Point) -> isize,
-{
+mod memory_report;
+pub use memory_report::MemoryReport;
+
+mod tag_index;
+use tag_index::TagIndex;
+
+mod path_query;
+pub use path_query::{PathQuery, QueryStatus};
+
+mod region;
+pub use region::Region;
+
+mod lazy_index;
+use lazy_index::LazyIndex;
+
+enum CostFnWrapper<F1, F2> {
     Sequential(F2, PhantomData<F1>), // F1 has to appear in the enum even if `parallel` is disabled
     #[cfg(feature = "parallel")]
     Parallel(F1),
 }
 
+impl<F1, F2> CostFnWrapper<F1, F2> {
+    /// The wrapped function, for parts that are never run in parallel
+    fn as_fn_mut<C>(&mut self) -> &mut dyn FnMut(Point) -> Option<C>
+    where
+        F1: Fn(Point) -> Option<C>,
+        F2: FnMut(Point) -> Option<C>,
+    {
+        match self {
+            CostFnWrapper::Sequential(get_cost, _) => get_cost,
+            #[cfg(feature = "parallel")]
+            CostFnWrapper::Parallel(get_cost) => get_cost,
+        }
+    }
+}
+
+/// The parts of a Path between two Points, see [`PathCache::resolve_path`]
+struct PathParts<C> {
+    start_path: Option<Path<Point, C>>,
+    nodes: Vec<NodeID>,
+    goal_path: Option<Path<Point, C>>,
+}
+
+impl<C> PathParts<C> {
+    fn known(path: Path<Point, C>) -> PathParts<C> {
+        PathParts {
+            start_path: Some(path),
+            nodes: vec![],
+            goal_path: None,
+        }
+    }
+}
+
+/// A goal, the Node it is attached to and the Path from that Node to the goal
+type GoalNode<C> = (Point, NodeID, Option<Path<Point, C>>);
+
+/// How much of a side of a Chunk has to be renewed in [`PathCache::tiles_changed`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Renew {
+    No,
+    Inner,
+    Corner(Point),
+    All,
+}
+
+/// The position and size of the Chunk in column `x` and row `y`, where the last Chunks of every
+/// row and column are only `last_size` large
+fn chunk_bounds(
+    (x, y): Point,
+    (num_chunks_w, num_chunks_h): (usize, usize),
+    (last_width, last_height): (usize, usize),
+    chunk_size: usize,
+) -> (Point, (usize, usize)) {
+    let w = if x == num_chunks_w - 1 {
+        last_width
+    } else {
+        chunk_size
+    };
+    let h = if y == num_chunks_h - 1 {
+        last_height
+    } else {
+        chunk_size
+    };
+    ((x * chunk_size, y * chunk_size), (w, h))
+}
+
+/// The number of Chunks along a side of length `len`, and the length of the last one
+fn chunk_count(len: usize, chunk_size: usize) -> (usize, usize) {
+    let count = len / chunk_size;
+    let remain = len - count * chunk_size;
+    if remain > 0 {
+        (count + 1, remain)
+    } else {
+        (count, chunk_size)
+    }
+}
+
 /// A struct to store the Hierarchical Pathfinding information.
 #[derive(Clone, Debug)]
-pub struct PathCache<N: Neighborhood> {
+pub struct PathCache<N: Neighborhood, C: Cost = usize> {
     width: usize,
     height: usize,
     chunks: Vec<Chunk>,
     num_chunks: (usize, usize),
-    nodes: NodeList,
+    nodes: NodeList<C>,
     neighborhood: N,
     config: PathCacheConfig,
+    /// the Paths that were resolved, if [`PathCacheConfig::path_lru_budget`] is used
+    path_lru: Option<SharedPathLru<C>>,
+    /// the tags set with [`tag_tiles`](PathCache::tag_tiles)
+    tags: TagIndex,
+    /// see [`build_distance_index`](PathCache::build_distance_index)
+    distance_table: LazyIndex<DistanceTable<C>>,
+    /// see [`build_contraction_hierarchy`](PathCache::build_contraction_hierarchy)
+    contraction: LazyIndex<ContractionHierarchy<C>>,
+    /// see [`PathCacheConfig::landmarks`]
+    landmarks: LazyIndex<Landmarks<C>>,
+    /// a lower bound for the cost of every walkable Tile, or `None` if there are none.
+    ///
+    /// The heuristic of A* is scaled by this, so that it doesn't overestimate costs below 1.
+    min_cost: Option<C>,
 }
 
-impl<N: Neighborhood + Sync> PathCache<N> {
+impl<N: Neighborhood + Sync, C: Cost> PathCache<N, C> {
     /// Creates a new `PathCache`
     ///
     /// ## Arguments
     /// - `(width, height)` - the size of the Grid
-    /// - `get_cost` - get the cost for walking over a Tile. (Cost < 0 means solid Tile)
+    /// - `get_cost` - get the cost for walking over a Tile. (`None` means solid Tile)
     /// - `neighborhood` - the Neighborhood to use. (See [`Neighborhood`])
     /// - `config` - optional config for creating the cache. (See [`PathCacheConfig`])
     ///
     /// `get_cost((x, y))` should return the cost for walking over the Tile at (x, y).
-    /// Costs below 0 are solid Tiles.
+    /// `None` marks a solid Tile.
     ///
     /// ## Examples
     /// Basic usage:
@@ -80,9 +184,9 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// let (width, height) = (grid[0].len(), grid.len());
     /// type Grid = [[usize; 5]; 5];
     ///
-    /// const COST_MAP: [isize; 3] = [1, 10, -1];
+    /// const COST_MAP: [Option<usize>; 3] = [Some(1), Some(10), None];
     ///
-    /// fn cost_fn(grid: &Grid) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
+    /// fn cost_fn(grid: &Grid) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
     ///     move |(x, y)| COST_MAP[grid[y][x]]
     /// }
     ///
@@ -93,15 +197,15 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     ///     PathCacheConfig::with_chunk_size(3), // config
     /// );
     /// ```
-    pub fn new<F: Sync + Fn(Point) -> isize>(
+    pub fn new<F: Sync + Fn(Point) -> Option<C>>(
         (width, height): (usize, usize),
         get_cost: F,
         neighborhood: N,
         config: PathCacheConfig,
-    ) -> PathCache<N> {
+    ) -> PathCache<N, C> {
         #[cfg(feature = "parallel")]
         {
-            PathCache::new_internal::<F, fn(Point) -> isize>(
+            PathCache::new_internal::<F, fn(Point) -> Option<C>>(
                 (width, height),
                 CostFnWrapper::Parallel(get_cost),
                 neighborhood,
@@ -110,7 +214,7 @@ impl<N: Neighborhood + Sync> PathCache<N> {
         }
         #[cfg(not(feature = "parallel"))]
         {
-            PathCache::new_internal::<fn(Point) -> isize, F>(
+            PathCache::new_internal::<fn(Point) -> Option<C>, F>(
                 (width, height),
                 CostFnWrapper::Sequential(get_cost, PhantomData),
                 neighborhood,
@@ -124,15 +228,15 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// Equivalent to `new` if `parallel` feature is disabled.
     ///
     /// Note that this is _**way**_ slower than `new` with `parallel`.
-    pub fn new_with_fn_mut<F: FnMut(Point) -> isize>(
+    pub fn new_with_fn_mut<F: FnMut(Point) -> Option<C>>(
         (width, height): (usize, usize),
         get_cost: F,
         neighborhood: N,
         config: PathCacheConfig,
-    ) -> PathCache<N> {
-        PathCache::new_internal::<fn(Point) -> isize, F>(
+    ) -> PathCache<N, C> {
+        PathCache::new_internal::<fn(Point) -> Option<C>, F>(
             (width, height),
-            CostFnWrapper::Sequential(get_cost, PhantomData::default()),
+            CostFnWrapper::Sequential(get_cost, PhantomData),
             neighborhood,
             config,
         )
@@ -143,13 +247,13 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// Note that `get_cost` has to be `Fn` instead of `FnMut`.
     #[cfg(feature = "parallel")]
     #[deprecated(since = "0.5.0", note = "`new` is automatically parallel")]
-    pub fn new_parallel<F: Sync + Fn(Point) -> isize>(
+    pub fn new_parallel<F: Sync + Fn(Point) -> Option<C>>(
         (width, height): (usize, usize),
         get_cost: F,
         neighborhood: N,
         config: PathCacheConfig,
-    ) -> PathCache<N> {
-        PathCache::new_internal::<F, fn(Point) -> isize>(
+    ) -> PathCache<N, C> {
+        PathCache::new_internal::<F, fn(Point) -> Option<C>>(
             (width, height),
             CostFnWrapper::Parallel(get_cost),
             neighborhood,
@@ -159,36 +263,27 @@ impl<N: Neighborhood + Sync> PathCache<N> {
 
     fn new_internal<F1, F2>(
         (width, height): (usize, usize),
-        get_cost: CostFnWrapper<F1, F2>,
+        mut get_cost: CostFnWrapper<F1, F2>,
         neighborhood: N,
         config: PathCacheConfig,
-    ) -> PathCache<N>
+    ) -> PathCache<N, C>
     where
-        F1: Sync + Fn(Point) -> isize,
-        F2: FnMut(Point) -> isize,
+        F1: Sync + Fn(Point) -> Option<C>,
+        F2: FnMut(Point) -> Option<C>,
     {
         #[cfg(feature = "log")]
         let (outer_timer, timer) = (std::time::Instant::now(), std::time::Instant::now());
 
         // calculate chunk size
-        let (num_chunks_w, last_width) = {
-            let w = width / config.chunk_size;
-            let remain = width - w * config.chunk_size;
-            if remain > 0 {
-                (w + 1, remain)
-            } else {
-                (w, config.chunk_size)
-            }
-        };
-        let (num_chunks_h, last_height) = {
-            let h = height / config.chunk_size;
-            let remain = height - h * config.chunk_size;
-            if remain > 0 {
-                (h + 1, remain)
-            } else {
-                (h, config.chunk_size)
-            }
-        };
+        let (num_chunks_w, last_width) = chunk_count(width, config.chunk_size);
+        let (num_chunks_h, last_height) = chunk_count(height, config.chunk_size);
+        let num_chunks = (num_chunks_w, num_chunks_h);
+        let last_size = (last_width, last_height);
+
+        let min_cost = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter_map(get_cost.as_fn_mut())
+            .min();
 
         let mut nodes = NodeList::new();
 
@@ -197,22 +292,13 @@ impl<N: Neighborhood + Sync> PathCache<N> {
             CostFnWrapper::Sequential(mut get_cost, _) => {
                 let mut chunks: Vec<Chunk> = Vec::with_capacity(num_chunks_w * num_chunks_h);
                 for y in 0..num_chunks_h {
-                    let h = if y == num_chunks_h - 1 {
-                        last_height
-                    } else {
-                        config.chunk_size
-                    };
-
                     for x in 0..num_chunks_w {
-                        let w = if x == num_chunks_w - 1 {
-                            last_width
-                        } else {
-                            config.chunk_size
-                        };
+                        let (pos, size) =
+                            chunk_bounds((x, y), num_chunks, last_size, config.chunk_size);
 
                         chunks.push(Chunk::new(
-                            (x * config.chunk_size, y * config.chunk_size),
-                            (w, h),
+                            pos,
+                            size,
                             (width, height),
                             &mut get_cost,
                             &neighborhood,
@@ -233,26 +319,15 @@ impl<N: Neighborhood + Sync> PathCache<N> {
                 let (mut chunks, node_lists): (Vec<_>, Vec<_>) = (0..num_chunks_h * num_chunks_w)
                     .into_par_iter()
                     .map(|index| {
-                        let x = index % num_chunks_w;
-                        let y = index / num_chunks_w;
-
-                        let w = if x == num_chunks_w - 1 {
-                            last_width
-                        } else {
-                            config.chunk_size
-                        };
-
-                        let h = if y == num_chunks_h - 1 {
-                            last_height
-                        } else {
-                            config.chunk_size
-                        };
+                        let (x, y) = (index % num_chunks_w, index / num_chunks_w);
+                        let (pos, size) =
+                            chunk_bounds((x, y), num_chunks, last_size, config.chunk_size);
 
                         let mut node_list = NodeList::new();
 
                         let chunk = Chunk::new(
-                            (x * config.chunk_size, y * config.chunk_size),
-                            (w, h),
+                            pos,
+                            size,
                             (width, height),
                             &get_cost,
                             &neighborhood,
@@ -285,10 +360,17 @@ impl<N: Neighborhood + Sync> PathCache<N> {
             width,
             height,
             chunks,
-            num_chunks: (num_chunks_w, num_chunks_h),
+            num_chunks,
             nodes,
             neighborhood,
             config,
+            path_lru: (!config.cache_paths && config.path_lru_budget > 0)
+                .then(|| SharedPathLru::new(config.path_lru_budget)),
+            tags: TagIndex::default(),
+            distance_table: LazyIndex::new(false),
+            contraction: LazyIndex::new(false),
+            landmarks: LazyIndex::new(config.landmarks > 0),
+            min_cost,
         };
 
         // connect neighboring Nodes across Chunk borders
@@ -302,10 +384,16 @@ impl<N: Neighborhood + Sync> PathCache<N> {
 
     /// Calculates the Path from `start` to `goal` on the Grid.
     ///
-    /// If no Path could be found, `None` is returned.
+    /// If no Path could be found, `None` is returned. Paths whose total cost would overflow `C`
+    /// count as not found.
+    ///
+    /// The Path ends on `goal` itself, or next to it or within a range of it, depending on the
+    /// [`goal_mode`](PathCacheConfig::goal_mode). Use
+    /// [`find_path_with_goal_mode`](PathCache::find_path_with_goal_mode) to choose a different
+    /// [`GoalMode`] for a single search.
     ///
     /// `get_cost((x, y))` should return the cost for walking over the Tile at (x, y).
-    /// Costs below 0 are solid Tiles.
+    /// `None` marks a solid Tile.
     ///
     /// ## Examples
     /// Basic usage:
@@ -319,8 +407,8 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// #     [0, 0, 0, 2, 0],
     /// # ];
     /// # let (width, height) = (grid[0].len(), grid.len());
-    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-    /// #     move |(x, y)| [1, 10, -1][grid[y][x]]
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
     /// # }
     /// let pathfinding: PathCache<_> = // ...
     /// # PathCache::new(
@@ -365,8 +453,8 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// #     [0, 0, 0, 2, 0],
     /// # ];
     /// # let (width, height) = (grid[0].len(), grid.len());
-    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-    /// #     move |(x, y)| [1, 10, -1][grid[y][x]]
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
     /// # }
     /// # let pathfinding = PathCache::new(
     /// #     (width, height),
@@ -416,8 +504,8 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// #     [0, 0, 0, 2, 0],
     /// # ];
     /// # let (width, height) = (grid[0].len(), grid.len());
-    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-    /// #     move |(x, y)| [1, 10, -1][grid[y][x]]
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
     /// # }
     /// # let pathfinding = PathCache::new(
     /// #     (width, height),
@@ -446,102 +534,16 @@ impl<N: Neighborhood + Sync> PathCache<N> {
         &self,
         start: Point,
         goal: Point,
-        mut get_cost: impl FnMut(Point) -> isize,
-    ) -> Option<AbstractPath<N>> {
-        #[cfg(feature = "log")]
-        let (outer_timer, timer) = (std::time::Instant::now(), std::time::Instant::now());
-
-        if !self.in_bounds(start) {
-            panic!(
-                "start {:?} is out of bounds of a grid of size {}x{}",
-                start, self.width, self.height
-            );
-        }
-        if !self.in_bounds(goal) {
-            return None;
-        }
-
-        if get_cost(start) < 0 {
-            // cannot start on a wall
-            return None;
-        }
-
-        let neighborhood = self.neighborhood.clone();
-
-        if start == goal {
-            return Some(AbstractPath::from_known_path(
-                neighborhood,
-                Path::from_slice(&[start, start], 0),
-            ));
-        }
-
-        let (start_id, start_path) =
-            if let Some(s) = self.find_nearest_node(start, &mut get_cost, false) {
-                s
-            } else {
-                // no path from start to any Node => start is in cave within chunk
-                // => hope that goal is in the same cave
-                return self
-                    .get_chunk(start)
-                    .find_path(start, goal, get_cost, &neighborhood)
-                    .map(|path| AbstractPath::from_known_path(neighborhood, path));
-            };
-
-        // try-operator: see above, but we know that start is not in a cave
-        let (goal_id, goal_path) = self.find_nearest_node(goal, &mut get_cost, true)?;
-
-        re_trace!("find nodes", timer);
-
-        // size hint for number of visited nodes in graph::a_star_search:
-        //     percentage of total area visited (heuristic / max_heuristic)
-        //     as the percentage of nodes visited ( * self.nodes.len())
-        let heuristic = neighborhood.heuristic(start, goal);
-        let max_heuristic = neighborhood.heuristic((0, 0), (self.width - 1, self.height - 1));
-        let max_size = self.nodes.len();
-        let size_hint = heuristic as f32 / max_heuristic as f32 * max_size as f32;
-
-        let path = graph::a_star_search(
-            &self.nodes,
-            start_id,
-            goal_id,
-            &neighborhood,
-            size_hint as usize,
-        )?;
-
-        re_trace!("graph::a_star_search", timer);
-
-        let mut paths = NodeIDMap::default();
-        paths.insert(goal_id, path);
-
-        let mut ret_map = PointMap::default();
-        self.resolve_paths(
-            start,
-            start_path,
-            &mut [(goal, goal_id, goal_path)],
-            &paths,
-            get_cost,
-            &mut ret_map,
-        );
-
-        re_trace!("resolve_paths", timer);
-        re_trace!("total time", outer_timer);
-
-        ret_map.remove(&goal)
+        get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> Option<AbstractPath<N, C>> {
+        self.find_path_with_context(start, goal, get_cost, &mut SearchContext::new())
     }
 
-    /// Calculates the Paths from one `start` to several `goals` on the Grid.
-    ///
-    /// This is equivalent to [`find_path`](PathCache::find_path), except that it is optimized to handle multiple Goals
-    /// at once. However, it is slower for very few goals, since it does not use a heuristic like
-    /// [`find_path`](PathCache::find_path) does.
-    ///
-    /// Instead of returning a single Option, it returns a Hashmap, where the position of the Goal
-    /// is the key, and the Value is a Tuple of the Path and the Cost of that Path.
-    ///
-    /// `get_cost((x, y))` should return the cost for walking over the Tile at (x, y).
-    /// Costs below 0 are solid Tiles.
+    /// Same as [`find_path`](PathCache::find_path), but reuses the buffers in `context` instead
+    /// of allocating new ones.
     ///
-    /// See [`find_path`](PathCache::find_path) for more details on how to use the returned Paths.
+    /// Keeping a [`SearchContext`] around and passing it to every query avoids most of the
+    /// allocations of a search, which can make a noticeable difference for short Paths.
     ///
     /// ## Examples
     /// Basic usage:
@@ -555,8 +557,8 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// #     [0, 0, 0, 2, 0],
     /// # ];
     /// # let (width, height) = (grid[0].len(), grid.len());
-    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-    /// #     move |(x, y)| [1, 10, -1][grid[y][x]]
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
     /// # }
     /// let pathfinding: PathCache<_> = // ...
     /// # PathCache::new(
@@ -566,24 +568,37 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// #     PathCacheConfig::with_chunk_size(3),
     /// # );
     ///
-    /// let start = (0, 0);
-    /// let goals = [(4, 4), (2, 0)];
+    /// // keep this around, e.g. as part of the game state
+    /// let mut context = SearchContext::new();
     ///
-    /// // find_paths returns a HashMap<goal, Path> for all successes
-    /// let paths = pathfinding.find_paths(
-    ///     start,
-    ///     &goals,
+    /// let path = pathfinding.find_path_with_context(
+    ///     (0, 0),
+    ///     (4, 4),
     ///     cost_fn(&grid),
+    ///     &mut context,
     /// );
     ///
-    /// // (4, 4) is reachable
-    /// assert!(paths.contains_key(&goals[0]));
-    ///
-    /// // (2, 0) is not reachable
-    /// assert!(!paths.contains_key(&goals[1]));
+    /// assert_eq!(path.unwrap().cost(), 12);
     /// ```
+    pub fn find_path_with_context(
+        &self,
+        start: Point,
+        goal: Point,
+        get_cost: impl FnMut(Point) -> Option<C>,
+        context: &mut SearchContext<C>,
+    ) -> Option<AbstractPath<N, C>> {
+        self.find_path_to_goal(start, goal, self.config.goal_mode, get_cost, context)
+            .map(|(_, path)| path)
+    }
+
+    /// Same as [`find_path`](PathCache::find_path), but with the given [`GoalMode`] instead of
+    /// the [`goal_mode`](PathCacheConfig::goal_mode) of the config.
     ///
-    /// The returned Path is always equivalent to the one returned by [`find_path`](PathCache::find_path):
+    /// This allows searching for different kinds of goals with the same `PathCache`, e.g. for
+    /// units that attack from next to a target and units that attack from a distance.
+    ///
+    /// ## Examples
+    /// Basic usage:
     /// ```
     /// # use hierarchical_pathfinding::prelude::*;
     /// # let mut grid = [
@@ -594,49 +609,186 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// #     [0, 0, 0, 2, 0],
     /// # ];
     /// # let (width, height) = (grid[0].len(), grid.len());
-    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-    /// #     move |(x, y)| [1, 10, -1][grid[y][x]]
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
     /// # }
-    /// # let pathfinding = PathCache::new(
+    /// let pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
     /// #     (width, height),
     /// #     cost_fn(&grid),
     /// #     ManhattanNeighborhood::new(width, height),
     /// #     PathCacheConfig::with_chunk_size(3),
     /// # );
-    /// let start = (0, 0);
-    /// let goal = (4, 4);
-    ///
-    /// let paths = pathfinding.find_paths(
-    ///     start,
-    ///     &[goal],
-    ///     cost_fn(&grid),
-    /// );
-    /// let dijkstra_path: Vec<_> = paths[&goal].clone().collect();
     ///
-    /// let a_star_path: Vec<_> = pathfinding.find_path(
-    ///     start,
-    ///     goal,
-    ///     cost_fn(&grid),
-    /// ).unwrap().collect();
+    /// // walk up to the wall at (3, 3) to e.g. mine it
+    /// let path = pathfinding
+    ///     .find_path_with_goal_mode((0, 4), (3, 3), GoalMode::AdjacentToGoal, cost_fn(&grid))
+    ///     .unwrap();
+    /// assert_eq!(path.cost(), 3);
     ///
-    /// assert_eq!(dijkstra_path, a_star_path);
+    /// // stop anywhere within 2 Tiles of (4, 4)
+    /// let path = pathfinding
+    ///     .find_path_with_goal_mode((0, 0), (4, 4), GoalMode::WithinRange(2), cost_fn(&grid))
+    ///     .unwrap();
+    /// assert_eq!(path.cost(), 6);
     /// ```
-    pub fn find_paths(
+    pub fn find_path_with_goal_mode(
         &self,
         start: Point,
-        goals: &[Point],
-        get_cost: impl FnMut(Point) -> isize,
-    ) -> PointMap<AbstractPath<N>> {
-        self.find_paths_internal(start, goals, get_cost, false)
+        goal: Point,
+        goal_mode: GoalMode,
+        get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> Option<AbstractPath<N, C>> {
+        self.find_path_to_goal(start, goal, goal_mode, get_cost, &mut SearchContext::new())
+            .map(|(_, path)| path)
     }
 
-    /// Finds the closest from a list of goals.
+    /// Finds the Path to `goal` according to `mode` and the Tile that it ends on
+    fn find_path_to_goal(
+        &self,
+        start: Point,
+        goal: Point,
+        mode: GoalMode,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+        context: &mut SearchContext<C>,
+    ) -> Option<(Point, AbstractPath<N, C>)> {
+        if mode == GoalMode::Exact {
+            return self
+                .find_path_exact(start, goal, get_cost, context)
+                .map(|path| (goal, path));
+        }
+        let tiles = self.goal_tiles(goal, mode, &mut get_cost);
+        if tiles.contains(&start) {
+            // find_paths_exact would keep searching for the other Tiles
+            let path = Path::from_slice(&[start, start], C::ZERO);
+            return Some((start, self.known_path(path)));
+        }
+        // only_closest_goal stops at the closest Node, which might not lead to the cheapest Tile
+        self.find_paths_exact::<fn(Point) -> Option<C>, _>(
+            start,
+            &tiles,
+            CostFnWrapper::Sequential(get_cost, PhantomData),
+            false,
+            context,
+        )
+        .into_iter()
+        .min_by_key(|(_, path)| path.cost())
+    }
+
+    /// Finds the Tiles that count as reaching `goal` with the given [`GoalMode`]
+    fn goal_tiles(
+        &self,
+        goal: Point,
+        mode: GoalMode,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> Vec<Point> {
+        if !self.in_bounds(goal) {
+            return vec![];
+        }
+        let mut tiles = vec![];
+        match mode {
+            GoalMode::Exact => tiles.push(goal),
+            GoalMode::AdjacentToGoal => {
+                self.neighborhood.get_all_neighbors(goal, &mut tiles);
+                tiles.retain(|&pos| self.in_bounds(pos) && get_cost(pos).is_some());
+            }
+            GoalMode::WithinRange(range) => {
+                let max_x = goal.0.saturating_add(range).min(self.width - 1);
+                let max_y = goal.1.saturating_add(range).min(self.height - 1);
+                for y in goal.1.saturating_sub(range)..=max_y {
+                    for x in goal.0.saturating_sub(range)..=max_x {
+                        if self.neighborhood.heuristic((x, y), goal) <= range
+                            && get_cost((x, y)).is_some()
+                        {
+                            tiles.push((x, y));
+                        }
+                    }
+                }
+            }
+        }
+        tiles
+    }
+
+    /// Finds the Path to exactly `goal`, see [`find_path`](PathCache::find_path)
+    fn find_path_exact(
+        &self,
+        start: Point,
+        goal: Point,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+        context: &mut SearchContext<C>,
+    ) -> Option<AbstractPath<N, C>> {
+        #[cfg(feature = "log")]
+        let (outer_timer, timer) = (std::time::Instant::now(), std::time::Instant::now());
+
+        self.assert_in_bounds("start", start);
+        if !self.in_bounds(goal) {
+            return None;
+        }
+
+        // cannot start on a wall
+        get_cost(start)?;
+
+        if start == goal {
+            return Some(self.known_path(Path::from_slice(&[start, start], C::ZERO)));
+        }
+
+        let Some((start_id, start_path)) =
+            self.find_nearest_node(start, &mut get_cost, false, context)
+        else {
+            // no path from start to any Node => start is in cave within chunk
+            // => hope that goal is in the same cave
+            return self
+                .get_chunk(start)
+                .find_path(
+                    &mut context.grid,
+                    start,
+                    goal,
+                    get_cost,
+                    &self.neighborhood,
+                    self.min_cost(),
+                )
+                .map(|path| self.known_path(path));
+        };
+
+        // try-operator: see above, but we know that start is not in a cave
+        let (goal_id, goal_path) = self.find_nearest_node(goal, &mut get_cost, true, context)?;
+
+        re_trace!("find nodes", timer);
+
+        let path = self.graph_path(start_id, goal_id, context)?;
+
+        re_trace!("graph::a_star_search", timer);
+
+        let parts = self.resolve_path(
+            start,
+            start_path.as_ref(),
+            goal,
+            &path,
+            goal_path,
+            get_cost,
+            context,
+            &mut PointMap::default(),
+        );
+        let path = self.build_path(start, parts);
+
+        re_trace!("resolve_path", timer);
+        re_trace!("total time", outer_timer);
+
+        path
+    }
+
+    /// Calculates the Path from `start` to `goal`, or to the Tile closest to `goal` if `goal`
+    /// cannot be reached.
     ///
-    /// Returns a tuple of the goal and the Path to that goal, or `None` if none of the goals are
-    /// reachable.
+    /// Returns the Tile that the Path leads to and the Path itself, or `None` if `start` is solid.
+    /// If `goal` can be reached, this is the same as [`find_path`](PathCache::find_path).
+    /// Otherwise, "closest" is measured with the [`heuristic`](Neighborhood::heuristic) of the
+    /// Neighborhood. The abstract Graph is searched for all Chunks that can be reached from
+    /// `start`, which are then searched for the closest Tile in the order of their distance to
+    /// `goal`, until no Chunk can contain a closer Tile.
     ///
-    /// Similar to [`find_paths`](PathCache::find_paths) in performance and search strategy, but
-    /// stops after the first goal is found.
+    /// `get_cost((x, y))` should return the cost for walking over the Tile at (x, y).
+    /// `None` marks a solid Tile.
     ///
     /// ## Examples
     /// Basic usage:
@@ -650,8 +802,8 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// #     [0, 0, 0, 2, 0],
     /// # ];
     /// # let (width, height) = (grid[0].len(), grid.len());
-    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-    /// #     move |(x, y)| [1, 10, -1][grid[y][x]]
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
     /// # }
     /// let pathfinding: PathCache<_> = // ...
     /// # PathCache::new(
@@ -661,189 +813,2009 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// #     PathCacheConfig::with_chunk_size(3),
     /// # );
     ///
-    /// let start = (0, 0);
-    /// let goals = [(4, 4), (2, 0), (2, 2)];
-    ///
-    /// // find_closest_goal returns Some((goal, Path)) on success
-    /// let (goal, path) = pathfinding.find_closest_goal(
-    ///     start,
-    ///     &goals,
-    ///     cost_fn(&grid),
-    /// ).unwrap();
-    ///
-    /// assert_eq!(goal, goals[2]);
+    /// // (4, 0) is walled off
+    /// assert!(pathfinding.find_path((0, 0), (4, 0), cost_fn(&grid)).is_none());
     ///
-    /// let naive_closest = pathfinding
-    ///     .find_paths(start, &goals, cost_fn(&grid))
-    ///     .into_iter()
-    ///     .min_by_key(|(_, path)| path.cost())
+    /// let (tile, path) = pathfinding
+    ///     .find_path_or_closest((0, 0), (4, 0), cost_fn(&grid))
     ///     .unwrap();
+    /// assert_eq!(tile, (4, 2));
+    /// assert_eq!(path.cost(), 10);
     ///
-    /// assert_eq!(goal, naive_closest.0);
-    ///
-    /// let path: Vec<_> = path.collect();
-    /// let naive_path: Vec<_> = naive_closest.1.collect();
-    /// assert_eq!(path, naive_path);
-    /// ```
-    /// Comparison with [`find_paths`](PathCache::find_paths):
+    /// // reachable goals work just like find_path
+    /// let (tile, path) = pathfinding
+    ///     .find_path_or_closest((0, 0), (4, 4), cost_fn(&grid))
+    ///     .unwrap();
+    /// assert_eq!(tile, (4, 4));
+    /// assert_eq!(path.cost(), 12);
     /// ```
-    /// # use hierarchical_pathfinding::prelude::*;
-    /// # let mut grid = [
-    /// #     [0, 2, 0, 0, 0],
-    /// #     [0, 2, 2, 2, 2],
-    /// #     [0, 1, 0, 0, 0],
-    /// #     [0, 1, 0, 2, 0],
-    /// #     [0, 0, 0, 2, 0],
-    /// # ];
-    /// # let (width, height) = (grid[0].len(), grid.len());
-    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-    /// #     move |(x, y)| [1, 10, -1][grid[y][x]]
-    /// # }
-    /// # let pathfinding = PathCache::new(
-    /// #     (width, height),
-    /// #     cost_fn(&grid),
-    /// #     ManhattanNeighborhood::new(width, height),
-    /// #     PathCacheConfig::with_chunk_size(3),
-    /// # );
-    /// # let start = (0, 0);
-    /// # let goals = [(4, 4), (2, 0), (2, 2)];
-    /// let (goal, path) = pathfinding.find_closest_goal(
-    ///     start,
-    ///     &goals,
-    ///     cost_fn(&grid),
-    /// ).unwrap();
     ///
-    /// let naive_closest = pathfinding
-    ///     .find_paths(start, &goals, cost_fn(&grid))
-    ///     .into_iter()
-    ///     .min_by_key(|(_, path)| path.cost())
-    ///     .unwrap();
+    /// ## Panics
+    /// Panics if `start` is out of bounds.
+    pub fn find_path_or_closest(
+        &self,
+        start: Point,
+        goal: Point,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> Option<(Point, AbstractPath<N, C>)> {
+        let mut context = SearchContext::new();
+        let mode = self.config.goal_mode;
+        if let Some(found) = self.find_path_to_goal(start, goal, mode, &mut get_cost, &mut context)
+        {
+            return Some(found);
+        }
+        // cannot start on a wall
+        get_cost(start)?;
+
+        let distance = |pos: Point| self.neighborhood.heuristic(pos, goal);
+
+        // the Tiles of the Chunk of start can be reached directly
+        let (tile, path) = self.closest_in_chunk(start, goal, &mut get_cost, &mut context.grid)?;
+        let mut best = (tile, self.known_path(path));
+        let mut covered = PointSet::default();
+
+        let Some((start_id, _)) = self.find_nearest_node(start, &mut get_cost, false, &mut context)
+        else {
+            // start is in a cave within its Chunk
+            return Some(best);
+        };
+
+        let reached = graph::dijkstra_within(
+            &mut context.graph,
+            &self.nodes,
+            &[(start_id, C::ZERO)],
+            None,
+        );
+
+        // the Nodes of every reached Chunk, together with the distance of the Chunk to goal
+        let mut chunks = hashbrown::HashMap::new();
+        for (&id, &(cost, _)) in &reached {
+            let index = self.get_chunk_index(self.nodes[id].pos);
+            chunks
+                .entry(index)
+                .or_insert_with(Vec::new)
+                .push((cost, id));
+        }
+        let mut chunks = chunks
+            .into_iter()
+            .map(|(index, nodes)| {
+                let chunk = &self.chunks[index];
+                // the point of the Chunk that is closest to goal
+                let closest = (
+                    goal.0.clamp(chunk.left(), chunk.right() - 1),
+                    goal.1.clamp(chunk.top(), chunk.bottom() - 1),
+                );
+                (distance(closest), index, nodes)
+            })
+            .to_vec();
+        chunks.sort_by_key(|&(chunk_distance, index, _)| (chunk_distance, index));
+
+        for (chunk_distance, _, mut nodes) in chunks {
+            if chunk_distance >= distance(best.0) {
+                break;
+            }
+            nodes.sort_unstable();
+            for (_, id) in nodes {
+                let pos = self.nodes[id].pos;
+                if covered.contains(&pos) {
+                    // a Chunk can be split into several areas, which only need to be searched once
+                    continue;
+                }
+                let (tile, _) = self.closest_in_chunk_with(
+                    pos,
+                    goal,
+                    &mut get_cost,
+                    &mut context.grid,
+                    &mut covered,
+                )?;
+                if distance(tile) < distance(best.0) {
+                    // tile can be reached from the Node, which can be reached from start
+                    if let Some(path) =
+                        self.find_path_exact(start, tile, &mut get_cost, &mut context)
+                    {
+                        best = (tile, path);
+                    }
+                }
+            }
+        }
+
+        Some(best)
+    }
+
+    /// Finds the Tile in the Chunk of `start` that is closest to `goal` and can be reached from
+    /// `start` within that Chunk, see [`find_path_or_closest`](PathCache::find_path_or_closest)
+    fn closest_in_chunk(
+        &self,
+        start: Point,
+        goal: Point,
+        get_cost: impl FnMut(Point) -> Option<C>,
+        buffers: &mut SearchBuffers<Point, C>,
+    ) -> Option<(Point, Path<Point, C>)> {
+        self.closest_in_chunk_with(start, goal, get_cost, buffers, &mut PointSet::default())
+    }
+
+    /// Same as [`closest_in_chunk`](PathCache::closest_in_chunk), but adds all Tiles that can be
+    /// reached to `reached`
+    fn closest_in_chunk_with(
+        &self,
+        start: Point,
+        goal: Point,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+        buffers: &mut SearchBuffers<Point, C>,
+        reached: &mut PointSet,
+    ) -> Option<(Point, Path<Point, C>)> {
+        let chunk = self.get_chunk(start);
+        let mut tiles = vec![];
+        for y in chunk.top()..chunk.bottom() {
+            for x in chunk.left()..chunk.right() {
+                if get_cost((x, y)).is_some() {
+                    tiles.push((x, y));
+                }
+            }
+        }
+        let paths = chunk.find_paths(buffers, start, &tiles, get_cost, &self.neighborhood);
+        reached.extend(paths.keys().copied());
+        let (tile, path) = paths
+            .into_iter()
+            .min_by_key(|(tile, path)| (self.neighborhood.heuristic(*tile, goal), path.cost()))?;
+        if tile == start {
+            return Some((start, Path::from_slice(&[start, start], C::ZERO)));
+        }
+        Some((tile, path))
+    }
+
+    /// Starts a search for the Path from `start` to `goal` that can be performed in steps.
+    ///
+    /// The returned [`PathQuery`] does not search anything until [`step`](PathQuery::step) is
+    /// called, which limits how many Nodes or Tiles are expanded at once. This allows spreading
+    /// long searches over multiple frames. Once done, the query produces the same Path as
+    /// [`find_path`](PathCache::find_path).
+    ///
+    /// Only [`GoalMode::Exact`] can be split up this way, since the other modes search for all
+    /// goal Tiles at once. Keep the [`goal_mode`](PathCacheConfig::goal_mode) of the config at
+    /// `Exact` and use e.g. [`find_path_with_goal_mode`](PathCache::find_path_with_goal_mode)
+    /// for the other modes.
+    ///
+    /// `get_cost((x, y))` should return the cost for walking over the Tile at (x, y).
+    /// `None` marks a solid Tile. It is kept by the query until the search is done.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// use hierarchical_pathfinding::QueryStatus;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// let mut query = pathfinding.query_path((0, 0), (4, 4), cost_fn(&grid));
+    ///
+    /// let path = loop {
+    ///     // e.g. once per frame
+    ///     match query.step(2) {
+    ///         QueryStatus::InProgress => continue,
+    ///         QueryStatus::Found(path) => break Some(path),
+    ///         QueryStatus::NoPath => break None,
+    ///     }
+    /// };
+    ///
+    /// assert_eq!(path.unwrap().cost(), 12);
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if `start` is out of bounds, or if the [`goal_mode`](PathCacheConfig::goal_mode)
+    /// is not [`GoalMode::Exact`].
+    pub fn query_path<F: FnMut(Point) -> Option<C>>(
+        &self,
+        start: Point,
+        goal: Point,
+        get_cost: F,
+    ) -> PathQuery<'_, N, C, F> {
+        self.assert_in_bounds("start", start);
+        PathQuery::new(self, start, goal, get_cost)
+    }
+
+    /// Estimates the cost of the Path from `start` to `goal` without calculating the Path itself.
+    ///
+    /// Only the nearest Nodes of `start` and `goal` are looked up and the abstract Graph is
+    /// searched, which skips resolving the Paths inside of the Chunks and the A* search of
+    /// [`a_star_fallback`](PathCacheConfig::a_star_fallback). This makes it a lot cheaper than
+    /// [`find_path`](PathCache::find_path) when only the cost or the existence of a Path is
+    /// needed, e.g. as a Heuristic for other algorithms.
+    ///
+    /// Returns `None` if no Path exists. Otherwise, the estimate is the cost of an actual Path
+    /// from `start` to `goal`, so it is never less than the cost of the optimal Path, and never
+    /// less than the cost of the Path returned by `find_path` unless the `a_star_fallback`
+    /// replaced that Path with a shorter one.
+    ///
+    /// `get_cost((x, y))` should return the cost for walking over the Tile at (x, y).
+    /// `None` marks a solid Tile.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// let estimate = pathfinding.estimate_cost((0, 0), (4, 4), cost_fn(&grid));
+    /// let path = pathfinding.find_path((0, 0), (4, 4), cost_fn(&grid)).unwrap();
+    /// assert!(estimate.unwrap() >= path.cost());
+    ///
+    /// // (2, 0) is not reachable
+    /// assert_eq!(pathfinding.estimate_cost((0, 0), (2, 0), cost_fn(&grid)), None);
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if `start` is out of bounds.
+    pub fn estimate_cost(
+        &self,
+        start: Point,
+        goal: Point,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> Option<C> {
+        self.assert_in_bounds("start", start);
+        if !self.in_bounds(goal) {
+            return None;
+        }
+
+        // cannot start on a wall
+        get_cost(start)?;
+
+        if start == goal {
+            return Some(C::ZERO);
+        }
+
+        let context = &mut SearchContext::new();
+
+        let Some((start_id, start_path)) =
+            self.find_nearest_node(start, &mut get_cost, false, context)
+        else {
+            // start is in a cave within its chunk, see find_path
+            return self
+                .get_chunk(start)
+                .find_path(
+                    &mut context.grid,
+                    start,
+                    goal,
+                    get_cost,
+                    &self.neighborhood,
+                    self.min_cost(),
+                )
+                .map(|path| path.cost());
+        };
+        let (goal_id, goal_path) = self.find_nearest_node(goal, &mut get_cost, true, context)?;
+
+        let graph_cost = if let Some(table) = self.distance_table() {
+            // no need to reconstruct the Path
+            table.cost(start_id, goal_id)?
+        } else {
+            self.graph_path(start_id, goal_id, context)?.cost()
+        };
+
+        let start_cost = start_path.map_or(C::ZERO, |path| path.cost());
+        let goal_cost = goal_path.map_or(C::ZERO, |path| path.cost());
+        start_cost.checked_add(graph_cost)?.checked_add(goal_cost)
+    }
+
+    /// Calculates the Paths from one `start` to several `goals` on the Grid.
+    ///
+    /// This is equivalent to [`find_path`](PathCache::find_path), except that it is optimized to handle multiple Goals
+    /// at once. However, it is slower for very few goals, since it does not use a heuristic like
+    /// [`find_path`](PathCache::find_path) does.
+    ///
+    /// Instead of returning a single Option, it returns a Hashmap, where the position of the Goal
+    /// is the key, and the Value is a Tuple of the Path and the Cost of that Path. With a
+    /// [`goal_mode`](PathCacheConfig::goal_mode) other than [`GoalMode::Exact`], the Path of every
+    /// goal is the cheapest one to any of the Tiles that count as reaching it. See
+    /// [`find_paths_with_goal_mode`](PathCache::find_paths_with_goal_mode) to choose the
+    /// [`GoalMode`] for a single search.
+    ///
+    /// `get_cost((x, y))` should return the cost for walking over the Tile at (x, y).
+    /// `None` marks a solid Tile.
+    ///
+    /// See [`find_path`](PathCache::find_path) for more details on how to use the returned Paths.
+    ///
+    /// With the `parallel` feature, the parts of the Paths that are specific to each goal are
+    /// calculated on multiple threads, which is why `get_cost` has to be [`Sync`] and [`Fn`].
+    /// See [`find_paths_with_fn_mut`](PathCache::find_paths_with_fn_mut) for a sequential version.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// let start = (0, 0);
+    /// let goals = [(4, 4), (2, 0)];
+    ///
+    /// // find_paths returns a HashMap<goal, Path> for all successes
+    /// let paths = pathfinding.find_paths(
+    ///     start,
+    ///     &goals,
+    ///     cost_fn(&grid),
+    /// );
+    ///
+    /// // (4, 4) is reachable
+    /// assert!(paths.contains_key(&goals[0]));
+    ///
+    /// // (2, 0) is not reachable
+    /// assert!(!paths.contains_key(&goals[1]));
+    /// ```
+    ///
+    /// The returned Path is always equivalent to the one returned by [`find_path`](PathCache::find_path):
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// # let pathfinding = PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    /// let start = (0, 0);
+    /// let goal = (4, 4);
+    ///
+    /// let paths = pathfinding.find_paths(
+    ///     start,
+    ///     &[goal],
+    ///     cost_fn(&grid),
+    /// );
+    /// let dijkstra_path: Vec<_> = paths[&goal].clone().collect();
+    ///
+    /// let a_star_path: Vec<_> = pathfinding.find_path(
+    ///     start,
+    ///     goal,
+    ///     cost_fn(&grid),
+    /// ).unwrap().collect();
+    ///
+    /// assert_eq!(dijkstra_path, a_star_path);
+    /// ```
+    pub fn find_paths<F: Sync + Fn(Point) -> Option<C>>(
+        &self,
+        start: Point,
+        goals: &[Point],
+        get_cost: F,
+    ) -> PointMap<AbstractPath<N, C>> {
+        self.find_paths_with_goal_mode(start, goals, self.config.goal_mode, get_cost)
+    }
+
+    /// Same as [`find_paths`](PathCache::find_paths), but with the given [`GoalMode`] instead
+    /// of the [`goal_mode`](PathCacheConfig::goal_mode) of the config.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// let goals = [(4, 4), (2, 0)];
+    /// let paths = pathfinding.find_paths_with_goal_mode(
+    ///     (0, 0),
+    ///     &goals,
+    ///     GoalMode::AdjacentToGoal,
+    ///     cost_fn(&grid),
+    /// );
+    ///
+    /// // next to (4, 4) instead of on it
+    /// assert_eq!(paths[&(4, 4)].cost(), 11);
+    /// // (2, 0) and all of its neighbors are walled off
+    /// assert!(!paths.contains_key(&(2, 0)));
+    /// ```
+    pub fn find_paths_with_goal_mode<F: Sync + Fn(Point) -> Option<C>>(
+        &self,
+        start: Point,
+        goals: &[Point],
+        goal_mode: GoalMode,
+        get_cost: F,
+    ) -> PointMap<AbstractPath<N, C>> {
+        #[cfg(feature = "parallel")]
+        {
+            self.find_paths_internal::<F, fn(Point) -> Option<C>>(
+                start,
+                goals,
+                goal_mode,
+                CostFnWrapper::Parallel(get_cost),
+                false,
+                &mut SearchContext::new(),
+            )
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.find_paths_internal::<fn(Point) -> Option<C>, F>(
+                start,
+                goals,
+                goal_mode,
+                CostFnWrapper::Sequential(get_cost, PhantomData),
+                false,
+                &mut SearchContext::new(),
+            )
+        }
+    }
+
+    /// Same as [`find_paths`](PathCache::find_paths), but doesn't use threads to allow [`FnMut`].
+    ///
+    /// Equivalent to `find_paths` if `parallel` feature is disabled.
+    pub fn find_paths_with_fn_mut<F: FnMut(Point) -> Option<C>>(
+        &self,
+        start: Point,
+        goals: &[Point],
+        get_cost: F,
+    ) -> PointMap<AbstractPath<N, C>> {
+        self.find_paths_internal::<fn(Point) -> Option<C>, F>(
+            start,
+            goals,
+            self.config.goal_mode,
+            CostFnWrapper::Sequential(get_cost, PhantomData),
+            false,
+            &mut SearchContext::new(),
+        )
+    }
+
+    /// Calculates the cost of walking from every one of `sources` to every one of `targets`.
+    ///
+    /// Returns a matrix where `matrix[i][j]` is the cost of the Path from `sources[i]` to
+    /// `targets[j]`, or `None` if there is no such Path.
+    ///
+    /// This is a lot faster than calling [`find_paths`](PathCache::find_paths) for every source,
+    /// because every source and target is connected to the Nodes of its Chunk only once, and
+    /// only a single search on the abstract Graph is needed per source. No Paths are resolved.
+    ///
+    /// Like with [`estimate_cost`](PathCache::estimate_cost), the costs are those of the Paths
+    /// that HPA* would find, which can be slightly higher than the costs of the optimal Paths.
+    /// They are never higher than the ones returned by `estimate_cost`.
+    ///
+    /// `get_cost((x, y))` should return the cost for walking over the Tile at (x, y).
+    /// `None` marks a solid Tile.
+    ///
+    /// With the `parallel` feature, the rows of the matrix are calculated on multiple threads,
+    /// which is why `get_cost` has to be [`Sync`] and [`Fn`]. See
+    /// [`cost_matrix_with_fn_mut`](PathCache::cost_matrix_with_fn_mut) for a sequential version.
+    ///
+    /// ## Panics
+    /// If any of the `sources` is out of bounds of the Grid.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// let workers = [(0, 0), (4, 4)];
+    /// let jobs = [(0, 4), (4, 2), (2, 0)];
+    ///
+    /// let matrix = pathfinding.cost_matrix(&workers, &jobs, cost_fn(&grid));
+    ///
+    /// assert_eq!(matrix[0], [Some(4), Some(10), None]);
+    /// assert_eq!(matrix[1], [Some(8), Some(2), None]);
+    /// ```
+    pub fn cost_matrix<F: Sync + Fn(Point) -> Option<C>>(
+        &self,
+        sources: &[Point],
+        targets: &[Point],
+        get_cost: F,
+    ) -> Vec<Vec<Option<C>>> {
+        #[cfg(feature = "parallel")]
+        {
+            self.cost_matrix_internal::<F, fn(Point) -> Option<C>>(
+                sources,
+                targets,
+                CostFnWrapper::Parallel(get_cost),
+            )
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.cost_matrix_internal::<fn(Point) -> Option<C>, F>(
+                sources,
+                targets,
+                CostFnWrapper::Sequential(get_cost, PhantomData),
+            )
+        }
+    }
+
+    /// Same as [`cost_matrix`](PathCache::cost_matrix), but doesn't use threads to allow
+    /// [`FnMut`].
+    ///
+    /// Equivalent to `cost_matrix` if `parallel` feature is disabled.
+    pub fn cost_matrix_with_fn_mut<F: FnMut(Point) -> Option<C>>(
+        &self,
+        sources: &[Point],
+        targets: &[Point],
+        get_cost: F,
+    ) -> Vec<Vec<Option<C>>> {
+        self.cost_matrix_internal::<fn(Point) -> Option<C>, F>(
+            sources,
+            targets,
+            CostFnWrapper::Sequential(get_cost, PhantomData),
+        )
+    }
+
+    /// Finds the closest from a list of goals.
+    ///
+    /// Returns a tuple of the goal and the Path to that goal, or `None` if none of the goals are
+    /// reachable.
+    ///
+    /// Similar to [`find_paths`](PathCache::find_paths) in performance and search strategy, but
+    /// stops after the first goal is found.
+    /// The [`goal_mode`](PathCacheConfig::goal_mode) applies to every goal, see
+    /// [`find_closest_goal_with_goal_mode`](PathCache::find_closest_goal_with_goal_mode) to
+    /// choose a different [`GoalMode`].
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// let start = (0, 0);
+    /// let goals = [(4, 4), (2, 0), (2, 2)];
+    ///
+    /// // find_closest_goal returns Some((goal, Path)) on success
+    /// let (goal, path) = pathfinding.find_closest_goal(
+    ///     start,
+    ///     &goals,
+    ///     cost_fn(&grid),
+    /// ).unwrap();
+    ///
+    /// assert_eq!(goal, goals[2]);
+    ///
+    /// let naive_closest = pathfinding
+    ///     .find_paths(start, &goals, cost_fn(&grid))
+    ///     .into_iter()
+    ///     .min_by_key(|(_, path)| path.cost())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(goal, naive_closest.0);
+    ///
+    /// let path: Vec<_> = path.collect();
+    /// let naive_path: Vec<_> = naive_closest.1.collect();
+    /// assert_eq!(path, naive_path);
+    /// ```
+    /// Comparison with [`find_paths`](PathCache::find_paths):
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// # let pathfinding = PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    /// # let start = (0, 0);
+    /// # let goals = [(4, 4), (2, 0), (2, 2)];
+    /// let (goal, path) = pathfinding.find_closest_goal(
+    ///     start,
+    ///     &goals,
+    ///     cost_fn(&grid),
+    /// ).unwrap();
+    ///
+    /// let naive_closest = pathfinding
+    ///     .find_paths(start, &goals, cost_fn(&grid))
+    ///     .into_iter()
+    ///     .min_by_key(|(_, path)| path.cost())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(goal, naive_closest.0);
+    ///
+    /// let path: Vec<_> = path.collect();
+    /// let naive_path: Vec<_> = naive_closest.1.collect();
+    /// assert_eq!(path, naive_path);
+    /// ```
+    pub fn find_closest_goal(
+        &self,
+        start: Point,
+        goals: &[Point],
+        get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> Option<(Point, AbstractPath<N, C>)> {
+        self.find_closest_goal_with_goal_mode(start, goals, self.config.goal_mode, get_cost)
+    }
+
+    /// Same as [`find_closest_goal`](PathCache::find_closest_goal), but with the given
+    /// [`GoalMode`] instead of the [`goal_mode`](PathCacheConfig::goal_mode) of the config.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// // (4, 0) is walled off, but the wall at (3, 3) can be reached from the side
+    /// let goals = [(4, 0), (3, 3)];
+    /// let (goal, path) = pathfinding
+    ///     .find_closest_goal_with_goal_mode(
+    ///         (0, 4),
+    ///         &goals,
+    ///         GoalMode::AdjacentToGoal,
+    ///         cost_fn(&grid),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(goal, (3, 3));
+    /// assert_eq!(path.cost(), 3);
+    /// ```
+    pub fn find_closest_goal_with_goal_mode(
+        &self,
+        start: Point,
+        goals: &[Point],
+        goal_mode: GoalMode,
+        get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> Option<(Point, AbstractPath<N, C>)> {
+        self.find_paths_internal::<fn(Point) -> Option<C>, _>(
+            start,
+            goals,
+            goal_mode,
+            CostFnWrapper::Sequential(get_cost, PhantomData),
+            true,
+            &mut SearchContext::new(),
+        )
+        .into_iter()
+        .next()
+    }
+
+    /// Finds the start from a list of starts that can reach `goal` with the lowest cost.
+    ///
+    /// Returns a tuple of the start and the Path from it to `goal`, or `None` if `goal` cannot
+    /// be reached from any of the starts.
+    ///
+    /// This is the opposite of [`find_closest_goal`](PathCache::find_closest_goal): A single
+    /// search on the abstract Graph begins at the Nodes next to all of the starts at once, each
+    /// with the cost of reaching it from its start, and stops once `goal` is reached. The Path is
+    /// searched in the direction from the starts to `goal`, so it has the correct cost even if
+    /// walking the opposite direction would cost something different.
+    ///
+    /// Since the resolved Path from a start can be cheaper than its cost in the abstract Graph,
+    /// the Paths from the other starts are resolved as well, unless their distance to `goal`
+    /// shows that they cannot be cheaper. The result is never more expensive than the cheapest
+    /// Path to exactly `goal` that [`find_path`](PathCache::find_path) finds from any of the
+    /// starts.
+    ///
+    /// The [`goal_mode`](PathCacheConfig::goal_mode) does not apply here.
+    ///
+    /// `get_cost((x, y))` should return the cost for walking over the Tile at (x, y).
+    /// `None` marks a solid Tile. Starts on solid Tiles are skipped.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// let starts = [(0, 0), (4, 4), (2, 0)];
+    /// let goal = (2, 2);
+    /// let (start, path) = pathfinding
+    ///     .find_closest_start(&starts, goal, cost_fn(&grid))
+    ///     .unwrap();
+    ///
+    /// let naive_closest = starts
+    ///     .iter()
+    ///     .filter_map(|&start| pathfinding.find_path(start, goal, cost_fn(&grid)))
+    ///     .map(|path| path.cost())
+    ///     .min()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(start, (4, 4));
+    /// assert!(path.cost() <= naive_closest);
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if any of the `starts` are outside of the Grid.
+    pub fn find_closest_start(
+        &self,
+        starts: &[Point],
+        goal: Point,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> Option<(Point, AbstractPath<N, C>)> {
+        for &start in starts {
+            self.assert_in_bounds("start", start);
+        }
+        if !self.in_bounds(goal) {
+            return None;
+        }
+
+        let mut context = SearchContext::new();
+        let mut best: Option<(Point, AbstractPath<N, C>)> = None;
+
+        // the Nodes next to the starts and the cost of reaching them
+        let mut seeds = vec![];
+        let mut start_paths = vec![];
+        for &start in starts {
+            // cannot start on a wall
+            if get_cost(start).is_none() {
+                continue;
+            }
+            if start == goal {
+                let path = Path::from_slice(&[start, start], C::ZERO);
+                return Some((start, self.known_path(path)));
+            }
+            if let Some((id, path)) =
+                self.find_nearest_node(start, &mut get_cost, false, &mut context)
+            {
+                seeds.push((id, path.as_ref().map_or(C::ZERO, Path::cost)));
+                start_paths.push((start, path));
+            } else if self.same_chunk(start, goal) {
+                // start is in a cave within its Chunk, see find_path
+                let path = self.get_chunk(start).find_path(
+                    &mut context.grid,
+                    start,
+                    goal,
+                    &mut get_cost,
+                    &self.neighborhood,
+                    self.min_cost(),
+                );
+                if let Some(path) = path {
+                    if best
+                        .as_ref()
+                        .is_none_or(|(_, best)| path.cost() < best.cost())
+                    {
+                        best = Some((start, self.known_path(path)));
+                    }
+                }
+            }
+        }
+
+        let Some((goal_id, goal_path)) =
+            self.find_nearest_node(goal, &mut get_cost, true, &mut context)
+        else {
+            return best;
+        };
+
+        let Some(graph_path) =
+            graph::dijkstra_from_any(&mut context.graph, &self.nodes, &seeds, goal_id)
+        else {
+            return best;
+        };
+
+        // the start with the cheapest Path to the Node that the Path begins at
+        let first = graph_path[0];
+        let index = (0..seeds.len())
+            .filter(|&i| seeds[i].0 == first)
+            .min_by_key(|&i| seeds[i].1)
+            .expect("Inconsistency in Pathfinding");
+        let (start, start_path) = &start_paths[index];
+
+        let parts = self.resolve_path(
+            *start,
+            start_path.as_ref(),
+            goal,
+            &graph_path,
+            goal_path,
+            &mut get_cost,
+            &mut context,
+            &mut PointMap::default(),
+        );
+        if let Some(path) = self.build_path(*start, parts) {
+            if best
+                .as_ref()
+                .is_none_or(|(_, best)| path.cost() < best.cost())
+            {
+                best = Some((*start, path));
+            }
+        }
+
+        // resolving a Path can make it cheaper than its cost in the abstract Graph
+        let starts = start_paths.iter().map(|(start, _)| *start).to_vec();
+        self.improve_closest_start(&starts, goal, best, get_cost, &mut context)
+    }
+
+    /// Resolves the Paths from all `starts` that are close enough to `goal` to be cheaper than
+    /// `best`, and returns the cheapest one
+    fn improve_closest_start(
+        &self,
+        starts: &[Point],
+        goal: Point,
+        mut best: Option<(Point, AbstractPath<N, C>)>,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+        context: &mut SearchContext<C>,
+    ) -> Option<(Point, AbstractPath<N, C>)> {
+        let mut candidates = starts
+            .iter()
+            .map(|&start| {
+                let bound = self
+                    .heuristic()
+                    .bound(self.neighborhood.heuristic(start, goal));
+                (bound, start)
+            })
+            .to_vec();
+        candidates.sort_by_key(|&(bound, _)| bound);
+        for (bound, start) in candidates {
+            if best.as_ref().is_some_and(|(_, best)| bound >= best.cost()) {
+                break;
+            }
+            let Some(path) = self.find_path_exact(start, goal, &mut get_cost, context) else {
+                continue;
+            };
+            if best
+                .as_ref()
+                .is_none_or(|(_, best)| path.cost() < best.cost())
+            {
+                best = Some((start, path));
+            }
+        }
+        best
+    }
+
+    /// Calculates the Path from `start` to the closest Tile of a [`Region`].
+    ///
+    /// Returns a tuple of the Tile that the Path leads to and the Path itself, or `None` if no
+    /// Tile of the Region can be reached.
+    ///
+    /// The abstract Graph is searched with a heuristic towards the bounding box of the Region.
+    /// Every Node in a Chunk that contains Tiles of the Region is a candidate for the end of the
+    /// Path, which is then connected to the closest Region Tile within that Chunk. The search
+    /// stops once no other Node can lead to a closer Tile. The Path ends at the first Tile of
+    /// the Region that it reaches, so it is always fully resolved.
+    ///
+    /// The [`goal_mode`](PathCacheConfig::goal_mode) does not apply to Regions, since a Region can
+    /// simply be extended to include the Tiles around it.
+    ///
+    /// `get_cost((x, y))` should return the cost for walking over the Tile at (x, y).
+    /// `None` marks a solid Tile.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// // the bottom-right corner of the Grid
+    /// let region = Region::Rect { pos: (2, 2), size: (3, 3) };
+    ///
+    /// let (tile, path) = pathfinding
+    ///     .find_path_to_region((0, 0), region, cost_fn(&grid))
+    ///     .unwrap();
+    ///
+    /// // around the swamp is cheaper than through it
+    /// assert_eq!(tile, (2, 4));
+    /// assert_eq!(path.cost(), 6);
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if `start` is outside of the Grid.
+    pub fn find_path_to_region(
+        &self,
+        start: Point,
+        region: Region,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> Option<(Point, AbstractPath<N, C>)> {
+        self.assert_in_bounds("start", start);
+
+        // cannot start on a wall
+        get_cost(start)?;
+
+        if region.contains(start) {
+            let path = Path::from_slice(&[start, start], C::ZERO);
+            return Some((start, self.known_path(path)));
+        }
+
+        let (min, max) = region.bounds((self.width, self.height))?;
+        let mut context = SearchContext::new();
+
+        // the closest Tile that can be reached without leaving the Chunk of start
+        let start_chunk = self.get_chunk(start);
+        let mut tiles = vec![];
+        region.tiles_in(start_chunk.pos, start_chunk.size, &mut tiles);
+        let local = grid::dijkstra_search(
+            &mut context.grid,
+            &self.neighborhood,
+            start_chunk.pos,
+            start_chunk.size,
+            &mut get_cost,
+            start,
+            &tiles,
+            true,
+        )
+        .into_iter()
+        .next();
+
+        let Some((start_id, start_path)) =
+            self.find_nearest_node(start, &mut get_cost, false, &mut context)
+        else {
+            // start is in a cave within its Chunk
+            return local.map(|(tile, path)| (tile, self.known_path(path)));
+        };
+        let start_cost = start_path.as_ref().map_or(C::ZERO, Path::cost);
+
+        let found = {
+            let SearchContext { grid, graph } = &mut context;
+            // the Region Tiles of every Chunk that was visited so far
+            let mut chunk_tiles = hashbrown::HashMap::new();
+
+            let heuristic = |id: NodeID| {
+                let pos = self.nodes[id].pos;
+                let closest = (pos.0.clamp(min.0, max.0), pos.1.clamp(min.1, max.1));
+                self.heuristic()
+                    .estimate(self.neighborhood.heuristic(pos, closest))
+            };
+            let goal_cost = |id: NodeID| {
+                let pos = self.nodes[id].pos;
+                let index = self.get_chunk_index(pos);
+                let chunk = &self.chunks[index];
+                if !chunk.overlaps(min, max) {
+                    return None;
+                }
+                let tiles = chunk_tiles.entry(index).or_insert_with(|| {
+                    let mut tiles = vec![];
+                    region.tiles_in(chunk.pos, chunk.size, &mut tiles);
+                    tiles
+                });
+                if tiles.is_empty() {
+                    return None;
+                }
+                let (tile, path) = grid::dijkstra_search(
+                    grid,
+                    &self.neighborhood,
+                    chunk.pos,
+                    chunk.size,
+                    &mut get_cost,
+                    pos,
+                    tiles,
+                    true,
+                )
+                .into_iter()
+                .next()?;
+                // the search starts at start_id, but the Path starts at start
+                let cost = start_cost.checked_add(path.cost())?;
+                Some((cost, (tile, path)))
+            };
+            graph::a_star_to_any(
+                graph,
+                &self.nodes,
+                start_id,
+                local.as_ref().map(|(_, path)| path.cost()),
+                heuristic,
+                goal_cost,
+            )
+        };
+
+        if let Some((graph_path, _, (tile, tile_path))) = found {
+            let last = graph_path[graph_path.len() - 1];
+            let goal_path = if self.nodes[last].pos == tile {
+                None
+            } else {
+                Some(tile_path)
+            };
+            let parts = self.resolve_path(
+                start,
+                start_path.as_ref(),
+                tile,
+                &graph_path,
+                goal_path,
+                &mut get_cost,
+                &mut context,
+                &mut PointMap::default(),
+            );
+            let path = self.build_path(start, parts)?;
+            // the Path between two Nodes can cross the Region before the Path reaches tile
+            Some(self.truncate_at_region(start, path, region, get_cost))
+        } else {
+            local.map(|(tile, path)| (tile, self.known_path(path)))
+        }
+    }
+
+    /// Shortens `path` to end at the first Tile of `region` on it, and returns that Tile and the
+    /// resolved Path to it
+    fn truncate_at_region(
+        &self,
+        start: Point,
+        path: AbstractPath<N, C>,
+        region: Region,
+        get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> (Point, AbstractPath<N, C>) {
+        let mut points = vec![start];
+        let mut cost = C::ZERO;
+        for (pos, total) in path.cumulative_costs(get_cost) {
+            points.push(pos);
+            cost = total;
+            if region.contains(pos) {
+                break;
+            }
+        }
+        let tile = points[points.len() - 1];
+        (tile, self.known_path(Path::new(points, cost)))
+    }
+
+    /// Finds every Tile that can be reached from `start` with a total cost of at most `max_cost`.
+    ///
+    /// Returns a `HashMap` with the cost of walking to every reachable Tile, including `start`
+    /// itself with a cost of `0`. Like in [`find_path`](PathCache::find_path), the cost of a step
+    /// is the cost of the Tile that is left.
+    ///
+    /// The search is a Dijkstra on the Grid that stops at `max_cost`, so its work depends only on
+    /// the number of Tiles in range. It is limited to the Chunks that are close enough to `start`
+    /// to contain a Tile in range, based on the distance to them and the lowest cost of any Tile.
+    ///
+    /// `get_cost((x, y))` should return the cost for walking over the Tile at (x, y).
+    /// `None` marks a solid Tile.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// let in_range = pathfinding.reachable_within((0, 0), 4, cost_fn(&grid));
+    ///
+    /// assert_eq!(in_range.len(), 7);
+    /// assert_eq!(in_range[&(0, 0)], 0);
+    /// assert_eq!(in_range[&(0, 4)], 4);
+    /// // (1, 2) costs 10 to leave, but entering it is cheap
+    /// assert_eq!(in_range[&(1, 2)], 3);
+    /// assert_eq!(in_range[&(1, 3)], 4);
+    /// assert!(!in_range.contains_key(&(1, 4)));
+    /// ```
+    pub fn reachable_within(
+        &self,
+        start: Point,
+        max_cost: C,
+        get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> PointMap<C> {
+        self.reachable_within_with_predecessors(start, max_cost, get_cost)
+            .into_iter()
+            .map(|(pos, (cost, _))| (pos, cost))
+            .collect()
+    }
+
+    /// Same as [`reachable_within`](PathCache::reachable_within), but also returns the previous
+    /// Tile on the cheapest Path to every reachable Tile.
     ///
-    /// assert_eq!(goal, naive_closest.0);
+    /// The previous Tile of `start` is `start` itself.
     ///
-    /// let path: Vec<_> = path.collect();
-    /// let naive_path: Vec<_> = naive_closest.1.collect();
-    /// assert_eq!(path, naive_path);
+    /// ## Examples
+    /// Building the Path to a reachable Tile:
     /// ```
-    pub fn find_closest_goal(
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// # let pathfinding = PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    /// let start = (0, 0);
+    /// let in_range = pathfinding.reachable_within_with_predecessors(start, 6, cost_fn(&grid));
+    ///
+    /// let mut path = vec![(2, 4)];
+    /// while path[path.len() - 1] != start {
+    ///     let (_, previous) = in_range[&path[path.len() - 1]];
+    ///     path.push(previous);
+    /// }
+    /// path.reverse();
+    ///
+    /// assert_eq!(in_range[&(2, 4)].0, 6);
+    /// assert_eq!(path, vec![(0, 0), (0, 1), (0, 2), (0, 3), (0, 4), (1, 4), (2, 4)]);
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if `start` is out of bounds.
+    pub fn reachable_within_with_predecessors(
+        &self,
+        start: Point,
+        max_cost: C,
+        get_cost: impl FnMut(Point) -> Option<C>,
+    ) -> PointMap<(C, Point)> {
+        self.assert_in_bounds("start", start);
+        // Every step costs at least min_cost, which rules out all Chunks that are too far away.
+        // The costs of the Nodes can't rule out any more Chunks: they are only upper bounds, since
+        // a Chunk can also be entered between its Nodes.
+        let heuristic = Heuristic::exact(self.min_cost());
+        let mut area: Option<(Point, Point)> = None;
+        for chunk in &self.chunks {
+            let end = (chunk.right(), chunk.bottom());
+            let closest = (
+                start.0.clamp(chunk.pos.0, end.0 - 1),
+                start.1.clamp(chunk.pos.1, end.1 - 1),
+            );
+            if heuristic.bound(self.neighborhood.heuristic(start, closest)) > max_cost {
+                continue;
+            }
+            area = Some(if let Some((min, max)) = area {
+                (
+                    (min.0.min(chunk.pos.0), min.1.min(chunk.pos.1)),
+                    (max.0.max(end.0), max.1.max(end.1)),
+                )
+            } else {
+                (chunk.pos, end)
+            });
+        }
+        // the Chunk of start is always in range
+        let (min, max) = area.expect("Inconsistency in Pathfinding");
+
+        let (in_range, cut_off) = grid::dijkstra_within(
+            &mut SearchBuffers::default(),
+            &self.neighborhood,
+            min,
+            (max.0 - min.0, max.1 - min.1),
+            get_cost,
+            start,
+            max_cost,
+        );
+        debug_assert!(
+            !cut_off,
+            "a Tile outside of the Chunks in range was reached"
+        );
+        in_range
+    }
+
+    /// Finds the closest Tile for which `predicate` returns `true`.
+    ///
+    /// Returns a tuple of the Tile and the Path to it, or `None` if no matching Tile can be
+    /// reached with a cost of at most `max_cost`.
+    ///
+    /// This is similar to [`find_closest_goal`](PathCache::find_closest_goal), except that the
+    /// goals don't have to be known beforehand. The Grid is searched outwards from `start` until
+    /// a matching Tile or `max_cost` is reached, and `predicate` is called for all Tiles of a
+    /// Chunk once the search enters it.
+    ///
+    /// `get_cost((x, y))` should return the cost for walking over the Tile at (x, y).
+    /// `None` marks a solid Tile. Like in [`find_path`](PathCache::find_path), the matching Tile
+    /// itself may be solid.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// // the closest swamp Tile
+    /// let (tile, path) = pathfinding
+    ///     .find_nearest_matching((0, 4), |(x, y)| grid[y][x] == 1, cost_fn(&grid), 100)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(tile, (1, 3));
+    /// assert_eq!(path.cost(), 2);
+    ///
+    /// // too far away
+    /// let nearest = pathfinding.find_nearest_matching((0, 4), |pos| pos == (4, 4), cost_fn(&grid), 7);
+    /// assert!(nearest.is_none());
+    /// ```
+    pub fn find_nearest_matching(
+        &self,
+        start: Point,
+        mut predicate: impl FnMut(Point) -> bool,
+        get_cost: impl FnMut(Point) -> Option<C>,
+        max_cost: C,
+    ) -> Option<(Point, AbstractPath<N, C>)> {
+        let start_matches = self.in_bounds(start) && predicate(start);
+        self.find_nearest_internal(
+            start,
+            start_matches,
+            |chunk: &Chunk, goals: &mut Vec<Point>| {
+                for y in chunk.pos.1..chunk.pos.1 + chunk.size.1 {
+                    for x in chunk.pos.0..chunk.pos.0 + chunk.size.0 {
+                        if predicate((x, y)) {
+                            goals.push((x, y));
+                        }
+                    }
+                }
+            },
+            get_cost,
+            max_cost,
+        )
+    }
+
+    /// Sets the tags of `tiles` to the ones returned by `get_tags`.
+    ///
+    /// Tags are the bits of a `u64`, with a meaning that is up to the user, like "has water" or
+    /// "has enemy". Every Chunk keeps track of the tags of its Tiles, so that
+    /// [`find_nearest_tagged`](PathCache::find_nearest_tagged) can skip all Chunks without
+    /// matching Tiles. Tiles don't have any tags until they are set with this method.
+    ///
+    /// The tags are independent of the cost of the Tiles, so this has to be called separately
+    /// from [`tiles_changed`](PathCache::tiles_changed) whenever the tags of some Tiles change.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let mut pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// const WATER: u64 = 1 << 0;
+    /// const ORE: u64 = 1 << 1;
+    ///
+    /// let mut tags = std::collections::HashMap::new();
+    /// tags.insert((4, 4), WATER);
+    /// tags.insert((2, 2), WATER | ORE);
+    /// let tiles: Vec<_> = tags.keys().copied().collect();
+    ///
+    /// pathfinding.tag_tiles(&tiles, |pos| tags.get(&pos).copied().unwrap_or(0));
+    /// assert_eq!(pathfinding.tags_at((2, 2)), WATER | ORE);
+    ///
+    /// // the ore was mined
+    /// tags.insert((2, 2), WATER);
+    /// pathfinding.tag_tiles(&[(2, 2)], |pos| tags.get(&pos).copied().unwrap_or(0));
+    /// assert_eq!(pathfinding.tags_at((2, 2)), WATER);
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if any of the `tiles` are outside of the Grid.
+    pub fn tag_tiles(&mut self, tiles: &[Point], mut get_tags: impl FnMut(Point) -> u64) {
+        for &pos in tiles {
+            self.assert_in_bounds("tile", pos);
+            let chunk_index = self.get_chunk_index(pos);
+            self.tags
+                .set(chunk_index, self.chunks.len(), pos, get_tags(pos));
+        }
+    }
+
+    /// Returns the tags of the Tile at `pos` that were set with
+    /// [`tag_tiles`](PathCache::tag_tiles)
+    pub fn tags_at(&self, pos: Point) -> u64 {
+        if !self.in_bounds(pos) {
+            return 0;
+        }
+        self.tags.get(self.get_chunk_index(pos), pos)
+    }
+
+    /// Finds the closest Tile that has any of `tags`, as set with
+    /// [`tag_tiles`](PathCache::tag_tiles).
+    ///
+    /// Returns a tuple of the Tile and the Path to it, or `None` if no matching Tile can be
+    /// reached with a cost of at most `max_cost`.
+    ///
+    /// Works like [`find_nearest_matching`](PathCache::find_nearest_matching), except that the
+    /// matching Tiles of a Chunk are looked up from its tags instead of checking every Tile of it.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let mut pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// const WATER: u64 = 1 << 0;
+    /// const ORE: u64 = 1 << 1;
+    ///
+    /// pathfinding.tag_tiles(&[(4, 4), (2, 2), (0, 4)], |pos| match pos {
+    ///     (0, 4) => ORE,
+    ///     _ => WATER,
+    /// });
+    ///
+    /// let (tile, path) = pathfinding.find_nearest_tagged((0, 0), WATER, cost_fn(&grid), 100).unwrap();
+    /// assert_eq!(tile, (2, 2));
+    /// assert_eq!(path.cost(), 8);
+    ///
+    /// let (tile, _) = pathfinding.find_nearest_tagged((0, 0), WATER | ORE, cost_fn(&grid), 100).unwrap();
+    /// assert_eq!(tile, (0, 4));
+    /// ```
+    pub fn find_nearest_tagged(
+        &self,
+        start: Point,
+        tags: u64,
+        get_cost: impl FnMut(Point) -> Option<C>,
+        max_cost: C,
+    ) -> Option<(Point, AbstractPath<N, C>)> {
+        let start_matches = self.tags_at(start) & tags != 0;
+        self.find_nearest_internal(
+            start,
+            start_matches,
+            |chunk: &Chunk, goals: &mut Vec<Point>| {
+                let chunk_index = self.get_chunk_index(chunk.pos);
+                goals.extend(self.tags.tiles_with(chunk_index, tags));
+            },
+            get_cost,
+            max_cost,
+        )
+    }
+
+    /// Finds the closest of the goals that `find_goals` adds for every Chunk.
+    ///
+    /// This is a Dijkstra search on the Grid from `start` that stops at the first goal or at
+    /// `max_cost`, so it finds the same goal as a search without Chunks would. The goals of a
+    /// Chunk are only looked up once the search reaches it.
+    fn find_nearest_internal(
+        &self,
+        start: Point,
+        start_matches: bool,
+        mut find_goals: impl FnMut(&Chunk, &mut Vec<Point>),
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+        max_cost: C,
+    ) -> Option<(Point, AbstractPath<N, C>)> {
+        self.assert_in_bounds("start", start);
+
+        // cannot start on a wall
+        get_cost(start)?;
+
+        if start_matches {
+            let path = Path::from_slice(&[start, start], C::ZERO);
+            return Some((start, self.known_path(path)));
+        }
+
+        let mut chunk_goals: Vec<Option<PointSet>> = vec![None; self.chunks.len()];
+        let mut goals = vec![];
+        let is_goal = |pos: Point| {
+            let index = self.get_chunk_index(pos);
+            chunk_goals[index]
+                .get_or_insert_with(|| {
+                    goals.clear();
+                    find_goals(&self.chunks[index], &mut goals);
+                    goals.iter().copied().collect()
+                })
+                .contains(&pos)
+        };
+        let (goal, path) = grid::dijkstra_from_any(
+            &mut SearchBuffers::default(),
+            &self.neighborhood,
+            (0, 0),
+            (self.width, self.height),
+            get_cost,
+            &[(start, C::ZERO)],
+            is_goal,
+            max_cost,
+        )?;
+        Some((goal, self.known_path(path)))
+    }
+
+    fn find_paths_internal<F1, F2>(
+        &self,
+        start: Point,
+        goals: &[Point],
+        mode: GoalMode,
+        mut get_cost: CostFnWrapper<F1, F2>,
+        only_closest_goal: bool,
+        context: &mut SearchContext<C>,
+    ) -> PointMap<AbstractPath<N, C>>
+    where
+        F1: Sync + Fn(Point) -> Option<C>,
+        F2: FnMut(Point) -> Option<C>,
+    {
+        if mode == GoalMode::Exact {
+            return self.find_paths_exact(start, goals, get_cost, only_closest_goal, context);
+        }
+
+        let goal_tiles = {
+            let get_cost = get_cost.as_fn_mut();
+            goals
+                .iter()
+                .map(|&goal| (goal, self.goal_tiles(goal, mode, &mut *get_cost)))
+                .to_vec()
+        };
+        let mut tiles = goal_tiles
+            .iter()
+            .flat_map(|(_, tiles)| tiles.iter().copied())
+            .to_vec();
+        tiles.sort_unstable();
+        tiles.dedup();
+
+        let paths = self.find_paths_exact(start, &tiles, get_cost, only_closest_goal, context);
+
+        // the cheapest Path to any of the Tiles of every goal
+        let cheapest = goal_tiles.into_iter().filter_map(|(goal, tiles)| {
+            tiles
+                .iter()
+                .filter_map(|tile| paths.get(tile))
+                .min_by_key(|path| path.cost())
+                .map(|path| (goal, path.clone()))
+        });
+        if only_closest_goal {
+            cheapest
+                .min_by_key(|(_, path)| path.cost())
+                .into_iter()
+                .collect()
+        } else {
+            cheapest.collect()
+        }
+    }
+
+    /// Finds the Paths to exactly the `goals`, see [`find_paths`](PathCache::find_paths)
+    fn find_paths_exact<F1, F2>(
         &self,
         start: Point,
         goals: &[Point],
-        get_cost: impl FnMut(Point) -> isize,
-    ) -> Option<(Point, AbstractPath<N>)> {
-        self.find_paths_internal(start, goals, get_cost, true)
+        mut get_cost: CostFnWrapper<F1, F2>,
+        only_closest_goal: bool,
+        context: &mut SearchContext<C>,
+    ) -> PointMap<AbstractPath<N, C>>
+    where
+        F1: Sync + Fn(Point) -> Option<C>,
+        F2: FnMut(Point) -> Option<C>,
+    {
+        self.assert_in_bounds("start", start);
+
+        let mut ret = PointMap::default();
+
+        let (start_id, start_path) = {
+            let mut get_cost = get_cost.as_fn_mut();
+
+            if get_cost(start).is_none() || goals.is_empty() {
+                return ret;
+            }
+
+            if goals.len() == 1 {
+                let goal = goals[0];
+                return self
+                    .find_path_exact(start, goal, get_cost, context)
+                    .map(|path| (goal, path))
+                    .into_iter()
+                    .collect();
+            }
+
+            if let Some(s) = self.find_nearest_node(start, &mut get_cost, false, context) {
+                s
+            } else {
+                // no path from start to any Node => start is in cave within chunk
+                // => find all goals in the same cave
+                return self
+                    .get_chunk(start)
+                    .find_paths(
+                        &mut context.grid,
+                        start,
+                        goals,
+                        get_cost,
+                        &self.neighborhood,
+                    )
+                    .into_iter()
+                    .map(|(goal, path)| (goal, self.known_path(path)))
+                    .collect();
+            }
+        };
+
+        if goals.contains(&start) {
+            ret.insert(
+                start,
+                self.known_path(Path::from_slice(&[start, start], C::ZERO)),
+            );
+        }
+        let goals = goals
+            .iter()
+            .copied()
+            .filter(|&goal| goal != start && self.in_bounds(goal))
+            .to_vec();
+        let goal_data = self.find_goal_nodes(&goals, &mut get_cost, context);
+
+        let goal_ids = goal_data.iter().map(|(_, id, _)| *id).to_vec();
+
+        let paths = graph::dijkstra_search(
+            &mut context.graph,
+            &self.nodes,
+            start_id,
+            &goal_ids,
+            only_closest_goal,
+        );
+
+        self.resolve_paths(
+            start,
+            start_path.as_ref(),
+            goal_data,
+            &paths,
+            get_cost,
+            context,
+            &mut ret,
+        );
+        ret
+    }
+
+    /// Resolves the Paths to all goals that were reached in the graph, using multiple threads if
+    /// possible
+    #[allow(clippy::too_many_arguments)]
+    fn resolve_paths<F1, F2>(
+        &self,
+        start: Point,
+        start_path: Option<&Path<Point, C>>,
+        goal_data: Vec<GoalNode<C>>,
+        paths: &NodeIDMap<Path<NodeID, C>>,
+        get_cost: CostFnWrapper<F1, F2>,
+        context: &mut SearchContext<C>,
+        out: &mut PointMap<AbstractPath<N, C>>,
+    ) where
+        F1: Sync + Fn(Point) -> Option<C>,
+        F2: FnMut(Point) -> Option<C>,
+    {
+        // the graph paths to all goals that were found
+        let goal_data = goal_data
             .into_iter()
-            .next()
+            .filter_map(|(goal, goal_id, goal_path)| {
+                paths.get(&goal_id).map(|path| (goal, path, goal_path))
+            });
+
+        match get_cost {
+            CostFnWrapper::Sequential(mut get_cost, _) => {
+                // a map for direct paths from the start to other nodes in the same chunk as start.
+                // see `resolve_path`
+                let mut start_path_map = PointMap::default();
+
+                for (goal, path, goal_path) in goal_data {
+                    let parts = self.resolve_path(
+                        start,
+                        start_path,
+                        goal,
+                        path,
+                        goal_path,
+                        &mut get_cost,
+                        context,
+                        &mut start_path_map,
+                    );
+                    if let Some(path) = self.build_path(start, parts) {
+                        out.insert(goal, path);
+                    }
+                }
+            }
+            #[cfg(feature = "parallel")]
+            CostFnWrapper::Parallel(get_cost) => {
+                use rayon::prelude::*;
+                // every thread has its own start_path_map, so a few paths might be calculated
+                // more than once
+                let resolved: Vec<_> = goal_data
+                    .to_vec()
+                    .into_par_iter()
+                    .map_init(
+                        || (SearchContext::new(), PointMap::default()),
+                        |(context, start_path_map), (goal, path, goal_path)| {
+                            let parts = self.resolve_path(
+                                start,
+                                start_path,
+                                goal,
+                                path,
+                                goal_path,
+                                &get_cost,
+                                context,
+                                start_path_map,
+                            );
+                            (goal, parts)
+                        },
+                    )
+                    .collect();
+
+                for (goal, parts) in resolved {
+                    if let Some(path) = self.build_path(start, parts) {
+                        out.insert(goal, path);
+                    }
+                }
+            }
+        }
     }
 
-    fn find_paths_internal(
+    /// Finds the nearest Node for each goal, using multiple threads if possible
+    fn find_goal_nodes<F1, F2>(
         &self,
-        start: Point,
         goals: &[Point],
-        mut get_cost: impl FnMut(Point) -> isize,
-        only_closest_goal: bool,
-    ) -> PointMap<AbstractPath<N>> {
-        if !self.in_bounds(start) {
-            panic!(
-                "start {:?} is out of bounds of a grid of size {}x{}",
-                start, self.width, self.height
-            );
-        }
-        if get_cost(start) < 0 || goals.is_empty() {
-            return PointMap::default();
+        get_cost: &mut CostFnWrapper<F1, F2>,
+        context: &mut SearchContext<C>,
+    ) -> Vec<GoalNode<C>>
+    where
+        F1: Sync + Fn(Point) -> Option<C>,
+        F2: FnMut(Point) -> Option<C>,
+    {
+        // goals that are in a cave within a chunk are skipped. If it was the same cave as start,
+        // then we would have already stopped at the `find_nearest_node` for start.
+        // Since we didn't, we know that goal is in a different cave that is not
+        // reachable from the node network.
+        match get_cost {
+            CostFnWrapper::Sequential(get_cost, _) => goals
+                .iter()
+                .filter_map(|&goal| {
+                    self.find_nearest_node(goal, &mut *get_cost, true, context)
+                        .map(|(goal_id, goal_path)| (goal, goal_id, goal_path))
+                })
+                .collect(),
+            #[cfg(feature = "parallel")]
+            CostFnWrapper::Parallel(get_cost) => {
+                use rayon::prelude::*;
+                goals
+                    .par_iter()
+                    .map_init(SearchContext::new, |context, &goal| {
+                        self.find_nearest_node(goal, &*get_cost, true, context)
+                            .map(|(goal_id, goal_path)| (goal, goal_id, goal_path))
+                    })
+                    .flatten()
+                    .collect()
+            }
         }
+    }
 
-        if goals.len() == 1 {
-            let goal = goals[0];
-            return self
-                .find_path(start, goal, get_cost)
-                .map(|path| (goal, path))
-                .into_iter()
-                .collect();
+    fn cost_matrix_internal<F1, F2>(
+        &self,
+        sources: &[Point],
+        targets: &[Point],
+        get_cost: CostFnWrapper<F1, F2>,
+    ) -> Vec<Vec<Option<C>>>
+    where
+        F1: Sync + Fn(Point) -> Option<C>,
+        F2: FnMut(Point) -> Option<C>,
+    {
+        for &source in sources {
+            self.assert_in_bounds("source", source);
         }
 
-        let neighborhood = self.neighborhood.clone();
-
-        let (start_id, start_path) =
-            if let Some(s) = self.find_nearest_node(start, &mut get_cost, false) {
-                s
-            } else {
-                // no path from start to any Node => start is in cave within chunk
-                // => find all goals in the same cave
-                return self
-                    .get_chunk(start)
-                    .find_paths(start, goals, get_cost, &neighborhood)
-                    .into_iter()
-                    .map(|(goal, path)| {
-                        (
-                            goal,
-                            AbstractPath::from_known_path(neighborhood.clone(), path),
-                        )
+        match get_cost {
+            CostFnWrapper::Sequential(mut get_cost, _) => {
+                let context = &mut SearchContext::new();
+                let target_nodes = targets
+                    .iter()
+                    .map(|&target| self.nodes_to_target(target, &mut get_cost, context))
+                    .to_vec();
+                sources
+                    .iter()
+                    .map(|&source| {
+                        self.cost_row(source, targets, &target_nodes, &mut get_cost, context)
                     })
-                    .collect();
-            };
-
-        let mut goal_data = Vec::with_capacity(goals.len());
-        let mut goal_ids = Vec::with_capacity(goals.len());
-
-        let mut ret = PointMap::default();
-        let mut heuristic = 0;
-
-        for goal in goals.iter().copied() {
-            if goal == start {
-                let path = AbstractPath::from_known_path(
-                    self.neighborhood.clone(),
-                    Path::from_slice(&[start, start], 0),
-                );
-                ret.insert(goal, path);
-                continue;
+                    .collect()
             }
-
-            if !self.in_bounds(goal) {
-                continue;
+            #[cfg(feature = "parallel")]
+            CostFnWrapper::Parallel(get_cost) => {
+                use rayon::prelude::*;
+                let target_nodes: Vec<_> = targets
+                    .par_iter()
+                    .map_init(SearchContext::new, |context, &target| {
+                        self.nodes_to_target(target, &get_cost, context)
+                    })
+                    .collect();
+                sources
+                    .par_iter()
+                    .map_init(SearchContext::new, |context, &source| {
+                        self.cost_row(source, targets, &target_nodes, &get_cost, context)
+                    })
+                    .collect()
             }
+        }
+    }
 
-            let (goal_id, goal_path) =
-                if let Some(g) = self.find_nearest_node(goal, &mut get_cost, true) {
-                    g
-                } else {
-                    // goal is in a cave within a chunk. If it was the same cave as start,
-                    // then we would have already stopped at the `find_nearest_node` for start.
-                    // Since we didn't, we know that goal is in a different cave that is not
-                    // reachable from the node network.
-                    continue;
-                };
-
-            goal_data.push((goal, goal_id, goal_path));
-            goal_ids.push(goal_id);
-            if only_closest_goal {
-                heuristic = heuristic.min(self.neighborhood.heuristic(start, goal));
-            } else {
-                heuristic = heuristic.max(self.neighborhood.heuristic(start, goal));
-            }
+    /// Finds the cost of walking from every Node in the Chunk of `target` to `target`
+    fn nodes_to_target(
+        &self,
+        target: Point,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+        context: &mut SearchContext<C>,
+    ) -> Vec<(NodeID, C)> {
+        if !self.in_bounds(target) {
+            return vec![];
         }
+        let chunk = self.get_chunk(target);
 
-        let max_heuristic = neighborhood.heuristic((0, 0), (self.width - 1, self.height - 1));
-        let max_size = self.nodes.len();
-        let size_hint = heuristic as f32 / max_heuristic as f32 * max_size as f32;
+        let Some(target_cost) = get_cost(target) else {
+            // a solid target cannot be used as a start, so search from every Node instead
+            return chunk
+                .nodes
+                .iter()
+                .filter_map(|&id| {
+                    chunk
+                        .find_path(
+                            &mut context.grid,
+                            self.nodes[id].pos,
+                            target,
+                            &mut get_cost,
+                            &self.neighborhood,
+                            self.min_cost(),
+                        )
+                        .map(|path| (id, path.cost()))
+                })
+                .collect();
+        };
 
-        let paths = graph::dijkstra_search(
-            &self.nodes,
-            start_id,
-            &goal_ids,
-            only_closest_goal,
-            size_hint as usize,
+        let points = chunk.nodes.iter().map(|&id| self.nodes[id].pos).to_vec();
+        let paths = chunk.find_paths(
+            &mut context.grid,
+            target,
+            &points,
+            get_cost,
+            &self.neighborhood,
         );
+        chunk
+            .nodes
+            .iter()
+            .filter_map(|&id| {
+                let node = &self.nodes[id];
+                if node.pos == target {
+                    return Some((id, C::ZERO));
+                }
+                paths
+                    .get(&node.pos)
+                    .map(|path| (id, path.reversed(target_cost, node.walk_cost).cost()))
+            })
+            .collect()
+    }
 
-        self.resolve_paths(
-            start,
-            start_path,
-            &mut goal_data,
-            &paths,
+    /// Calculates one row of [`cost_matrix`](PathCache::cost_matrix)
+    ///
+    /// `target_nodes` contains the result of [`nodes_to_target`](PathCache::nodes_to_target) for
+    /// every target.
+    fn cost_row(
+        &self,
+        source: Point,
+        targets: &[Point],
+        target_nodes: &[Vec<(NodeID, C)>],
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+        context: &mut SearchContext<C>,
+    ) -> Vec<Option<C>> {
+        if get_cost(source).is_none() {
+            return vec![None; targets.len()];
+        }
+        let chunk = self.get_chunk(source);
+
+        // the Nodes of the Chunk and the targets that can be reached without leaving the Chunk
+        let points = chunk
+            .nodes
+            .iter()
+            .map(|&id| self.nodes[id].pos)
+            .chain(
+                targets
+                    .iter()
+                    .copied()
+                    .filter(|&target| self.in_bounds(target) && self.same_chunk(source, target)),
+            )
+            .to_vec();
+        let direct = chunk.find_paths(
+            &mut context.grid,
+            source,
+            &points,
             get_cost,
-            &mut ret,
+            &self.neighborhood,
         );
-        ret
+
+        let starts = chunk
+            .nodes
+            .iter()
+            .filter_map(|&id| {
+                direct
+                    .get(&self.nodes[id].pos)
+                    .map(|path| (id, path.cost()))
+            })
+            .to_vec();
+        let node_costs = graph::dijkstra_within(&mut context.graph, &self.nodes, &starts, None);
+
+        targets
+            .iter()
+            .zip(target_nodes)
+            .map(|(target, nodes)| {
+                let via_nodes = nodes.iter().filter_map(|(id, cost)| {
+                    node_costs
+                        .get(id)
+                        .and_then(|(start_cost, _)| start_cost.checked_add(*cost))
+                });
+                direct
+                    .get(target)
+                    .map(Path::cost)
+                    .into_iter()
+                    .chain(via_nodes)
+                    .min()
+            })
+            .collect()
     }
 
     /// Notifies the `PathCache` that the Grid changed.
@@ -854,6 +2826,11 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// changes as possible into a single call to `tiles_changed` to avoid unnecessary
     /// recalculations.
     ///
+    /// This removes the index created by
+    /// [`build_distance_index`](PathCache::build_distance_index) and the hierarchy created by
+    /// [`build_contraction_hierarchy`](PathCache::build_contraction_hierarchy), which have to be
+    /// built again afterwards if they are still needed.
+    ///
     /// Side note: if anybody has a way to improve this method, open a GitHub Issue / Pull Request.
     ///
     /// ## Examples
@@ -868,8 +2845,8 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// #     [0, 0, 0, 2, 0],
     /// # ];
     /// # let (width, height) = (grid[0].len(), grid.len());
-    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-    /// #     move |(x, y)| [1, 10, -1][grid[y][x]]
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
     /// # }
     /// let mut pathfinding: PathCache<_> = // ...
     /// # PathCache::new(
@@ -903,17 +2880,21 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// let path = pathfinding.find_path(start, goal, cost_fn(&grid));
     /// assert!(path.is_some());
     /// ```
-    pub fn tiles_changed<F: Sync + Fn(Point) -> isize>(&mut self, tiles: &[Point], get_cost: F) {
+    pub fn tiles_changed<F: Sync + Fn(Point) -> Option<C>>(
+        &mut self,
+        tiles: &[Point],
+        get_cost: F,
+    ) {
         #[cfg(feature = "parallel")]
         {
-            self.tiles_changed_internal::<F, fn(Point) -> isize>(
+            self.tiles_changed_internal::<F, fn(Point) -> Option<C>>(
                 tiles,
                 CostFnWrapper::Parallel(get_cost),
             );
         }
         #[cfg(not(feature = "parallel"))]
         {
-            self.tiles_changed_internal::<fn(Point) -> isize, F>(
+            self.tiles_changed_internal::<fn(Point) -> Option<C>, F>(
                 tiles,
                 CostFnWrapper::Sequential(get_cost, PhantomData),
             );
@@ -925,12 +2906,12 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// Equivalent to `tiles_changed` if `parallel` feature is disabled.
     ///
     /// Note that this is _**way**_ slower than `tiles_changed` with `parallel`.
-    pub fn tiles_changed_with_fn_mut<F: FnMut(Point) -> isize>(
+    pub fn tiles_changed_with_fn_mut<F: FnMut(Point) -> Option<C>>(
         &mut self,
         tiles: &[Point],
         get_cost: F,
     ) {
-        self.tiles_changed_internal::<fn(Point) -> isize, F>(
+        self.tiles_changed_internal::<fn(Point) -> Option<C>, F>(
             tiles,
             CostFnWrapper::Sequential(get_cost, PhantomData),
         );
@@ -941,32 +2922,126 @@ impl<N: Neighborhood + Sync> PathCache<N> {
         tiles: &[Point],
         mut get_cost: CostFnWrapper<F1, F2>,
     ) where
-        F1: Sync + Fn(Point) -> isize,
-        F2: FnMut(Point) -> isize,
+        F1: Sync + Fn(Point) -> Option<C>,
+        F2: FnMut(Point) -> Option<C>,
     {
-        let size = self.config.chunk_size;
-
         #[cfg(feature = "log")]
         let (outer_timer, timer) = (std::time::Instant::now(), std::time::Instant::now());
 
+        self.invalidate_indices();
+
+        // the bound is only lowered, since raising it would need a scan of the whole Grid
+        let changed_min = tiles.iter().filter_map(|&p| get_cost.as_fn_mut()(p)).min();
+        self.min_cost = self.min_cost.into_iter().chain(changed_min).min();
+
         let mut dirty = PointMap::default();
         for &p in tiles {
             let chunk_pos = self.get_chunk_pos(p);
             dirty.entry(chunk_pos).or_insert_with(Vec::new).push(p);
         }
 
-        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-        enum Renew {
-            No,
-            Inner,
-            Corner(Point),
-            All,
+        let renew = self.sides_to_renew(&dirty);
+
+        re_trace!("establish renew", timer);
+
+        self.remove_renewed_nodes(&renew);
+
+        re_trace!("remove nodes of sides in renew", timer);
+
+        let mut changed_nodes = NodeIDSet::default();
+
+        if let Some(lru) = &self.path_lru {
+            lru.invalidate(|pos| dirty.contains_key(&self.get_chunk_pos(pos)));
+        }
+
+        // remove all Paths in changed chunks
+        for cp in dirty.keys() {
+            for id in self.chunks[self.get_chunk_index(*cp)].nodes.iter() {
+                self.nodes[*id].edges.clear();
+            }
+        }
+
+        self.recreate_sides(&renew, &dirty, get_cost.as_fn_mut(), &mut changed_nodes);
+
+        re_trace!("recreates sides in renew", timer);
+
+        match get_cost {
+            CostFnWrapper::Sequential(mut get_cost, _) => {
+                for cp in dirty.keys() {
+                    let chunk_index = self.get_chunk_index(*cp);
+                    let chunk = &mut self.chunks[chunk_index];
+                    let nodes = chunk.nodes.iter().copied().to_vec();
+
+                    for node in nodes.iter() {
+                        changed_nodes.insert(*node);
+                    }
+
+                    chunk.nodes.clear();
+                    chunk.add_nodes(
+                        &nodes,
+                        &mut get_cost,
+                        &self.neighborhood,
+                        &mut self.nodes,
+                        &self.config,
+                    );
+                }
+                re_trace!("recreate Paths", timer);
+            }
+            #[cfg(feature = "parallel")]
+            CostFnWrapper::Parallel(get_cost) => {
+                use rayon::prelude::*;
+                let dirty_indices: hashbrown::HashSet<usize> = dirty
+                    .keys()
+                    .map(|(x, y)| self.get_chunk_index((*x, *y)))
+                    .collect();
+
+                let paths: Vec<_> = {
+                    let neighborhood = &self.neighborhood;
+                    let all_nodes = &self.nodes;
+                    let config = &self.config;
+
+                    self.chunks
+                        .par_iter()
+                        .enumerate()
+                        .filter(|(chunk_index, _)| dirty_indices.contains(chunk_index))
+                        .map(|(_, chunk)| {
+                            chunk.connect_nodes_parallel(&get_cost, neighborhood, all_nodes, config)
+                        })
+                        .collect()
+                };
+
+                re_trace!("get paths", timer);
+
+                for (id, other_id, path) in paths.into_iter().flatten() {
+                    self.nodes.add_edge(id, other_id, path);
+                }
+
+                for chunk_index in dirty_indices.iter() {
+                    for node in self.chunks[*chunk_index].nodes.iter() {
+                        changed_nodes.insert(*node);
+                    }
+                }
+
+                re_trace!("update edges", timer);
+            }
         }
 
+        // re-establish cross-chunk connections
+        self.connect_nodes(Some(changed_nodes));
+
+        re_trace!("connect nodes", timer);
+        re_trace!("total time", outer_timer);
+    }
+
+    /// Finds the sides of every Chunk that have to be renewed, because they contain changed Tiles
+    /// or are next to a side that does
+    fn sides_to_renew(&self, dirty: &PointMap<Vec<Point>>) -> PointMap<[Renew; 4]> {
+        let size = self.config.chunk_size;
+
         // map of chunk_pos => array: [Renew; 4] where array[side] says if chunk[side] needs to be renewed
         let mut renew = PointMap::default();
 
-        for (&cp, positions) in dirty.iter() {
+        for (&cp, positions) in dirty {
             let chunk = self.get_chunk(cp);
             // for every changed tile in the chunk
             for &p in positions {
@@ -1005,11 +3080,12 @@ impl<N: Neighborhood + Sync> PathCache<N> {
             }
         }
 
-        re_trace!("establish renew", timer);
-
-        // remove all nodes of sides in renew
+        renew
+    }
 
-        for (&cp, sides) in renew.iter() {
+    /// Removes the Nodes on all sides that are renewed
+    fn remove_renewed_nodes(&mut self, renew: &PointMap<[Renew; 4]>) {
+        for (&cp, sides) in renew {
             let chunk_index = self.get_chunk_index(cp);
             let chunk = &self.chunks[chunk_index];
             let removed = chunk
@@ -1035,151 +3111,256 @@ impl<N: Neighborhood + Sync> PathCache<N> {
                 self.nodes.remove_node(id);
             }
         }
+    }
 
-        re_trace!("remove nodes of sides in renew", timer);
-
-        let mut changed_nodes = NodeIDSet::default();
-
-        // remove all Paths in changed chunks
-        for cp in dirty.keys() {
-            let chunk_index = self.get_chunk_index(*cp);
+    /// Creates the Nodes on all sides that are renewed. Chunks that are not `dirty` are connected
+    /// to their new Nodes right away, and those Nodes are added to `changed_nodes`.
+    fn recreate_sides(
+        &mut self,
+        renew: &PointMap<[Renew; 4]>,
+        dirty: &PointMap<Vec<Point>>,
+        mut get_cost: &mut dyn FnMut(Point) -> Option<C>,
+        changed_nodes: &mut NodeIDSet,
+    ) {
+        for (&cp, sides) in renew {
+            let mut candidates = PointSet::default();
+            let chunk_index = self.get_chunk_index(cp);
             let chunk = &self.chunks[chunk_index];
-            for id in chunk.nodes.iter() {
-                self.nodes[*id].edges.clear();
-            }
-        }
-
-        {
-            let mut get_cost: &mut dyn FnMut(Point) -> isize = match &mut get_cost {
-                CostFnWrapper::Sequential(get_cost, _) => get_cost,
-                #[cfg(feature = "parallel")]
-                CostFnWrapper::Parallel(get_cost) => get_cost,
-            };
 
-            // recreate sides in renew
-            for (&cp, sides) in renew.iter() {
-                let mut candidates = PointSet::default();
-                let chunk_index = self.get_chunk_index(cp);
-                let chunk = &self.chunks[chunk_index];
-
-                for dir in Dir::all() {
-                    if sides[dir.num()] != Renew::No {
-                        chunk.calculate_side_nodes(
-                            dir,
-                            (self.width, self.height),
-                            &mut get_cost,
-                            self.config,
-                            &mut candidates,
-                        );
-                    }
+            for dir in Dir::all() {
+                if sides[dir.num()] != Renew::No {
+                    chunk.calculate_side_nodes(
+                        dir,
+                        (self.width, self.height),
+                        &mut get_cost,
+                        self.config,
+                        &mut candidates,
+                    );
                 }
+            }
 
-                // Only include nodes that aren't already part of the map
-                candidates.retain(|&pos| self.nodes.id_at(pos).is_none());
+            // Only include nodes that aren't already part of the map
+            candidates.retain(|&pos| self.nodes.id_at(pos).is_none());
 
-                if candidates.is_empty() {
-                    continue;
-                }
+            if candidates.is_empty() {
+                continue;
+            }
 
-                let all_nodes = &mut self.nodes;
-                let nodes = candidates
-                    .into_iter()
-                    .map(|p| all_nodes.add_node(p, get_cost(p) as usize))
-                    .to_vec();
+            let all_nodes = &mut self.nodes;
+            let nodes = candidates
+                .into_iter()
+                .map(|p| {
+                    let cost =
+                        get_cost(p).expect("Internal Error #3 in PathCache. Please report this");
+                    all_nodes.add_node(p, cost)
+                })
+                .to_vec();
 
-                let chunk = &mut self.chunks[chunk_index];
-                if !dirty.contains_key(&cp) {
-                    for node in nodes.iter() {
-                        changed_nodes.insert(*node);
-                    }
-                    chunk.add_nodes(
-                        &nodes,
-                        &mut get_cost,
-                        &self.neighborhood,
-                        &mut self.nodes,
-                        &self.config,
-                    );
-                } else {
-                    for id in nodes {
-                        chunk.nodes.insert(id);
-                    }
+            let chunk = &mut self.chunks[chunk_index];
+            if !dirty.contains_key(&cp) {
+                for node in nodes.iter() {
+                    changed_nodes.insert(*node);
+                }
+                chunk.add_nodes(
+                    &nodes,
+                    &mut get_cost,
+                    &self.neighborhood,
+                    &mut self.nodes,
+                    &self.config,
+                );
+            } else {
+                for id in nodes {
+                    chunk.nodes.insert(id);
                 }
             }
         }
+    }
 
-        re_trace!("recreates sides in renew", timer);
+    /// Discards the indices over the abstract Graph, since its Node IDs and the costs between them
+    /// are about to change
+    fn invalidate_indices(&mut self) {
+        self.distance_table.invalidate();
+        self.contraction.invalidate();
+        self.landmarks.invalidate();
+    }
+
+    /// The landmarks from [`PathCacheConfig::landmarks`], which are selected and calculated when
+    /// a search first needs them after a change
+    fn landmarks(&self) -> Option<&Landmarks<C>> {
+        self.landmarks.get_or_build(|| {
+            let mut buffers = SearchBuffers::default();
+            Landmarks::new(&mut buffers, &self.nodes, self.config.landmarks)
+        })
+    }
+
+    /// Precomputes the cost of the cheapest Path between every pair of Nodes in the abstract
+    /// Graph.
+    ///
+    /// While the index exists, [`find_path`](PathCache::find_path) and
+    /// [`estimate_cost`](PathCache::estimate_cost) look up the Path between the Nodes of the
+    /// start and the goal instead of searching the abstract Graph, which makes that part of the
+    /// search take constant time.
+    ///
+    /// The index needs Memory that grows with the square of the number of Nodes (see
+    /// [`memory_usage`](PathCache::memory_usage)), so it is only useful for Grids with up to a
+    /// few thousand Nodes. With the `parallel` feature, it is built on multiple threads.
+    ///
+    /// [`tiles_changed`](PathCache::tiles_changed) discards the index, since it would no longer
+    /// be correct, and the next query that needs it rebuilds it. That way, a batch of changes
+    /// only causes a single rebuild. [`clear_distance_index`](PathCache::clear_distance_index)
+    /// stops using the index.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let mut pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// pathfinding.build_distance_index();
+    /// assert!(pathfinding.has_distance_index());
+    ///
+    /// let path = pathfinding.find_path((0, 0), (4, 4), cost_fn(&grid));
+    /// assert_eq!(path.unwrap().cost(), 12);
+    ///
+    /// grid[1][2] = 0;
+    /// pathfinding.tiles_changed(&[(2, 1)], cost_fn(&grid));
+    ///
+    /// // rebuilt for this query
+    /// let path = pathfinding.find_path((0, 0), (2, 0), cost_fn(&grid));
+    /// assert_eq!(path.unwrap().cost(), 10);
+    ///
+    /// pathfinding.clear_distance_index();
+    /// assert!(!pathfinding.has_distance_index());
+    /// ```
+    pub fn build_distance_index(&mut self) {
+        #[cfg(feature = "log")]
+        let timer = std::time::Instant::now();
 
-        match get_cost {
-            CostFnWrapper::Sequential(mut get_cost, _) => {
-                for cp in dirty.keys() {
-                    let chunk_index = self.get_chunk_index(*cp);
-                    let chunk = &mut self.chunks[chunk_index];
-                    let nodes = chunk.nodes.iter().copied().to_vec();
+        self.distance_table.set(DistanceTable::new(&self.nodes));
 
-                    for node in nodes.iter() {
-                        changed_nodes.insert(*node);
-                    }
+        re_trace!("build_distance_index", timer);
+    }
 
-                    chunk.nodes.clear();
-                    chunk.add_nodes(
-                        &nodes,
-                        &mut get_cost,
-                        &self.neighborhood,
-                        &mut self.nodes,
-                        &self.config,
-                    );
-                }
-                re_trace!("recreate Paths", timer);
-            }
-            #[cfg(feature = "parallel")]
-            CostFnWrapper::Parallel(get_cost) => {
-                use rayon::prelude::*;
-                let dirty_indices: hashbrown::HashSet<usize> = dirty
-                    .keys()
-                    .map(|(x, y)| self.get_chunk_index((*x, *y)))
-                    .collect();
+    /// Returns `true` if the index created by
+    /// [`build_distance_index`](PathCache::build_distance_index) is used, even if it still has
+    /// to be rebuilt after [`tiles_changed`](PathCache::tiles_changed)
+    pub fn has_distance_index(&self) -> bool {
+        self.distance_table.is_enabled()
+    }
 
-                let paths: Vec<_> = {
-                    let neighborhood = &self.neighborhood;
-                    let all_nodes = &self.nodes;
-                    let cache_paths = self.config.cache_paths;
+    /// Removes the index created by [`build_distance_index`](PathCache::build_distance_index)
+    /// to free its Memory
+    pub fn clear_distance_index(&mut self) {
+        self.distance_table.clear();
+    }
 
-                    self.chunks
-                        .par_iter()
-                        .enumerate()
-                        .filter(|(chunk_index, _)| dirty_indices.contains(chunk_index))
-                        .map(|(_, chunk)| {
-                            chunk.connect_nodes_parallel(
-                                &get_cost,
-                                neighborhood,
-                                all_nodes,
-                                cache_paths,
-                            )
-                        })
-                        .collect()
-                };
+    /// The index of [`build_distance_index`](PathCache::build_distance_index), which is rebuilt
+    /// here if the Graph changed since it was last used
+    fn distance_table(&self) -> Option<&DistanceTable<C>> {
+        self.distance_table
+            .get_or_build(|| DistanceTable::new(&self.nodes))
+    }
 
-                re_trace!("get paths", timer);
+    /// Builds a [Contraction Hierarchy](https://en.wikipedia.org/wiki/Contraction_hierarchies)
+    /// over the abstract Graph.
+    ///
+    /// While the hierarchy exists, [`find_path`](PathCache::find_path) and
+    /// [`estimate_cost`](PathCache::estimate_cost) use it to find the Path between the Nodes of
+    /// the start and the goal, which only has to look at a tiny part of the abstract Graph instead
+    /// of the many Nodes that A* expands on large Grids. Unlike
+    /// [`build_distance_index`](PathCache::build_distance_index), the Memory it needs only grows
+    /// a little faster than the number of Nodes.
+    ///
+    /// Building the hierarchy takes a lot longer than a single search (several seconds for tens
+    /// of thousands of Nodes), so it is only worth it for Grids that rarely change.
+    /// [`tiles_changed`](PathCache::tiles_changed) discards the hierarchy, and the next query
+    /// that needs it rebuilds it, so that a batch of changes only causes a single rebuild.
+    /// [`PathQuery`] searches the abstract Graph with A* until then, to stay within its budget.
+    ///
+    /// ## Examples
+    /// Basic usage:
+    /// ```
+    /// # use hierarchical_pathfinding::prelude::*;
+    /// # let mut grid = [
+    /// #     [0, 2, 0, 0, 0],
+    /// #     [0, 2, 2, 2, 2],
+    /// #     [0, 1, 0, 0, 0],
+    /// #     [0, 1, 0, 2, 0],
+    /// #     [0, 0, 0, 2, 0],
+    /// # ];
+    /// # let (width, height) = (grid[0].len(), grid.len());
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+    /// # }
+    /// let mut pathfinding: PathCache<_> = // ...
+    /// # PathCache::new(
+    /// #     (width, height),
+    /// #     cost_fn(&grid),
+    /// #     ManhattanNeighborhood::new(width, height),
+    /// #     PathCacheConfig::with_chunk_size(3),
+    /// # );
+    ///
+    /// pathfinding.build_contraction_hierarchy();
+    /// assert!(pathfinding.has_contraction_hierarchy());
+    ///
+    /// let path = pathfinding.find_path((0, 0), (4, 4), cost_fn(&grid));
+    /// assert_eq!(path.unwrap().cost(), 12);
+    ///
+    /// grid[1][2] = 0;
+    /// pathfinding.tiles_changed(&[(2, 1)], cost_fn(&grid));
+    ///
+    /// // rebuilt for this query
+    /// assert!(pathfinding.has_contraction_hierarchy());
+    /// let path = pathfinding.find_path((0, 0), (2, 0), cost_fn(&grid));
+    /// assert!(path.is_some());
+    ///
+    /// pathfinding.clear_contraction_hierarchy();
+    /// assert!(!pathfinding.has_contraction_hierarchy());
+    /// ```
+    pub fn build_contraction_hierarchy(&mut self) {
+        #[cfg(feature = "log")]
+        let timer = std::time::Instant::now();
 
-                for (id, other_id, path) in paths.into_iter().flatten() {
-                    self.nodes.add_edge(id, other_id, path);
-                }
+        self.contraction.set(ContractionHierarchy::new(&self.nodes));
 
-                for chunk_index in dirty_indices.iter() {
-                    for node in self.chunks[*chunk_index].nodes.iter() {
-                        changed_nodes.insert(*node);
-                    }
-                }
+        re_trace!("build_contraction_hierarchy", timer);
+    }
 
-                re_trace!("update edges", timer);
-            }
-        }
+    /// Returns `true` if the hierarchy created by
+    /// [`build_contraction_hierarchy`](PathCache::build_contraction_hierarchy) is used, even if
+    /// it still has to be rebuilt after [`tiles_changed`](PathCache::tiles_changed)
+    pub fn has_contraction_hierarchy(&self) -> bool {
+        self.contraction.is_enabled()
+    }
 
-        // re-establish cross-chunk connections
-        self.connect_nodes(Some(changed_nodes));
+    /// Removes the hierarchy created by
+    /// [`build_contraction_hierarchy`](PathCache::build_contraction_hierarchy) to free its Memory
+    pub fn clear_contraction_hierarchy(&mut self) {
+        self.contraction.clear();
+    }
 
-        re_trace!("connect nodes", timer);
-        re_trace!("total time", outer_timer);
+    /// The hierarchy of [`build_contraction_hierarchy`](PathCache::build_contraction_hierarchy),
+    /// which is rebuilt here if the Graph changed since it was last used
+    fn contraction(&self) -> Option<&ContractionHierarchy<C>> {
+        self.contraction
+            .get_or_build(|| ContractionHierarchy::new(&self.nodes))
     }
 
     /// Allows for debugging and visualizing the `PathCache`
@@ -1199,8 +3380,8 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     /// #     [0, 0, 0, 2, 0],
     /// # ];
     /// # let (width, height) = (grid[0].len(), grid.len());
-    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
-    /// #     move |(x, y)| [1, 10, -1][grid[y][x]]
+    /// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    /// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
     /// # }
     /// let pathfinding: PathCache<_> = // ...
     /// # PathCache::new(
@@ -1226,7 +3407,7 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     ///     }
     /// }
     /// ```
-    pub fn inspect_nodes(&self) -> CacheInspector<N> {
+    pub fn inspect_nodes(&self) -> CacheInspector<N, C> {
         CacheInspector::new(self)
     }
 
@@ -1248,6 +3429,19 @@ impl<N: Neighborhood + Sync> PathCache<N> {
         point.0 < self.width && point.1 < self.height
     }
 
+    /// Panics with a message that names the `point` if it is outside of the Grid
+    #[track_caller]
+    fn assert_in_bounds(&self, name: &str, point: Point) {
+        assert!(
+            self.in_bounds(point),
+            "{} {:?} is out of bounds of a grid of size {}x{}",
+            name,
+            point,
+            self.width,
+            self.height
+        );
+    }
+
     fn get_chunk_pos(&self, point: Point) -> Point {
         let size = self.config.chunk_size;
         ((point.0 / size) * size, (point.1 / size) * size)
@@ -1269,10 +3463,35 @@ impl<N: Neighborhood + Sync> PathCache<N> {
         a.0 / size == b.0 / size && a.1 / size == b.1 / size
     }
 
+    /// Wraps a fully calculated Path into an [`AbstractPath`] on this Grid
+    fn known_path(&self, path: Path<Point, C>) -> AbstractPath<N, C> {
+        AbstractPath::from_known_path(self.neighborhood.clone(), (self.width, self.height), path)
+    }
+
     fn node_at(&self, pos: Point) -> Option<NodeID> {
         self.nodes.id_at(pos)
     }
 
+    /// Reports how much Memory this `PathCache` uses.
+    ///
+    /// See [`MemoryReport`] for details and an example.
+    pub fn memory_usage(&self) -> MemoryReport {
+        let mut report = MemoryReport::new(&self.chunks, &self.nodes, &self.tags);
+        if let Some(lru) = &self.path_lru {
+            report.lru_path_bytes = lru.bytes();
+        }
+        if let Some(table) = self.distance_table.get() {
+            report.distance_index_bytes = table.heap_size();
+        }
+        if let Some(contraction) = self.contraction.get() {
+            report.contraction_hierarchy_bytes = contraction.heap_size();
+        }
+        if let Some(landmarks) = self.landmarks.get() {
+            report.landmark_bytes = landmarks.heap_size();
+        }
+        report
+    }
+
     /// Returns the config used to create this `PathCache`
     pub fn config(&self) -> &PathCacheConfig {
         &self.config
@@ -1281,161 +3500,245 @@ impl<N: Neighborhood + Sync> PathCache<N> {
     fn find_nearest_node(
         &self,
         pos: Point,
-        get_cost: impl FnMut(Point) -> isize,
+        get_cost: impl FnMut(Point) -> Option<C>,
         reverse: bool,
-    ) -> Option<(NodeID, Option<Path<Point>>)> {
+        context: &mut SearchContext<C>,
+    ) -> Option<(NodeID, Option<Path<Point, C>>)> {
         if let Some(id) = self.node_at(pos) {
             return Some((id, None));
         }
         self.get_chunk(pos)
-            .nearest_node(&self.nodes, pos, get_cost, &self.neighborhood, reverse)
+            .nearest_node(
+                &mut context.grid,
+                &self.nodes,
+                pos,
+                get_cost,
+                &self.neighborhood,
+                self.min_cost(),
+                reverse,
+            )
             .map(|(id, path)| (id, Some(path)))
     }
 
+    /// Finds the Path between two Nodes, using the distance index or the Contraction Hierarchy
+    /// if they exist, and the landmarks otherwise
+    fn graph_path(
+        &self,
+        start_id: NodeID,
+        goal_id: NodeID,
+        context: &mut SearchContext<C>,
+    ) -> Option<Path<NodeID, C>> {
+        if let Some(table) = self.distance_table() {
+            return table.path(start_id, goal_id);
+        }
+        if let Some(contraction) = self.contraction() {
+            return contraction.path(start_id, goal_id);
+        }
+        graph::a_star_search(
+            &mut context.graph,
+            &self.nodes,
+            start_id,
+            goal_id,
+            &self.neighborhood,
+            self.landmarks(),
+            self.heuristic(),
+        )
+    }
+
     fn grid_a_star(
         &self,
         start: Point,
         goal: Point,
-        get_cost: impl FnMut(Point) -> isize,
-    ) -> Option<Path<Point>> {
-        let heuristic = self.neighborhood.heuristic(start, goal);
-        let max_heuristic = self
-            .neighborhood
-            .heuristic((0, 0), (self.width - 1, self.height - 1));
-        let max_size = self.width * self.height;
-        let size_hint = heuristic as f32 / max_heuristic as f32 * max_size as f32;
-
+        get_cost: impl FnMut(Point) -> Option<C>,
+        context: &mut SearchContext<C>,
+    ) -> Option<Path<Point, C>> {
         grid::a_star_search(
+            &mut context.grid,
             &self.neighborhood,
-            |_| true,
+            (0, 0),
+            (self.width, self.height),
             get_cost,
             start,
             goal,
-            size_hint as usize,
+            self.heuristic(),
         )
     }
 
-    fn resolve_paths(
-        &self,
-        start: Point,
-        start_path: Option<Path<Point>>,
-        goal_data: &mut [(Point, NodeID, Option<Path<Point>>)],
-        paths: &NodeIDMap<Path<NodeID>>,
-        mut get_cost: impl FnMut(Point) -> isize,
-        out: &mut PointMap<AbstractPath<N>>,
-    ) {
-        // a map for direct paths from the start to other nodes in the same chunk as start.
-        // see `start_path` calculation below
-        let mut start_path_map = PointMap::default();
-
-        for (goal, goal_id, goal_path) in goal_data {
-            let path = if let Some(path) = paths.get(goal_id) {
-                path
-            } else {
-                continue;
-            };
+    /// The heuristic for searches over the whole Grid or the graph, with the
+    /// [`heuristic_weight`](PathCacheConfig::heuristic_weight)
+    fn heuristic(&self) -> Heuristic<C> {
+        Heuristic {
+            min_cost: self.min_cost(),
+            weight: self.config.heuristic_weight,
+        }
+    }
 
-            if path.len() == 1
-                || (self.config.a_star_fallback && path.cost() < 2 * self.config.chunk_size)
-            {
-                // len == 1: start_id == goal_id
-                let res = self
-                    .grid_a_star(start, *goal, &mut get_cost)
-                    .map(|path| AbstractPath::from_known_path(self.neighborhood.clone(), path))
-                    .expect("Inconsistency in Pathfinding");
+    /// The lowest cost of any Tile, for searches that have to find the cheapest Path
+    fn min_cost(&self) -> C {
+        self.min_cost.unwrap_or(C::ZERO)
+    }
 
-                out.insert(*goal, res);
-                continue;
-            }
+    /// Checks if the Path along `graph_path` should be replaced by an A* search on the Grid
+    fn needs_grid_search(&self, graph_path: &Path<NodeID, C>) -> bool {
+        // len == 1: start_id == goal_id
+        graph_path.len() == 1
+            || (self.config.a_star_fallback
+                && graph_path.cost() < C::from_usize(2 * self.config.chunk_size))
+    }
 
-            let path = path.iter().copied().to_vec();
-            let mut path = path.as_slice();
+    /// Finds the parts that make up the Path from `start` to `goal`: the Path from `start` to
+    /// the first Node, the Nodes along `graph_path` and the Path from the last Node to `goal`.
+    ///
+    /// `start_path_map` holds direct Paths from `start` to other Nodes in the same Chunk and can
+    /// be shared between all goals with the same `start`.
+    #[allow(clippy::too_many_arguments)]
+    fn resolve_path<'a>(
+        &self,
+        start: Point,
+        mut start_path: Option<&'a Path<Point, C>>,
+        goal: Point,
+        graph_path: &Path<NodeID, C>,
+        mut goal_path: Option<Path<Point, C>>,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
+        context: &mut SearchContext<C>,
+        start_path_map: &'a mut PointMap<Path<Point, C>>,
+    ) -> PathParts<C> {
+        if self.needs_grid_search(graph_path) {
+            let path = self
+                .grid_a_star(start, goal, &mut get_cost, context)
+                .expect("Inconsistency in Pathfinding");
+            return PathParts::known(path);
+        }
 
-            let mut start_path = start_path.as_ref();
-            if start_path.is_none() {
-                // start is itself a node, so leave the start as is
-            } else {
-                // start_path connects start to *some* node in the chunk, which is not necessarily
-                // the best node to start from.
-                // => find a direct path to the node furthest into the path that is still in the
-                //    same chunk as start
-                let candidate = path
-                    .iter()
-                    .map(|&id| self.nodes[id].pos)
-                    .chain(std::iter::once(*goal))
-                    .enumerate()
-                    .skip(1) // skip the current candidate
-                    .take_while(|(_, pos)| self.same_chunk(start, *pos))
-                    .last();
-
-                if let Some((index, next_pos)) = candidate {
-                    let new_start_path = start_path_map.entry(next_pos).or_insert_with(|| {
-                        // this path is guaranteed to be within this chunk, because all nodes
-                        // between start and candidate are in the same chunk as start
-                        // and paths between nodes are either fully within a chunk or the
-                        // nodes are in different chunks
-                        self.get_chunk(start)
-                            .find_path(start, next_pos, &mut get_cost, &self.neighborhood)
-                            .expect("Inconsistency in Pathfinding")
-                    });
-
-                    if next_pos == *goal {
-                        out.insert(
-                            *goal,
-                            AbstractPath::from_known_path(
-                                self.neighborhood.clone(),
-                                new_start_path.clone(),
-                            ),
-                        );
-                        continue;
-                    }
+        let path = graph_path.iter().copied().to_vec();
+        let mut path = path.as_slice();
+
+        if start_path.is_none() {
+            // start is itself a node, so leave the start as is
+        } else {
+            // start_path connects start to *some* node in the chunk, which is not necessarily
+            // the best node to start from.
+            // => find a direct path to the node furthest into the path that is still in the
+            //    same chunk as start
+            let candidate = path
+                .iter()
+                .map(|&id| self.nodes[id].pos)
+                .chain(std::iter::once(goal))
+                .enumerate()
+                .skip(1) // skip the current candidate
+                .take_while(|(_, pos)| self.same_chunk(start, *pos))
+                .last();
+
+            if let Some((index, next_pos)) = candidate {
+                let new_start_path = start_path_map.entry(next_pos).or_insert_with(|| {
+                    // this path is guaranteed to be within this chunk, because all nodes
+                    // between start and candidate are in the same chunk as start
+                    // and paths between nodes are either fully within a chunk or the
+                    // nodes are in different chunks
+                    self.get_chunk(start)
+                        .find_path(
+                            &mut context.grid,
+                            start,
+                            next_pos,
+                            &mut get_cost,
+                            &self.neighborhood,
+                            self.min_cost(),
+                        )
+                        .expect("Inconsistency in Pathfinding")
+                });
 
-                    start_path = Some(new_start_path);
-                    path = &path[index..];
+                if next_pos == goal {
+                    return PathParts::known(new_start_path.clone());
                 }
-            }
 
-            let mut goal_path = goal_path.take();
-            if goal_path.is_none() {
-                // goal is itself a node, so leave the goal as is
-            } else {
-                // same as with start_path, but for the goal
-                let candidate = path
-                    .iter()
-                    .enumerate()
-                    .rev()
-                    .skip(1) // skip the current candidate
-                    .take_while(|(_, &id)| self.same_chunk(*goal, self.nodes[id].pos))
-                    .last();
-
-                if let Some((index, id)) = candidate {
-                    let previous_pos = self.nodes[*id].pos;
-                    let new_goal_path = self
-                        .get_chunk(*goal)
-                        .find_path(previous_pos, *goal, &mut get_cost, &self.neighborhood)
-                        .expect("Inconsistency in Pathfinding");
-
-                    goal_path = Some(new_goal_path);
-                    path = &path[..=index];
-                }
+                start_path = Some(new_start_path);
+                path = &path[index..];
             }
+        }
 
-            let mut final_path = AbstractPath::new(self.neighborhood.clone(), start);
+        if goal_path.is_none() {
+            // goal is itself a node, so leave the goal as is
+        } else {
+            // same as with start_path, but for the goal
+            let candidate = path
+                .iter()
+                .enumerate()
+                .rev()
+                .skip(1) // skip the current candidate
+                .take_while(|(_, &id)| self.same_chunk(goal, self.nodes[id].pos))
+                .last();
+
+            if let Some((index, id)) = candidate {
+                let previous_pos = self.nodes[*id].pos;
+                let new_goal_path = self
+                    .get_chunk(goal)
+                    .find_path(
+                        &mut context.grid,
+                        previous_pos,
+                        goal,
+                        &mut get_cost,
+                        &self.neighborhood,
+                        self.min_cost(),
+                    )
+                    .expect("Inconsistency in Pathfinding");
 
-            if let Some(path) = start_path {
-                final_path.add_path(path.clone());
+                goal_path = Some(new_goal_path);
+                path = &path[..=index];
             }
+        }
 
-            for (a, b) in path.windows(2).map(|w| (w[0], w[1])) {
-                final_path.add_path_segment(self.nodes[a].edges[&b].clone());
-            }
+        PathParts {
+            start_path: start_path.cloned(),
+            nodes: path.to_vec(),
+            goal_path,
+        }
+    }
+
+    /// Assembles the Path from `start` out of the parts found by
+    /// [`resolve_path`](PathCache::resolve_path)
+    ///
+    /// Returns `None` if the total cost of the Path overflows.
+    fn build_path(&self, start: Point, parts: PathParts<C>) -> Option<AbstractPath<N, C>> {
+        let mut final_path = AbstractPath::new(
+            self.neighborhood.clone(),
+            (self.width, self.height),
+            self.min_cost(),
+            start,
+        );
+        if let Some(lru) = &self.path_lru {
+            final_path.set_lru(lru.handle());
+        }
+        // the parts were searched separately, so their sum still has to be checked
+        let mut total_cost = C::ZERO;
+
+        if let Some(path) = parts.start_path {
+            total_cost = total_cost.checked_add(path.cost())?;
+            final_path.add_path(path);
+        }
 
-            if let Some(path) = goal_path {
-                final_path.add_path(path);
+        for (a, b) in parts.nodes.windows(2).map(|w| (w[0], w[1])) {
+            let mut segment = self.nodes[a].edges[&b].clone();
+            if let (
+                PathSegment::Unknown {
+                    start, end, cost, ..
+                },
+                Some(lru),
+            ) = (&segment, &self.path_lru)
+            {
+                if let Some(path) = lru.get(*start, *end, *cost) {
+                    segment = PathSegment::Known(path);
+                }
             }
+            total_cost = total_cost.checked_add(segment.cost())?;
+            final_path.add_path_segment(segment);
+        }
 
-            out.insert(*goal, final_path);
+        if let Some(path) = parts.goal_path {
+            total_cost.checked_add(path.cost())?;
+            final_path.add_path(path);
         }
+
+        Some(final_path)
     }
 
     fn connect_nodes(&mut self, ids: Option<NodeIDSet>) {
@@ -1445,19 +3748,19 @@ impl<N: Neighborhood + Sync> PathCache<N> {
 
         // we iterate over ids if they exist or self.nodes otherwise, which cannot be unified
         // without allocations, so we extract the body of the loop as a function instead
-        let convert = |(id, node): (NodeID, &Node)| {
+        let convert = |(id, node): (NodeID, &Node<C>)| {
             seen.insert(id);
 
             target.clear();
             self.neighborhood.get_all_neighbors(node.pos, &mut target);
             for &other_pos in target.iter() {
                 if let Some(other_id) = self.node_at(other_pos) {
-                    if node.edges.contains_key(&other_id) || seen.contains(&other_id) {
+                    if node.edges.contains_key(other_id) || seen.contains(&other_id) {
                         continue;
                     }
                     let path = PathSegment::new(
                         Path::from_slice(&[node.pos, other_pos], node.walk_cost),
-                        self.config.cache_paths,
+                        &self.config,
                     );
                     new_paths.push((id, other_id, path));
                 }
@@ -1483,16 +3786,16 @@ impl<N: Neighborhood + Sync> PathCache<N> {
 ///
 /// Allows iteration over all Nodes and specific lookup with [`get_node`](CacheInspector::get_node)
 #[derive(Debug)]
-pub struct CacheInspector<'a, N: Neighborhood> {
-    src: &'a PathCache<N>,
-    inner: slab::Iter<'a, Node>,
+pub struct CacheInspector<'a, N: Neighborhood, C: Cost = usize> {
+    src: &'a PathCache<N, C>,
+    inner: slab::Iter<'a, Node<C>>,
 }
 
-impl<'a, N: Neighborhood> CacheInspector<'a, N> {
+impl<'a, N: Neighborhood, C: Cost> CacheInspector<'a, N, C> {
     /// Creates a new CacheInspector
     ///
     /// Same as calling [`.inspect_nodes()`](PathCache::inspect_nodes) on the cache
-    pub fn new(src: &'a PathCache<N>) -> Self {
+    pub fn new(src: &'a PathCache<N, C>) -> Self {
         CacheInspector {
             src,
             inner: src.nodes.iter(),
@@ -1502,13 +3805,13 @@ impl<'a, N: Neighborhood> CacheInspector<'a, N> {
     /// Provides the handle to a specific Node.
     ///
     /// It is recommended to use the `Iterator` implementation instead
-    pub fn get_node(&self, id: u32) -> NodeInspector<N> {
+    pub fn get_node(&self, id: u32) -> NodeInspector<N, C> {
         NodeInspector::new(self.src, id as NodeID)
     }
 }
 
-impl<'a, N: Neighborhood> Iterator for CacheInspector<'a, N> {
-    type Item = NodeInspector<'a, N>;
+impl<'a, N: Neighborhood, C: Cost> Iterator for CacheInspector<'a, N, C> {
+    type Item = NodeInspector<'a, N, C>;
     fn next(&mut self) -> Option<Self::Item> {
         self.inner
             .next()
@@ -1524,14 +3827,14 @@ impl<'a, N: Neighborhood> Iterator for CacheInspector<'a, N> {
 ///
 /// Gives basic info about the Node and an Iterator over all connected Nodes
 #[derive(Debug, Clone, Copy)]
-pub struct NodeInspector<'a, N: Neighborhood> {
-    src: &'a PathCache<N>,
-    node: &'a Node,
+pub struct NodeInspector<'a, N: Neighborhood, C: Cost = usize> {
+    src: &'a PathCache<N, C>,
+    node: &'a Node<C>,
     id: NodeID,
 }
 
-impl<'a, N: Neighborhood> NodeInspector<'a, N> {
-    fn new(src: &'a PathCache<N>, id: NodeID) -> Self {
+impl<'a, N: Neighborhood, C: Cost> NodeInspector<'a, N, C> {
+    fn new(src: &'a PathCache<N, C>, id: NodeID) -> Self {
         NodeInspector {
             src,
             node: &src.nodes[id],
@@ -1552,7 +3855,7 @@ impl<'a, N: Neighborhood> NodeInspector<'a, N> {
     }
 
     /// Provides an iterator over all connected Nodes with the Cost of the Path to that Node
-    pub fn connected(&'a self) -> impl Iterator<Item = (NodeInspector<'a, N>, Cost)> + 'a {
+    pub fn connected(&'a self) -> impl Iterator<Item = (NodeInspector<'a, N, C>, C)> + 'a {
         self.node
             .edges
             .iter()
@@ -1573,8 +3876,8 @@ mod tests {
             [0, 0, 0, 2, 0],
         ];
         let (width, height) = (grid[0].len(), grid.len());
-        fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Fn((usize, usize)) -> isize {
-            move |(x, y)| [1, 10, -1][grid[y][x]]
+        fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Fn((usize, usize)) -> Option<usize> {
+            move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
         }
         let pathfinding = PathCache::new(
             (width, height),
@@ -1598,4 +3901,197 @@ mod tests {
         let point = (0, 4);
         assert_eq!(pathfinding.get_chunk_index(point), 2);
     }
+
+    #[test]
+    fn reachable_within() {
+        use crate::search_context::SearchBuffers;
+        let (width, height) = (40, 40);
+        let cost_fn = |(x, y): (usize, usize)| match (x % 9, y % 7) {
+            (4, 0..=4) | (0..=5, 3) if x != 22 => None,
+            _ => Some(1 + (x * 3 + y) % 4),
+        };
+        let pathfinding = PathCache::new(
+            (width, height),
+            cost_fn,
+            MooreNeighborhood::new(width, height),
+            PathCacheConfig::with_chunk_size(6),
+        );
+
+        for start in [(0, 0), (20, 21), (39, 10)] {
+            for max_cost in [0, 1, 7, 30, 100, 1000] {
+                let expected = crate::grid::dijkstra_within(
+                    &mut SearchBuffers::default(),
+                    &MooreNeighborhood::new(width, height),
+                    (0, 0),
+                    (width, height),
+                    cost_fn,
+                    start,
+                    max_cost,
+                )
+                .0;
+                let reached =
+                    pathfinding.reachable_within_with_predecessors(start, max_cost, cost_fn);
+                assert_eq!(reached.len(), expected.len());
+                for (pos, (cost, previous)) in reached.iter() {
+                    assert!(*cost <= max_cost);
+                    assert_eq!(*cost, expected[pos].0);
+                    if *pos == start {
+                        assert_eq!(*previous, start);
+                    } else {
+                        let step = cost_fn(*previous).unwrap();
+                        assert_eq!(reached[previous].0 + step, *cost);
+                    }
+                }
+
+                let costs = pathfinding.reachable_within(start, max_cost, cost_fn);
+                assert_eq!(costs.len(), reached.len());
+            }
+        }
+
+        // can't start on a wall
+        assert!(pathfinding
+            .reachable_within((4, 0), 100, cost_fn)
+            .is_empty());
+    }
+
+    #[test]
+    fn distance_table() {
+        use crate::graph::{dijkstra_search, DistanceTable};
+        use crate::search_context::SearchBuffers;
+        let (width, height) = (40, 40);
+        let cost_fn = |(x, y): (usize, usize)| match (x % 9, y % 7) {
+            (4, 0..=4) | (0..=5, 3) if x != 22 => None,
+            _ => Some(1 + (x * 3 + y) % 4),
+        };
+        let pathfinding = PathCache::new(
+            (width, height),
+            cost_fn,
+            MooreNeighborhood::new(width, height),
+            PathCacheConfig::with_chunk_size(6),
+        );
+        let nodes = &pathfinding.nodes;
+        let table = DistanceTable::new(nodes);
+        let ids: Vec<_> = nodes.iter().map(|(id, _)| id).collect();
+
+        let mut buffers = SearchBuffers::default();
+        for &start in &ids {
+            let expected = dijkstra_search(&mut buffers, nodes, start, &ids, false);
+            for &goal in &ids {
+                let path = table.path(start, goal);
+                if start == goal {
+                    assert_eq!(path.unwrap().cost(), 0);
+                    continue;
+                }
+                assert_eq!(
+                    path.as_ref().map(|path| path.cost()),
+                    expected.get(&goal).map(|path| path.cost())
+                );
+                assert_eq!(
+                    table.cost(start, goal),
+                    path.as_ref().map(|path| path.cost())
+                );
+                if let Some(path) = path {
+                    assert_eq!(path[0], start);
+                    assert_eq!(path[path.len() - 1], goal);
+                    let mut cost = 0;
+                    for (a, b) in path.iter().zip(path.iter().skip(1)) {
+                        cost += nodes[*a].edges[b].cost();
+                    }
+                    assert_eq!(cost, path.cost());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn contraction_hierarchy() {
+        use crate::graph::{dijkstra_search, ContractionHierarchy};
+        use crate::search_context::SearchBuffers;
+        let (width, height) = (30, 30);
+        let cost_fn = |(x, y): (usize, usize)| match (x % 9, y % 7) {
+            (4, 0..=4) | (0..=5, 3) if x != 22 => None,
+            _ => Some(1 + (x * 7 + y * 3) % 11),
+        };
+        let pathfinding = PathCache::new(
+            (width, height),
+            cost_fn,
+            ManhattanNeighborhood::new(width, height),
+            PathCacheConfig::with_chunk_size(5),
+        );
+        let nodes = &pathfinding.nodes;
+        let hierarchy = ContractionHierarchy::new(nodes);
+        let ids: Vec<_> = nodes.iter().map(|(id, _)| id).collect();
+
+        let mut buffers = SearchBuffers::default();
+        for &start in ids.iter().step_by(4) {
+            let expected = dijkstra_search(&mut buffers, nodes, start, &ids, false);
+            for &goal in &ids {
+                let path = hierarchy.path(start, goal);
+                if start == goal {
+                    assert_eq!(path.unwrap().cost(), 0);
+                    continue;
+                }
+                assert_eq!(
+                    path.as_ref().map(|path| path.cost()),
+                    expected.get(&goal).map(|path| path.cost())
+                );
+                if let Some(path) = path {
+                    assert_eq!(path[0], start);
+                    assert_eq!(path[path.len() - 1], goal);
+                    let mut cost = 0;
+                    for (a, b) in path.iter().zip(path.iter().skip(1)) {
+                        cost += nodes[*a].edges[b].cost();
+                    }
+                    assert_eq!(cost, path.cost());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn landmarks() {
+        use crate::graph::{a_star_search, dijkstra_search, Landmarks};
+        use crate::grid::Heuristic;
+        use crate::search_context::SearchBuffers;
+        let (width, height) = (30, 30);
+        let cost_fn = |(x, y): (usize, usize)| match (x % 9, y % 7) {
+            (4, 0..=4) | (0..=5, 3) if x != 22 => None,
+            _ => Some(1 + (x * 7 + y * 3) % 11),
+        };
+        let pathfinding = PathCache::new(
+            (width, height),
+            cost_fn,
+            ManhattanNeighborhood::new(width, height),
+            PathCacheConfig::with_chunk_size(5),
+        );
+        let nodes = &pathfinding.nodes;
+        let mut buffers = SearchBuffers::default();
+        let landmarks = Landmarks::new(&mut buffers, nodes, 4);
+        assert!(landmarks.heap_size() > 0);
+        let ids: Vec<_> = nodes.iter().map(|(id, _)| id).collect();
+
+        for &start in ids.iter().step_by(3) {
+            let expected = dijkstra_search(&mut buffers, nodes, start, &ids, false);
+            for &goal in &ids {
+                let cost = expected.get(&goal).map(|path| path.cost());
+                if let Some(cost) = cost {
+                    assert!(landmarks.lower_bound(start, goal) <= cost);
+                }
+                let path = a_star_search(
+                    &mut buffers,
+                    nodes,
+                    start,
+                    goal,
+                    &pathfinding.neighborhood,
+                    Some(&landmarks),
+                    Heuristic::exact(1),
+                );
+                if start == goal {
+                    assert_eq!(path.unwrap().cost(), 0);
+                    continue;
+                }
+                assert_eq!(path.map(|path| path.cost()), cost);
+            }
+        }
+    }
 }
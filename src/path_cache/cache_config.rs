@@ -2,11 +2,16 @@
 ///
 /// Default options:
 /// ```
-/// # use hierarchical_pathfinding::PathCacheConfig;
+/// # use hierarchical_pathfinding::{GoalMode, PathCacheConfig};
 /// assert_eq!(
 ///     PathCacheConfig {
 ///         chunk_size: 8,
 ///         cache_paths: true,
+///         compact_paths: false,
+///         path_lru_budget: 0,
+///         landmarks: 0,
+///         heuristic_weight: 1.0,
+///         goal_mode: GoalMode::Exact,
 ///         a_star_fallback: true,
 ///         perfect_paths: false,
 ///     },
@@ -38,9 +43,16 @@
 ///
 /// ### Memory
 /// for 1024x1024: 100MB - 1000MB, depending on Node density on the Grid.
+/// [`PathCache::memory_usage`](crate::PathCache::memory_usage) reports the exact numbers for a
+/// specific Grid.
 ///
 /// Can be drastically reduced by setting `cache_paths` to `false`, at the expense of repeated
-/// calculations when using a Path.
+/// calculations when using a Path, or by setting `compact_paths` to `true`, at the expense of
+/// decoding the Paths when using them. `path_lru_budget` offers a middle ground to `cache_paths`
+/// by only keeping the Paths that were recently used. Each of the
+/// [`landmarks`](PathCacheConfig::landmarks) needs two costs per Node.
+// each flag is an independent setting, which an enum wouldn't describe any better
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct PathCacheConfig {
     /// The size of the individual Chunks (defaults to `8`)
@@ -54,7 +66,7 @@ pub struct PathCacheConfig {
     /// This has many different effects on the Performance and Memory:
     ///
     /// smaller chunks make calculations within a Chunk faster
-    /// - => decreased update time in tiles_changed
+    /// - => decreased update time in `tiles_changed`
     /// - => decreased time to find start and end Nodes
     ///
     /// bigger chunks lead to fewer Chunks and Nodes
@@ -79,6 +91,59 @@ pub struct PathCacheConfig {
     ///
     /// Drastically reduces Memory usage.
     pub cache_paths: bool,
+    /// `true`: store the Paths inside each Chunk as a start Point and the direction of every
+    /// step (3 bits per step instead of 16 bytes per Point).
+    ///
+    /// `false` (default): store the Paths as a list of Points.
+    ///
+    /// Only has an effect if [`cache_paths`](PathCacheConfig::cache_paths) is `true`. The Paths
+    /// are decoded when iterating over a returned Path reaches them, which is a lot cheaper than
+    /// the A* search that is required with `cache_paths` set to `false`.
+    ///
+    /// Only steps between neighboring Tiles (including diagonals) can be stored this way. Paths
+    /// with other steps, as produced by custom [`Neighborhood`](crate::neighbors::Neighborhood)s,
+    /// are stored as a list of Points instead.
+    pub compact_paths: bool,
+    /// The number of bytes that may be used to keep Paths that were resolved while iterating over
+    /// a returned Path (defaults to `0`, which disables it).
+    ///
+    /// Only has an effect if [`cache_paths`](PathCacheConfig::cache_paths) is `false`. Instead of
+    /// storing all Paths up front, a Path inside of a Chunk is stored the first time that it is
+    /// needed, so that later Paths through the same Chunk don't have to calculate it again. Once
+    /// the budget is exceeded, the least recently used Paths are discarded.
+    pub path_lru_budget: usize,
+    /// The number of landmark Nodes that are used to guide the search through the abstract Graph
+    /// (defaults to `0`, which disables them).
+    ///
+    /// The cost from and to every landmark is stored for every Node, which gives a much better
+    /// estimate of the remaining cost than the [`heuristic`](crate::neighbors::Neighborhood::heuristic)
+    /// of the Neighborhood when the Grid has a lot of walls. The landmarks are chosen to be far
+    /// apart. Their costs are calculated by the first search that needs them, and again after
+    /// [`tiles_changed`](crate::PathCache::tiles_changed), which makes that search slower. A
+    /// handful of landmarks (around `4` to `16`) is usually enough.
+    pub landmarks: usize,
+    /// The factor that the heuristic is multiplied with in the A* searches of
+    /// [`find_path`](crate::PathCache::find_path) (defaults to `1.0`).
+    ///
+    /// A weight `w` above `1.0` makes the searches head for the goal more greedily, which
+    /// visits fewer Tiles and Nodes, but the Path of each search can be up to `w` times as
+    /// expensive as the cheapest one that the search could find (e.g. `1.1` for at most 10%
+    /// more). This bound holds for any non-negative costs, since the
+    /// [`heuristic`](crate::neighbors::Neighborhood::heuristic) is scaled by the lowest cost of
+    /// any Tile before it is weighted. With [`landmarks`](PathCacheConfig::landmarks), the weight
+    /// is applied to the larger of the two lower bounds, so the same limit applies.
+    ///
+    /// The distance index and the Contraction Hierarchy always find the cheapest Paths in the
+    /// abstract Graph. Values of `1.0` or less keep the searches optimal.
+    pub heuristic_weight: f32,
+    /// Which Tiles count as reaching a goal (defaults to [`GoalMode::Exact`]).
+    ///
+    /// Used by [`find_path`](crate::PathCache::find_path),
+    /// [`find_paths`](crate::PathCache::find_paths) and
+    /// [`find_closest_goal`](crate::PathCache::find_closest_goal). Their `_with_goal_mode`
+    /// variants, like [`find_path_with_goal_mode`](crate::PathCache::find_path_with_goal_mode),
+    /// take the mode as a parameter instead.
+    pub goal_mode: GoalMode,
     /// `true` (default): When a Path is short (roughly `Length < 2 * chunk_size`), a regular
     /// A* search is performed on the Grid **after** HPA* calculated a Path to confirm the
     /// existence and length.
@@ -99,10 +164,68 @@ pub struct PathCacheConfig {
     pub perfect_paths: bool,
 }
 
+/// Which Tiles count as reaching a goal, see [`PathCacheConfig::goal_mode`] and
+/// [`PathCache::find_path_with_goal_mode`](crate::PathCache::find_path_with_goal_mode)
+///
+/// ## Examples
+/// Basic usage:
+/// ```
+/// # use hierarchical_pathfinding::prelude::*;
+/// # let mut grid = [
+/// #     [0, 2, 0, 0, 0],
+/// #     [0, 2, 2, 2, 2],
+/// #     [0, 1, 0, 0, 0],
+/// #     [0, 1, 0, 2, 0],
+/// #     [0, 0, 0, 2, 0],
+/// # ];
+/// # let (width, height) = (grid[0].len(), grid.len());
+/// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+/// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+/// # }
+/// let pathfinding = PathCache::new(
+///     (width, height),
+///     cost_fn(&grid),
+///     ManhattanNeighborhood::new(width, height),
+///     PathCacheConfig {
+///         chunk_size: 3,
+///         goal_mode: GoalMode::AdjacentToGoal,
+///         ..Default::default()
+///     },
+/// );
+///
+/// // walk up to the wall at (3, 3) to e.g. mine it
+/// let path = pathfinding.find_path((0, 4), (3, 3), cost_fn(&grid)).unwrap();
+/// assert_eq!(path.cost(), 3);
+/// let points: Vec<_> = path.collect();
+/// assert_eq!(points, [(1, 4), (2, 4), (2, 3)]);
+/// ```
+///
+/// Only [`Exact`](GoalMode::Exact) searches can be split up with
+/// [`query_path`](crate::PathCache::query_path), which panics with any other `goal_mode` in the
+/// config, since the other modes look for all goal Tiles at once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GoalMode {
+    /// The Path has to end on the goal itself.
+    ///
+    /// The goal may be solid, in which case the last step of the Path walks into it.
+    Exact,
+    /// The Path ends on a walkable neighbor of the goal (according to the
+    /// [`Neighborhood`](crate::neighbors::Neighborhood)), e.g. to attack or harvest it.
+    ///
+    /// This is the case even if the goal itself is walkable. If `start` is already next to the
+    /// goal, the Path stays on `start` with a cost of `0`.
+    AdjacentToGoal,
+    /// The Path ends on a walkable Tile within the given range of the goal, as measured by the
+    /// [`heuristic`](crate::neighbors::Neighborhood::heuristic) of the Neighborhood.
+    ///
+    /// Only Tiles that are at most this many Tiles away in both directions are considered.
+    WithinRange(usize),
+}
+
 impl PathCacheConfig {
     /// Creates a new `PathCacheConfig` with the given `chunk_size`.
     /// ```
-    /// # use hierarchical_pathfinding::PathCacheConfig;
+    /// # use hierarchical_pathfinding::{GoalMode, PathCacheConfig};
     /// let chunk_size = 123;
     /// assert_eq!(
     ///     PathCacheConfig::with_chunk_size(chunk_size),
@@ -112,6 +235,7 @@ impl PathCacheConfig {
     ///     }
     /// );
     /// ```
+    #[must_use]
     pub fn with_chunk_size(chunk_size: usize) -> Self {
         Self {
             chunk_size,
@@ -123,11 +247,16 @@ impl PathCacheConfig {
     ///
     /// Values:
     /// ```
-    /// # use hierarchical_pathfinding::PathCacheConfig;
+    /// # use hierarchical_pathfinding::{GoalMode, PathCacheConfig};
     /// assert_eq!(
     ///     PathCacheConfig {
     ///         chunk_size: 64,
     ///         cache_paths: false,
+    ///         compact_paths: false,
+    ///         path_lru_budget: 0,
+    ///         landmarks: 0,
+    ///         heuristic_weight: 1.0,
+    ///         goal_mode: GoalMode::Exact,
     ///         a_star_fallback: true,
     ///         perfect_paths: false,
     ///     },
@@ -137,6 +266,11 @@ impl PathCacheConfig {
     pub const LOW_MEM: PathCacheConfig = PathCacheConfig {
         chunk_size: 64,
         cache_paths: false,
+        compact_paths: false,
+        path_lru_budget: 0,
+        landmarks: 0,
+        heuristic_weight: 1.0,
+        goal_mode: GoalMode::Exact,
         a_star_fallback: true,
         perfect_paths: false,
     };
@@ -144,11 +278,16 @@ impl PathCacheConfig {
     ///
     /// Values:
     /// ```
-    /// # use hierarchical_pathfinding::PathCacheConfig;
+    /// # use hierarchical_pathfinding::{GoalMode, PathCacheConfig};
     /// assert_eq!(
     ///     PathCacheConfig {
     ///         chunk_size: 16,
     ///         cache_paths: true,
+    ///         compact_paths: false,
+    ///         path_lru_budget: 0,
+    ///         landmarks: 0,
+    ///         heuristic_weight: 1.0,
+    ///         goal_mode: GoalMode::Exact,
     ///         a_star_fallback: false,
     ///         perfect_paths: false,
     ///     },
@@ -158,6 +297,11 @@ impl PathCacheConfig {
     pub const HIGH_PERFORMANCE: PathCacheConfig = PathCacheConfig {
         chunk_size: 16,
         cache_paths: true,
+        compact_paths: false,
+        path_lru_budget: 0,
+        landmarks: 0,
+        heuristic_weight: 1.0,
+        goal_mode: GoalMode::Exact,
         a_star_fallback: false,
         perfect_paths: false,
     };
@@ -168,6 +312,11 @@ impl Default for PathCacheConfig {
         PathCacheConfig {
             chunk_size: 8,
             cache_paths: true,
+            compact_paths: false,
+            path_lru_budget: 0,
+            landmarks: 0,
+            heuristic_weight: 1.0,
+            goal_mode: GoalMode::Exact,
             a_star_fallback: true,
             perfect_paths: false,
         }
@@ -1,7 +1,9 @@
 use crate::{
+    cost::Cost,
     graph::NodeList,
     neighbors::Neighborhood,
     path::{Path, PathSegment},
+    search_context::SearchBuffers,
     *,
 };
 
@@ -14,13 +16,13 @@ pub(crate) struct Chunk {
 }
 
 impl Chunk {
-    pub fn new<N: Neighborhood>(
+    pub fn new<N: Neighborhood, C: Cost>(
         pos: Point,
         size: (usize, usize),
         total_size: (usize, usize),
-        mut get_cost: impl FnMut(Point) -> isize,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
         neighborhood: &N,
-        all_nodes: &mut NodeList,
+        all_nodes: &mut NodeList<C>,
         config: PathCacheConfig,
     ) -> Chunk {
         let mut chunk = Chunk {
@@ -47,7 +49,10 @@ impl Chunk {
 
         let nodes = candidates
             .into_iter()
-            .map(|p| all_nodes.add_node(p, get_cost(p) as usize))
+            .map(|p| {
+                let cost = get_cost(p).expect("Internal Error #4 in Chunk. Please report this");
+                all_nodes.add_node(p, cost)
+            })
             .to_vec();
 
         chunk.add_nodes(&nodes, &mut get_cost, neighborhood, all_nodes, &config);
@@ -55,11 +60,11 @@ impl Chunk {
         chunk
     }
 
-    pub fn calculate_side_nodes(
+    pub fn calculate_side_nodes<C: Cost>(
         &self,
         dir: Dir,
         total_size: (usize, usize),
-        mut get_cost: impl FnMut(Point) -> isize,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
         config: PathCacheConfig,
         candidates: &mut PointSet,
     ) {
@@ -97,11 +102,11 @@ impl Chunk {
 
         let solid = |i: usize| {
             let (c1, c2) = &costs[i];
-            *c1 < 0 || *c2 < 0
+            c1.is_none() || c2.is_none()
         };
-        let total_cost = |i: usize| {
-            let (c1, c2) = &costs[i];
-            *c1 + *c2
+        let total_cost = |i: usize| match costs[i] {
+            (Some(c1), Some(c2)) => c1.saturating_add(c2),
+            _ => unreachable!("Internal Error #7 in Chunk. Please report this"),
         };
 
         let mut has_gap = false;
@@ -171,12 +176,12 @@ impl Chunk {
         }
     }
 
-    pub fn add_nodes<N: Neighborhood>(
+    pub fn add_nodes<N: Neighborhood, C: Cost>(
         &mut self,
         to_visit: &[NodeID],
-        mut get_cost: impl FnMut(Point) -> isize,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
         neighborhood: &N,
-        all_nodes: &mut NodeList,
+        all_nodes: &mut NodeList<C>,
         config: &PathCacheConfig,
     ) {
         // first to_visit, then the rest => slicing works the same on both lists
@@ -190,28 +195,36 @@ impl Chunk {
             self.nodes.insert(id);
         }
 
+        let mut buffers = SearchBuffers::default();
+
         for (i, &id) in to_visit.iter().enumerate() {
             let point = points[i];
             let remaining = &points[(i + 1)..];
-            let paths = self.find_paths(point, remaining, &mut get_cost, neighborhood);
+            let paths =
+                self.find_paths(&mut buffers, point, remaining, &mut get_cost, neighborhood);
             for (other_pos, path) in paths {
                 let other_id = all_nodes
                     .id_at(other_pos)
                     .expect("Internal Error #5 in Chunk. Please report this");
 
-                all_nodes.add_edge(id, other_id, PathSegment::new(path, config.cache_paths));
+                all_nodes.add_edge(id, other_id, PathSegment::new(path, config));
             }
         }
     }
 
     #[cfg(feature = "parallel")]
-    pub fn connect_nodes_parallel<N: Neighborhood + Sync, F1: Fn(Point) -> isize + Sync>(
+    pub fn connect_nodes_parallel<N, C, F1>(
         &self,
         get_cost: F1,
         neighborhood: &N,
-        all_nodes: &NodeList,
-        cache_paths: bool,
-    ) -> Vec<(NodeID, NodeID, PathSegment)> {
+        all_nodes: &NodeList<C>,
+        config: &PathCacheConfig,
+    ) -> Vec<(NodeID, NodeID, PathSegment<C>)>
+    where
+        N: Neighborhood + Sync,
+        C: Cost,
+        F1: Fn(Point) -> Option<C> + Sync,
+    {
         use rayon::prelude::*;
 
         let mut ids = Vec::with_capacity(self.nodes.len());
@@ -223,129 +236,131 @@ impl Chunk {
 
         // connect every Node to every other Node
         ids.par_iter()
-            .flat_map(|&(i, id)| {
+            .map_init(SearchBuffers::default, |buffers, &(i, id)| {
                 let point = points[i];
                 let remaining = &points[(i + 1)..];
-                self.find_paths(point, remaining, &get_cost, neighborhood)
-                    .into_par_iter()
-                    .map(move |(other_pos, path)| {
-                        let other_id = all_nodes
-                            .id_at(other_pos)
-                            .expect("Internal Error #5 in Chunk. Please report this");
-
-                        (id, other_id, PathSegment::new(path, cache_paths))
-                    })
+                (
+                    id,
+                    self.find_paths(buffers, point, remaining, &get_cost, neighborhood),
+                )
+            })
+            .flat_map(|(id, paths)| {
+                paths.into_par_iter().map(move |(other_pos, path)| {
+                    let other_id = all_nodes
+                        .id_at(other_pos)
+                        .expect("Internal Error #5 in Chunk. Please report this");
+
+                    (id, other_id, PathSegment::new(path, config))
+                })
             })
             .collect()
     }
 
-    pub fn find_paths<N: Neighborhood>(
+    pub fn find_paths<N: Neighborhood, C: Cost>(
         &self,
+        buffers: &mut SearchBuffers<Point, C>,
         start: Point,
         goals: &[Point],
-        get_cost: impl FnMut(Point) -> isize,
+        get_cost: impl FnMut(Point) -> Option<C>,
         neighborhood: &N,
-    ) -> PointMap<Path<Point>> {
+    ) -> PointMap<Path<Point, C>> {
         if !self.in_chunk(start) {
             return PointMap::default();
         }
-        let heuristic = goals
-            .iter()
-            .map(|goal| neighborhood.heuristic(start, *goal))
-            .max()
-            .unwrap_or(0);
-        let max_heuristic = neighborhood.heuristic((0, 0), (self.size.0 - 1, self.size.1 - 1));
-        let max_size = self.size.0 * self.size.1;
-        let size_hint = heuristic as f32 / max_heuristic as f32 * max_size as f32;
         grid::dijkstra_search(
+            buffers,
             neighborhood,
-            |p| self.in_chunk(p),
+            self.pos,
+            self.size,
             get_cost,
             start,
             goals,
             false,
-            size_hint as usize,
         )
     }
 
-    pub fn nearest_node<N: Neighborhood>(
+    #[allow(clippy::too_many_arguments)]
+    pub fn nearest_node<N: Neighborhood, C: Cost>(
         &self,
-        all_nodes: &NodeList,
+        buffers: &mut SearchBuffers<Point, C>,
+        all_nodes: &NodeList<C>,
         start: Point,
-        mut get_cost: impl FnMut(Point) -> isize,
+        mut get_cost: impl FnMut(Point) -> Option<C>,
         neighborhood: &N,
+        min_cost: C,
         reverse: bool,
-    ) -> Option<(NodeID, Path<Point>)> {
-        let start_cost = get_cost(start);
-        if start_cost < 0 {
+    ) -> Option<(NodeID, Path<Point, C>)> {
+        let Some(start_cost) = get_cost(start) else {
             if !reverse {
                 return None;
             }
-            self.nodes.iter().copied().find_map(|id| {
-                self.find_path(all_nodes[id].pos, start, &mut get_cost, neighborhood)
-                    .map(|path| (id, path))
-            })
-        } else {
-            let mut points = Vec::with_capacity(self.nodes.len());
-            let mut map = PointMap::default();
-            let max_heuristic = neighborhood.heuristic((0, 0), (self.size.0 - 1, self.size.1 - 1));
-            let mut min_heuristic = max_heuristic;
-            for id in self.nodes.iter() {
-                let node = &all_nodes[*id];
-                let point = node.pos;
-                points.push(point);
-                map.insert(point, (*id, node.walk_cost));
-                min_heuristic = min_heuristic.min(neighborhood.heuristic(start, point));
-            }
-
-            let max_size = self.size.0 * self.size.1;
-            let size_hint = min_heuristic as f32 / max_heuristic as f32 * max_size as f32;
-            grid::dijkstra_search(
-                neighborhood,
-                |p| self.in_chunk(p),
-                get_cost,
-                start,
-                &points,
-                true,
-                size_hint as usize,
-            )
-            .into_iter()
-            .next()
-            .map(|(point, path)| {
-                let (id, node_cost) = map[&point];
-                (
-                    id,
-                    if reverse {
-                        path.reversed(start_cost as usize, node_cost)
-                    } else {
-                        path
-                    },
+            return self.nodes.iter().copied().find_map(|id| {
+                self.find_path(
+                    buffers,
+                    all_nodes[id].pos,
+                    start,
+                    &mut get_cost,
+                    neighborhood,
+                    min_cost,
                 )
-            })
+                .map(|path| (id, path))
+            });
+        };
+
+        let mut points = Vec::with_capacity(self.nodes.len());
+        let mut map = PointMap::default();
+        for id in self.nodes.iter() {
+            let node = &all_nodes[*id];
+            let point = node.pos;
+            points.push(point);
+            map.insert(point, (*id, node.walk_cost));
         }
+
+        grid::dijkstra_search(
+            buffers,
+            neighborhood,
+            self.pos,
+            self.size,
+            get_cost,
+            start,
+            &points,
+            true,
+        )
+        .into_iter()
+        .next()
+        .map(|(point, path)| {
+            let (id, node_cost) = map[&point];
+            (
+                id,
+                if reverse {
+                    path.reversed(start_cost, node_cost)
+                } else {
+                    path
+                },
+            )
+        })
     }
-    pub fn find_path<N: Neighborhood>(
+    pub fn find_path<N: Neighborhood, C: Cost>(
         &self,
+        buffers: &mut SearchBuffers<Point, C>,
         start: Point,
         goal: Point,
-        get_cost: impl FnMut(Point) -> isize,
+        get_cost: impl FnMut(Point) -> Option<C>,
         neighborhood: &N,
-    ) -> Option<Path<Point>> {
+        min_cost: C,
+    ) -> Option<Path<Point, C>> {
         if !self.in_chunk(start) || !self.in_chunk(goal) {
             return None;
         }
-        let heuristic = neighborhood.heuristic(start, goal);
-        let max_heuristic = neighborhood.heuristic((0, 0), (self.size.0 - 1, self.size.1 - 1));
-        let max_size = self.size.0 * self.size.1;
-        let size_hint = heuristic as f32 / max_heuristic as f32 * max_size as f32;
-
         grid::a_star_search(
+            buffers,
             neighborhood,
-            |p| self.in_chunk(p),
+            self.pos,
+            self.size,
             get_cost,
             start,
             goal,
-            size_hint as usize,
+            grid::Heuristic::exact(min_cost),
         )
     }
 
@@ -356,6 +371,11 @@ impl Chunk {
             && point.1 < self.bottom()
     }
 
+    /// Checks if the Chunk contains any Point of the rectangle from `min` to `max` (inclusive)
+    pub fn overlaps(&self, min: Point, max: Point) -> bool {
+        self.left() <= max.0 && min.0 < self.right() && self.top() <= max.1 && min.1 < self.bottom()
+    }
+
     pub fn at_side(&self, point: Point, side: Dir) -> bool {
         match side {
             UP => point.1 == self.top(),
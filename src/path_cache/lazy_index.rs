@@ -0,0 +1,53 @@
+use std::sync::OnceLock;
+
+/// An index over the abstract Graph that is built when a query first needs it.
+///
+/// Changes to the Graph only discard the index instead of rebuilding it right away, so that a
+/// batch of calls to [`tiles_changed`](crate::PathCache::tiles_changed) only causes a single
+/// rebuild, and none at all if no query needs the index in between.
+#[derive(Clone, Debug)]
+pub(crate) struct LazyIndex<T> {
+    /// if the index should be used at all
+    enabled: bool,
+    value: OnceLock<T>,
+}
+
+impl<T> LazyIndex<T> {
+    pub fn new(enabled: bool) -> LazyIndex<T> {
+        LazyIndex {
+            enabled,
+            value: OnceLock::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables the index with an already built `value`
+    pub fn set(&mut self, value: T) {
+        self.enabled = true;
+        self.value = OnceLock::from(value);
+    }
+
+    /// Disables the index and frees its Memory
+    pub fn clear(&mut self) {
+        self.enabled = false;
+        self.value = OnceLock::new();
+    }
+
+    /// Discards the index after the Graph changed, to be rebuilt by the next query
+    pub fn invalidate(&mut self) {
+        self.value = OnceLock::new();
+    }
+
+    /// The index if it is enabled, which is built with `build` if necessary
+    pub fn get_or_build(&self, build: impl FnOnce() -> T) -> Option<&T> {
+        self.enabled.then(|| self.value.get_or_init(build))
+    }
+
+    /// The index if it was already built
+    pub fn get(&self) -> Option<&T> {
+        self.value.get()
+    }
+}
@@ -0,0 +1,202 @@
+use super::{Chunk, TagIndex};
+use crate::{cost::Cost, graph::NodeList, NodeID, Point};
+
+use std::fmt;
+
+/// A breakdown of the Memory used by a [`PathCache`](crate::PathCache)
+///
+/// Returned by [`PathCache::memory_usage`](crate::PathCache::memory_usage). All sizes are in
+/// bytes and only count the memory owned by the `PathCache`. Sizes of allocations that are made
+/// by the standard library or `hashbrown` are estimated from their capacity, so the numbers are
+/// accurate to within a few percent.
+///
+/// The `PathCache` only has a single level of abstraction, so there is no breakdown per level.
+///
+/// ## Examples
+/// Basic usage:
+/// ```
+/// # use hierarchical_pathfinding::prelude::*;
+/// # let mut grid = [
+/// #     [0, 2, 0, 0, 0],
+/// #     [0, 2, 2, 2, 2],
+/// #     [0, 1, 0, 0, 0],
+/// #     [0, 1, 0, 2, 0],
+/// #     [0, 0, 0, 2, 0],
+/// # ];
+/// # let (width, height) = (grid[0].len(), grid.len());
+/// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+/// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+/// # }
+/// let pathfinding: PathCache<_> = // ...
+/// # PathCache::new(
+/// #     (width, height),
+/// #     cost_fn(&grid),
+/// #     ManhattanNeighborhood::new(width, height),
+/// #     PathCacheConfig::with_chunk_size(3),
+/// # );
+///
+/// let report = pathfinding.memory_usage();
+/// assert!(report.node_count > 0);
+/// assert_eq!(
+///     report.total_bytes(),
+///     report.chunk_bytes + report.node_bytes + report.edge_bytes + report.path_bytes
+///         + report.lru_path_bytes + report.hash_map_bytes + report.distance_index_bytes
+///         + report.contraction_hierarchy_bytes + report.landmark_bytes
+/// );
+/// println!("{}", report);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MemoryReport {
+    /// The Chunks that the Grid is divided into, without the sets of their Nodes
+    pub chunk_bytes: usize,
+    /// The Nodes of the abstract Graph, without their edges
+    pub node_bytes: usize,
+    /// The edges between the Nodes, without the Paths they contain
+    pub edge_bytes: usize,
+    /// The cached Paths of the edges.
+    ///
+    /// Always `0` if [`cache_paths`](crate::PathCacheConfig::cache_paths) is `false`.
+    pub path_bytes: usize,
+    /// The Paths that were kept after being resolved.
+    ///
+    /// Always `0` if [`path_lru_budget`](crate::PathCacheConfig::path_lru_budget) is not used.
+    pub lru_path_bytes: usize,
+    /// The hash maps that find the Nodes by position and by Chunk, and the index of the tags set
+    /// with [`tag_tiles`](crate::PathCache::tag_tiles)
+    pub hash_map_bytes: usize,
+    /// The index created by [`build_distance_index`](crate::PathCache::build_distance_index).
+    ///
+    /// `0` if there is no such index.
+    pub distance_index_bytes: usize,
+    /// The hierarchy created by
+    /// [`build_contraction_hierarchy`](crate::PathCache::build_contraction_hierarchy).
+    ///
+    /// `0` if there is no such hierarchy.
+    pub contraction_hierarchy_bytes: usize,
+    /// The costs of the [`landmarks`](crate::PathCacheConfig::landmarks).
+    ///
+    /// `0` if no landmarks are used.
+    pub landmark_bytes: usize,
+    /// The number of Nodes in the abstract Graph
+    pub node_count: usize,
+    /// The number of edges in the abstract Graph.
+    ///
+    /// Every connection between two Nodes counts as two edges, one in each direction.
+    pub edge_count: usize,
+    /// The number of cached Paths.
+    ///
+    /// The two directions of a connection share the same Path, so this is half of `edge_count`
+    /// if [`cache_paths`](crate::PathCacheConfig::cache_paths) is `true`.
+    pub path_count: usize,
+}
+
+impl MemoryReport {
+    pub(crate) fn new<C: Cost>(
+        chunks: &[Chunk],
+        nodes: &NodeList<C>,
+        tags: &TagIndex,
+    ) -> MemoryReport {
+        let mut report = MemoryReport {
+            chunk_bytes: size_of_val(chunks),
+            node_bytes: nodes.id_bound() * size_of::<crate::graph::Node<C>>(),
+            hash_map_bytes: hash_table_bytes::<(Point, NodeID)>(nodes.pos_map_capacity()),
+            ..MemoryReport::default()
+        };
+
+        for chunk in chunks {
+            report.hash_map_bytes += hash_table_bytes::<NodeID>(chunk.nodes.capacity());
+        }
+
+        report.hash_map_bytes += tags.chunk_list_bytes();
+        for capacity in tags.capacities() {
+            report.hash_map_bytes += hash_table_bytes::<(Point, u64)>(capacity);
+        }
+
+        for (id, node) in nodes.iter() {
+            report.node_count += 1;
+            report.edge_bytes += node.edges.heap_size();
+            for (&other_id, path) in node.edges.iter() {
+                report.edge_count += 1;
+                // both directions share the same allocation, so only count one of them
+                if id < other_id {
+                    let path_bytes = path.heap_size();
+                    if path_bytes > 0 {
+                        report.path_bytes += path_bytes;
+                        report.path_count += 1;
+                    }
+                }
+            }
+        }
+
+        report
+    }
+
+    /// The total number of bytes used by the `PathCache`
+    #[must_use]
+    pub fn total_bytes(&self) -> usize {
+        self.chunk_bytes
+            + self.node_bytes
+            + self.edge_bytes
+            + self.path_bytes
+            + self.lru_path_bytes
+            + self.hash_map_bytes
+            + self.distance_index_bytes
+            + self.contraction_hierarchy_bytes
+            + self.landmark_bytes
+    }
+
+    /// The average number of edges per Node
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn average_degree(&self) -> f64 {
+        if self.node_count == 0 {
+            0.0
+        } else {
+            self.edge_count as f64 / self.node_count as f64
+        }
+    }
+}
+
+impl fmt::Display for MemoryReport {
+    #[allow(clippy::cast_precision_loss)]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mb = |bytes: usize| bytes as f64 / (1024.0 * 1024.0);
+        write!(
+            f,
+            "{:.2} MB total ({:.2} MB chunks, {:.2} MB nodes, {:.2} MB edges, {:.2} MB paths, \
+             {:.2} MB kept paths, {:.2} MB hash maps, {:.2} MB distance index, \
+             {:.2} MB contraction hierarchy, {:.2} MB landmarks), {} nodes, {} edges, average degree {:.2}, {} cached paths",
+            mb(self.total_bytes()),
+            mb(self.chunk_bytes),
+            mb(self.node_bytes),
+            mb(self.edge_bytes),
+            mb(self.path_bytes),
+            mb(self.lru_path_bytes),
+            mb(self.hash_map_bytes),
+            mb(self.distance_index_bytes),
+            mb(self.contraction_hierarchy_bytes),
+            mb(self.landmark_bytes),
+            self.node_count,
+            self.edge_count,
+            self.average_degree(),
+            self.path_count,
+        )
+    }
+}
+
+/// Estimates the size of the allocation of a `hashbrown` table with the given capacity
+fn hash_table_bytes<T>(capacity: usize) -> usize {
+    // one control byte per bucket, plus one group of control bytes for unaligned loads
+    const GROUP_WIDTH: usize = 16;
+
+    if capacity == 0 {
+        return 0;
+    }
+    // hashbrown keeps 1/8 of the buckets free, and the number of buckets is a power of two
+    let buckets = if capacity < 8 {
+        (capacity + 1).next_power_of_two()
+    } else {
+        (capacity * 8 / 7).next_power_of_two()
+    };
+    buckets * (size_of::<T>() + 1) + GROUP_WIDTH
+}
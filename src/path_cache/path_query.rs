@@ -0,0 +1,289 @@
+use super::{GoalMode, PathCache};
+use crate::{
+    cost::Cost,
+    graph,
+    grid::{self, SearchProgress},
+    neighbors::Neighborhood,
+    path::{AbstractPath, Path},
+    search_context::SearchContext,
+    NodeID, Point, PointMap,
+};
+
+use std::fmt;
+
+/// The result of a call to [`PathQuery::step`]
+#[derive(Debug)]
+pub enum QueryStatus<N: Neighborhood, C: Cost = usize> {
+    /// The search ran out of expansions and has to be continued with another call to
+    /// [`step`](PathQuery::step)
+    InProgress,
+    /// The Path was found
+    Found(AbstractPath<N, C>),
+    /// There is no Path between start and goal
+    NoPath,
+}
+
+/// The stage that a [`PathQuery`] is in
+#[derive(Debug)]
+enum QueryState<C: Cost> {
+    /// the Nodes next to start and goal haven't been looked up yet
+    Begin,
+    /// searching the abstract Graph between the Nodes next to start and goal
+    Graph {
+        start_id: NodeID,
+        start_path: Option<Path<Point, C>>,
+        goal_id: NodeID,
+        goal_path: Option<Path<Point, C>>,
+    },
+    /// searching the Grid directly, see [`a_star_fallback`](crate::PathCacheConfig::a_star_fallback)
+    Grid,
+    /// `step` already returned a result
+    Done,
+}
+
+/// A search for a Path that can be spread over multiple calls, created by
+/// [`PathCache::query_path`].
+///
+/// Every call to [`step`](PathQuery::step) continues the search where the previous call left
+/// off, expanding at most the given number of Nodes in the abstract Graph or Tiles on the Grid.
+/// This allows long searches to be split up to stay within a time budget, e.g. one call per
+/// frame.
+///
+/// The searches within the Chunks of start and goal (to find the closest Nodes and to connect
+/// the Path to them) are not limited, since they never leave a single Chunk. Queries always use
+/// [`GoalMode::Exact`].
+///
+/// The query keeps a shared reference to the [`PathCache`], so the Grid cannot be changed with
+/// [`tiles_changed`](PathCache::tiles_changed) while a query is running.
+pub struct PathQuery<'a, N: Neighborhood, C: Cost, F> {
+    cache: &'a PathCache<N, C>,
+    start: Point,
+    goal: Point,
+    get_cost: F,
+    context: SearchContext<C>,
+    state: QueryState<C>,
+}
+
+impl<N: Neighborhood, C: Cost, F> fmt::Debug for PathQuery<'_, N, C, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // get_cost is usually a closure, which doesn't implement Debug
+        f.debug_struct("PathQuery")
+            .field("start", &self.start)
+            .field("goal", &self.goal)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, N, C, F> PathQuery<'a, N, C, F>
+where
+    N: Neighborhood + Sync,
+    C: Cost,
+    F: FnMut(Point) -> Option<C>,
+{
+    pub(super) fn new(
+        cache: &'a PathCache<N, C>,
+        start: Point,
+        goal: Point,
+        get_cost: F,
+    ) -> PathQuery<'a, N, C, F> {
+        // searching for multiple goal Tiles at once can't be split up
+        assert!(
+            cache.config.goal_mode == GoalMode::Exact,
+            "PathQuery only supports GoalMode::Exact, but the goal_mode is {:?}",
+            cache.config.goal_mode
+        );
+        PathQuery {
+            cache,
+            start,
+            goal,
+            get_cost,
+            context: SearchContext::new(),
+            state: QueryState::Begin,
+        }
+    }
+
+    /// Continues the search, expanding at most `max_expansions` Nodes or Tiles.
+    ///
+    /// Returns [`QueryStatus::InProgress`] if the search is not done yet, or the result of the
+    /// search otherwise. See [`PathCache::query_path`] for an example.
+    ///
+    /// ## Panics
+    /// Panics if the query already returned [`Found`](QueryStatus::Found) or
+    /// [`NoPath`](QueryStatus::NoPath) before.
+    pub fn step(&mut self, max_expansions: usize) -> QueryStatus<N, C> {
+        let mut budget = max_expansions;
+        loop {
+            match std::mem::replace(&mut self.state, QueryState::Done) {
+                QueryState::Begin => {
+                    if let Some(status) = self.begin() {
+                        return status;
+                    }
+                }
+                QueryState::Graph {
+                    start_id,
+                    start_path,
+                    goal_id,
+                    goal_path,
+                } => {
+                    let cache = self.cache;
+                    let progress = graph::a_star_continue(
+                        &mut self.context.graph,
+                        &cache.nodes,
+                        start_id,
+                        goal_id,
+                        &cache.neighborhood,
+                        // calculating the landmarks could take longer than the whole budget
+                        cache.landmarks.get(),
+                        cache.heuristic(),
+                        &mut budget,
+                    );
+                    match progress {
+                        SearchProgress::InProgress => {
+                            self.state = QueryState::Graph {
+                                start_id,
+                                start_path,
+                                goal_id,
+                                goal_path,
+                            };
+                            return QueryStatus::InProgress;
+                        }
+                        SearchProgress::Done(None) => return QueryStatus::NoPath,
+                        SearchProgress::Done(Some(path)) => {
+                            if let Some(status) =
+                                self.resolve(start_path.as_ref(), &path, goal_path)
+                            {
+                                return status;
+                            }
+                        }
+                    }
+                }
+                QueryState::Grid => {
+                    let cache = self.cache;
+                    let progress = grid::a_star_continue(
+                        &mut self.context.grid,
+                        &cache.neighborhood,
+                        (0, 0),
+                        (cache.width, cache.height),
+                        &mut self.get_cost,
+                        self.start,
+                        self.goal,
+                        cache.heuristic(),
+                        &mut budget,
+                    );
+                    match progress {
+                        SearchProgress::InProgress => {
+                            self.state = QueryState::Grid;
+                            return QueryStatus::InProgress;
+                        }
+                        SearchProgress::Done(path) => {
+                            let path = path.expect("Inconsistency in Pathfinding");
+                            return QueryStatus::Found(cache.known_path(path));
+                        }
+                    }
+                }
+                QueryState::Done => panic!("PathQuery::step called after the query was done"),
+            }
+        }
+    }
+
+    /// Looks up the Nodes next to start and goal and starts the search on the Graph.
+    ///
+    /// Returns the result if the query is already done.
+    fn begin(&mut self) -> Option<QueryStatus<N, C>> {
+        let cache = self.cache;
+        let (start, goal) = (self.start, self.goal);
+        let get_cost = &mut self.get_cost;
+        let context = &mut self.context;
+
+        if !cache.in_bounds(goal) || get_cost(start).is_none() {
+            return Some(QueryStatus::NoPath);
+        }
+        if start == goal {
+            let path = Path::from_slice(&[start, start], C::ZERO);
+            return Some(QueryStatus::Found(cache.known_path(path)));
+        }
+
+        let Some((start_id, start_path)) =
+            cache.find_nearest_node(start, &mut *get_cost, false, context)
+        else {
+            // start is in a cave within its Chunk, see find_path
+            let path = cache.get_chunk(start).find_path(
+                &mut context.grid,
+                start,
+                goal,
+                get_cost,
+                &cache.neighborhood,
+                cache.min_cost(),
+            );
+            return Some(match path {
+                Some(path) => QueryStatus::Found(cache.known_path(path)),
+                None => QueryStatus::NoPath,
+            });
+        };
+        let Some((goal_id, goal_path)) =
+            cache.find_nearest_node(goal, &mut *get_cost, true, context)
+        else {
+            return Some(QueryStatus::NoPath);
+        };
+
+        // an index that still has to be rebuilt could take longer than the whole budget
+        if start_id == goal_id
+            || cache.distance_table.get().is_some()
+            || cache.contraction.get().is_some()
+        {
+            // no search necessary
+            return match cache.graph_path(start_id, goal_id, context) {
+                Some(path) => self.resolve(start_path.as_ref(), &path, goal_path),
+                None => Some(QueryStatus::NoPath),
+            };
+        }
+
+        graph::a_star_begin(&mut context.graph, &cache.nodes, start_id);
+        self.state = QueryState::Graph {
+            start_id,
+            start_path,
+            goal_id,
+            goal_path,
+        };
+        None
+    }
+
+    /// Turns the Path through the Graph into the final Path, or starts the search on the Grid if
+    /// the Path is short.
+    ///
+    /// Returns the result if the query is done.
+    fn resolve(
+        &mut self,
+        start_path: Option<&Path<Point, C>>,
+        graph_path: &Path<NodeID, C>,
+        goal_path: Option<Path<Point, C>>,
+    ) -> Option<QueryStatus<N, C>> {
+        let cache = self.cache;
+        if cache.needs_grid_search(graph_path) {
+            grid::a_star_begin(
+                &mut self.context.grid,
+                (0, 0),
+                (cache.width, cache.height),
+                self.start,
+            );
+            self.state = QueryState::Grid;
+            return None;
+        }
+
+        let parts = cache.resolve_path(
+            self.start,
+            start_path,
+            self.goal,
+            graph_path,
+            goal_path,
+            &mut self.get_cost,
+            &mut self.context,
+            &mut PointMap::default(),
+        );
+        Some(match cache.build_path(self.start, parts) {
+            Some(path) => QueryStatus::Found(path),
+            None => QueryStatus::NoPath,
+        })
+    }
+}
@@ -0,0 +1,127 @@
+use crate::Point;
+
+use std::fmt;
+
+/// An area of multiple Tiles that can be used as the goal of
+/// [`find_path_to_region`](crate::PathCache::find_path_to_region).
+///
+/// Reaching any Tile of the Region counts as reaching the Region. Like with a single goal, the
+/// Tiles of the Region may be solid, in which case the last step of the Path walks into one of
+/// them.
+///
+/// ## Examples
+/// The border of a building can be described by its Tiles, or by a predicate:
+/// ```
+/// # use hierarchical_pathfinding::prelude::*;
+/// let building = Region::Rect { pos: (3, 4), size: (2, 3) };
+/// assert!(building.contains((4, 6)));
+/// assert!(!building.contains((5, 6)));
+///
+/// let border = [(3, 4), (4, 4), (3, 6), (4, 6)];
+/// let doors = Region::Points(&border);
+/// assert!(doors.contains((3, 6)));
+///
+/// let odd_rows = |(_, y): (usize, usize)| y % 2 == 1;
+/// let rows = Region::Predicate(&odd_rows);
+/// assert!(rows.contains((0, 5)));
+/// ```
+#[derive(Clone, Copy)]
+pub enum Region<'a> {
+    /// All Tiles of the rectangle with the top-left corner `pos` and the given `size`
+    Rect {
+        /// The top-left corner of the rectangle
+        pos: Point,
+        /// The width and height of the rectangle
+        size: Point,
+    },
+    /// The given Tiles
+    Points(&'a [Point]),
+    /// All Tiles for which the predicate returns `true`
+    ///
+    /// Since these Tiles could be anywhere, the search cannot be guided towards them, and every
+    /// Chunk has to be checked for matching Tiles.
+    Predicate(&'a dyn Fn(Point) -> bool),
+}
+
+impl fmt::Debug for Region<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Region::Rect { pos, size } => f
+                .debug_struct("Rect")
+                .field("pos", pos)
+                .field("size", size)
+                .finish(),
+            Region::Points(points) => f.debug_tuple("Points").field(points).finish(),
+            // closures don't implement Debug
+            Region::Predicate(_) => f.write_str("Predicate(..)"),
+        }
+    }
+}
+
+impl Region<'_> {
+    /// Checks if `pos` is part of the Region
+    #[must_use]
+    pub fn contains(&self, pos: Point) -> bool {
+        match self {
+            Region::Rect { pos: corner, size } => {
+                pos.0 >= corner.0
+                    && pos.1 >= corner.1
+                    && pos.0 - corner.0 < size.0
+                    && pos.1 - corner.1 < size.1
+            }
+            Region::Points(points) => points.contains(&pos),
+            Region::Predicate(predicate) => predicate(pos),
+        }
+    }
+
+    /// The smallest rectangle within a Grid of the given size that contains all Tiles of the
+    /// Region, as its top-left and bottom-right corners (inclusive)
+    pub(crate) fn bounds(&self, grid_size: Point) -> Option<(Point, Point)> {
+        let max = (grid_size.0.checked_sub(1)?, grid_size.1.checked_sub(1)?);
+        match self {
+            Region::Rect { pos, size } => {
+                if size.0 == 0 || size.1 == 0 || pos.0 > max.0 || pos.1 > max.1 {
+                    return None;
+                }
+                let end = (
+                    pos.0.saturating_add(size.0 - 1).min(max.0),
+                    pos.1.saturating_add(size.1 - 1).min(max.1),
+                );
+                Some((*pos, end))
+            }
+            Region::Points(points) => {
+                points
+                    .iter()
+                    .filter(|p| p.0 <= max.0 && p.1 <= max.1)
+                    .fold(None, |bounds, &p| {
+                        Some(match bounds {
+                            Some((min, max)) => (
+                                (p.0.min(min.0), p.1.min(min.1)),
+                                (p.0.max(max.0), p.1.max(max.1)),
+                            ),
+                            None => (p, p),
+                        })
+                    })
+            }
+            Region::Predicate(_) => Some(((0, 0), max)),
+        }
+    }
+
+    /// Adds the Tiles of the Region within the rectangle at `pos` with the given `size` to `out`
+    pub(crate) fn tiles_in(&self, pos: Point, size: Point, out: &mut Vec<Point>) {
+        match self {
+            Region::Points(points) => out.extend(points.iter().copied().filter(|p| {
+                p.0 >= pos.0 && p.1 >= pos.1 && p.0 - pos.0 < size.0 && p.1 - pos.1 < size.1
+            })),
+            _ => {
+                for y in pos.1..pos.1 + size.1 {
+                    for x in pos.0..pos.0 + size.0 {
+                        if self.contains((x, y)) {
+                            out.push((x, y));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{Point, PointMap};
+
+/// The tags of the Tiles in one Chunk
+#[derive(Clone, Debug, Default)]
+struct ChunkTags {
+    /// all tags of the Tiles in `tiles` combined
+    tags: u64,
+    /// the Tiles that have at least one tag
+    tiles: PointMap<u64>,
+}
+
+/// An index of the tags of the Tiles in every Chunk.
+///
+/// Tags are bits in a `u64` with a meaning that is defined by the user (like "has water").
+/// Only the tagged Tiles are stored, and the Chunks know which tags they contain, so a search
+/// for tagged Tiles can skip Chunks without looking at their Tiles.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TagIndex {
+    /// indexed like `PathCache::chunks`, empty until the first Tile is tagged
+    chunks: Vec<ChunkTags>,
+}
+
+impl TagIndex {
+    /// Sets the tags of the Tile at `pos` in the Chunk at `chunk_index`
+    pub fn set(&mut self, chunk_index: usize, num_chunks: usize, pos: Point, tags: u64) {
+        if self.chunks.is_empty() {
+            if tags == 0 {
+                return;
+            }
+            self.chunks = vec![ChunkTags::default(); num_chunks];
+        }
+        let chunk = &mut self.chunks[chunk_index];
+        let old = if tags == 0 {
+            chunk.tiles.remove(&pos)
+        } else {
+            chunk.tiles.insert(pos, tags)
+        };
+        if old.unwrap_or(0) & !tags == 0 {
+            // no tag was removed
+            chunk.tags |= tags;
+        } else {
+            chunk.tags = chunk.tiles.values().fold(0, |all, &tags| all | tags);
+        }
+    }
+
+    /// The tags of the Tile at `pos` in the Chunk at `chunk_index`
+    pub fn get(&self, chunk_index: usize, pos: Point) -> u64 {
+        self.chunks
+            .get(chunk_index)
+            .and_then(|chunk| chunk.tiles.get(&pos))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// The Tiles in the Chunk at `chunk_index` that have any of `tags`
+    pub fn tiles_with(&self, chunk_index: usize, tags: u64) -> impl Iterator<Item = Point> + '_ {
+        self.chunks
+            .get(chunk_index)
+            .filter(|chunk| chunk.tags & tags != 0)
+            .into_iter()
+            .flat_map(move |chunk| {
+                chunk
+                    .tiles
+                    .iter()
+                    .filter(move |(_, &tile_tags)| tile_tags & tags != 0)
+                    .map(|(&pos, _)| pos)
+            })
+    }
+
+    /// The capacities of the hash maps, for [`MemoryReport`](super::MemoryReport)
+    pub fn capacities(&self) -> impl Iterator<Item = usize> + '_ {
+        self.chunks.iter().map(|chunk| chunk.tiles.capacity())
+    }
+
+    /// The number of bytes used by the list of Chunks
+    pub fn chunk_list_bytes(&self) -> usize {
+        self.chunks.capacity() * size_of::<ChunkTags>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl TagIndex {
+        fn chunk_tags(&self, chunk_index: usize) -> u64 {
+            self.chunks.get(chunk_index).map_or(0, |chunk| chunk.tags)
+        }
+    }
+
+    #[test]
+    fn set_and_get() {
+        let mut index = TagIndex::default();
+        index.set(1, 4, (3, 3), 0);
+        assert!(index.chunks.is_empty());
+
+        index.set(1, 4, (3, 3), 0b01);
+        index.set(1, 4, (4, 3), 0b10);
+        index.set(2, 4, (9, 3), 0b10);
+        assert_eq!(index.get(1, (3, 3)), 0b01);
+        assert_eq!(index.get(1, (5, 3)), 0);
+        assert_eq!(index.chunk_tags(0), 0);
+        assert_eq!(index.chunk_tags(1), 0b11);
+        assert_eq!(index.chunk_tags(2), 0b10);
+
+        let mut tiles: Vec<_> = index.tiles_with(1, 0b11).collect();
+        tiles.sort();
+        assert_eq!(tiles, vec![(3, 3), (4, 3)]);
+        assert_eq!(index.tiles_with(1, 0b10).collect::<Vec<_>>(), vec![(4, 3)]);
+        assert_eq!(index.tiles_with(0, 0b10).count(), 0);
+
+        // removing tags
+        index.set(1, 4, (4, 3), 0b01);
+        assert_eq!(index.chunk_tags(1), 0b01);
+        index.set(1, 4, (4, 3), 0);
+        index.set(1, 4, (3, 3), 0);
+        assert_eq!(index.chunk_tags(1), 0);
+        assert_eq!(index.tiles_with(1, u64::MAX).count(), 0);
+    }
+}
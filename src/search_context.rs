@@ -0,0 +1,401 @@
+use crate::{
+    cost::Cost,
+    grid::{Element, HeuristicElement},
+    NodeID, Point,
+};
+
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+use std::ops::Index;
+
+/// The largest number of entries that a [`VisitedMap`] stores in a flat array.
+///
+/// A search over a larger area, like A* on the entire Grid or the graph of a large Grid, is
+/// usually done with a fresh [`SearchContext`] and only visits a small part of that area, so
+/// allocating an entry for every key would take longer than the search itself.
+const DENSE_LIMIT: usize = 1 << 14;
+
+/// Reusable scratch space for the searches performed by a [`PathCache`](crate::PathCache).
+///
+/// Every search needs a set of visited Tiles or Nodes and a priority queue of the ones that are
+/// yet to be visited. Creating those for every query means a lot of allocations, which can make
+/// up a considerable part of the time spent on short Paths.
+///
+/// A `SearchContext` keeps those buffers around between queries. Clearing them keeps their
+/// capacity and takes constant time, so after a few queries no more allocations are necessary.
+///
+/// A `SearchContext` is not tied to a specific [`PathCache`](crate::PathCache) and can be
+/// used with any of them that use the same [`Cost`] type, but it is not [`Sync`], so every
+/// thread needs its own one.
+///
+/// ## Examples
+/// Basic usage:
+/// ```
+/// # use hierarchical_pathfinding::prelude::*;
+/// # let mut grid = [
+/// #     [0, 2, 0, 0, 0],
+/// #     [0, 2, 2, 2, 2],
+/// #     [0, 1, 0, 0, 0],
+/// #     [0, 1, 0, 2, 0],
+/// #     [0, 0, 0, 2, 0],
+/// # ];
+/// # let (width, height) = (grid[0].len(), grid.len());
+/// # fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+/// #     move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
+/// # }
+/// let pathfinding: PathCache<_> = // ...
+/// # PathCache::new(
+/// #     (width, height),
+/// #     cost_fn(&grid),
+/// #     ManhattanNeighborhood::new(width, height),
+/// #     PathCacheConfig::with_chunk_size(3),
+/// # );
+///
+/// let mut context = SearchContext::new();
+///
+/// for goal in [(4, 4), (2, 2), (0, 4)] {
+///     let path = pathfinding.find_path_with_context((0, 0), goal, cost_fn(&grid), &mut context);
+///     assert!(path.is_some());
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SearchContext<C: Cost = usize> {
+    pub(crate) grid: SearchBuffers<Point, C>,
+    pub(crate) graph: SearchBuffers<NodeID, C>,
+}
+
+impl<C: Cost> SearchContext<C> {
+    /// Creates a new, empty `SearchContext`.
+    ///
+    /// No memory is allocated until the context is first used.
+    #[must_use]
+    pub fn new() -> SearchContext<C> {
+        SearchContext {
+            grid: SearchBuffers::default(),
+            graph: SearchBuffers::default(),
+        }
+    }
+}
+
+impl<C: Cost> Default for SearchContext<C> {
+    fn default() -> Self {
+        SearchContext::new()
+    }
+}
+
+/// The buffers used by a single search over `Id`s (either Points on the Grid or Nodes in the Graph)
+#[derive(Clone, Debug)]
+pub(crate) struct SearchBuffers<Id, C> {
+    pub visited: VisitedMap<Id, C>,
+    pub a_star_next: BinaryHeap<HeuristicElement<Id, C>>,
+    pub dijkstra_next: BinaryHeap<Element<Id, C>>,
+    pub neighbors: Vec<Id>,
+}
+
+impl<Id: DenseKey, C: Cost> SearchBuffers<Id, C> {
+    /// Clears all buffers for a new search within the rectangle at `base` with the given `size`.
+    ///
+    /// The capacity of all buffers is kept.
+    pub fn reset(&mut self, base: Point, size: (usize, usize)) -> &mut Self {
+        self.visited.reset(base, size);
+        self.a_star_next.clear();
+        self.dijkstra_next.clear();
+        self.neighbors.clear();
+        self
+    }
+}
+
+impl<Id, C: Ord> Default for SearchBuffers<Id, C> {
+    fn default() -> Self {
+        SearchBuffers {
+            visited: VisitedMap::default(),
+            a_star_next: BinaryHeap::new(),
+            dijkstra_next: BinaryHeap::new(),
+            neighbors: Vec::new(),
+        }
+    }
+}
+
+/// A key that can be mapped to an index in a flat array
+pub(crate) trait DenseKey: Copy + Default + Eq + Hash + std::fmt::Debug {
+    /// The index of `self` in a row-major array over the rectangle at `base` with the given size
+    fn dense_index(self, base: Point, size: (usize, usize)) -> usize;
+}
+
+impl DenseKey for Point {
+    #[track_caller]
+    fn dense_index(self, base: Point, size: (usize, usize)) -> usize {
+        // a Point outside of the rectangle would silently share an entry with another one.
+        // Points before `base` wrap around to large offsets, so they fail the check as well.
+        let (x, y) = (self.0.wrapping_sub(base.0), self.1.wrapping_sub(base.1));
+        if x >= size.0 || y >= size.1 {
+            outside_of_rectangle();
+        }
+        y * size.0 + x
+    }
+}
+
+impl DenseKey for NodeID {
+    #[track_caller]
+    fn dense_index(self, _base: Point, size: (usize, usize)) -> usize {
+        if self >= size.0 {
+            outside_of_rectangle();
+        }
+        // Node IDs are slab keys, which are already dense
+        self
+    }
+}
+
+// kept out of `dense_index`, which is called in the hot loops of the searches
+#[cold]
+#[track_caller]
+fn outside_of_rectangle() -> ! {
+    panic!("key is outside of the rectangle of the search");
+}
+
+/// Maps every visited `Id` to the cost of reaching it and its predecessor.
+///
+/// The values are stored in a flat array over the searched area instead of a `HashMap`.
+/// Every entry is stamped with the generation it was written in, which means that clearing
+/// the map only requires incrementing the current generation.
+///
+/// The first search over an area larger than [`DENSE_LIMIT`] starts out with a `HashMap`
+/// instead, since the flat array only pays off if the map is reused or the search visits a
+/// large part of the area. `entries` is empty while the `HashMap` is used.
+#[derive(Clone, Debug)]
+pub(crate) struct VisitedMap<Id, C> {
+    base: Point,
+    size: (usize, usize),
+    entries: Vec<(u32, (C, Id))>,
+    generation: u32,
+    sparse: hashbrown::HashMap<Id, (C, Id)>,
+    /// whether an area larger than [`DENSE_LIMIT`] was searched before
+    searched_large_area: bool,
+}
+
+impl<Id, C> Default for VisitedMap<Id, C> {
+    fn default() -> Self {
+        VisitedMap {
+            base: (0, 0),
+            size: (0, 0),
+            entries: Vec::new(),
+            generation: 0,
+            sparse: hashbrown::HashMap::new(),
+            searched_large_area: false,
+        }
+    }
+}
+
+impl<Id: DenseKey, C: Cost> VisitedMap<Id, C> {
+    /// Removes all entries and prepares the map for keys in the rectangle at `base` with the
+    /// given size
+    pub fn reset(&mut self, base: Point, size: (usize, usize)) {
+        self.base = base;
+        self.size = size;
+
+        let len = size.0 * size.1;
+        if len > self.entries.len() && len > DENSE_LIMIT && !self.searched_large_area {
+            self.searched_large_area = true;
+            self.entries = Vec::new();
+            self.sparse.clear();
+        } else if len > self.entries.len() {
+            self.searched_large_area |= len > DENSE_LIMIT;
+            self.allocate();
+        } else if self.generation == u32::MAX {
+            for entry in &mut self.entries {
+                entry.0 = 0;
+            }
+            self.generation = 1;
+        } else {
+            self.generation += 1;
+        }
+    }
+
+    /// Replaces `entries` with a flat array over the current area
+    fn allocate(&mut self) {
+        // A new zeroed allocation is cheaper than resizing: The memory is only touched once
+        // it is actually used, which matters for searches on the entire Grid.
+        self.entries = vec![(0, (C::ZERO, Id::default())); self.size.0 * self.size.1];
+        self.generation = 1;
+    }
+
+    // Every key in the area has an entry in the flat array unless it is empty, so the fallback
+    // to the `HashMap` costs no more than the bounds check of indexing.
+
+    #[track_caller]
+    pub fn get(&self, key: &Id) -> Option<&(C, Id)> {
+        match self.entries.get(key.dense_index(self.base, self.size)) {
+            Some((generation, value)) => (*generation == self.generation).then_some(value),
+            None => self.get_sparse(key),
+        }
+    }
+
+    #[track_caller]
+    pub fn get_mut(&mut self, key: &Id) -> Option<&mut (C, Id)> {
+        match self.entries.get_mut(key.dense_index(self.base, self.size)) {
+            Some((generation, value)) => (*generation == self.generation).then_some(value),
+            None => Self::get_sparse_mut(&mut self.sparse, key),
+        }
+    }
+
+    pub fn contains_key(&self, key: &Id) -> bool {
+        self.get(key).is_some()
+    }
+
+    #[track_caller]
+    pub fn insert(&mut self, key: Id, value: (C, Id)) {
+        match self.entries.get_mut(key.dense_index(self.base, self.size)) {
+            Some(entry) => *entry = (self.generation, value),
+            None => self.insert_sparse(key, value),
+        }
+    }
+
+    // The accesses to the `HashMap` are kept out of the methods above, so that those can still
+    // be inlined into the hot loops of the searches.
+
+    #[cold]
+    #[inline(never)]
+    fn get_sparse(&self, key: &Id) -> Option<&(C, Id)> {
+        self.sparse.get(key)
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn get_sparse_mut<'a>(
+        sparse: &'a mut hashbrown::HashMap<Id, (C, Id)>,
+        key: &Id,
+    ) -> Option<&'a mut (C, Id)> {
+        sparse.get_mut(key)
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn insert_sparse(&mut self, key: Id, value: (C, Id)) {
+        self.sparse.insert(key, value);
+        if self.sparse.len() * 16 > self.size.0 * self.size.1 {
+            // the search visits enough of the area for the flat array to be faster
+            self.allocate();
+            for (key, value) in self.sparse.drain() {
+                self.entries[key.dense_index(self.base, self.size)] = (1, value);
+            }
+        }
+    }
+}
+
+impl<Id: DenseKey, C: Cost> Index<&Id> for VisitedMap<Id, C> {
+    type Output = (C, Id);
+    #[track_caller]
+    fn index(&self, key: &Id) -> &(C, Id) {
+        self.get(key).expect("key not visited")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn different_rectangles() {
+        let mut map = VisitedMap::<Point, usize>::default();
+
+        map.reset((2, 3), (4, 2));
+        for y in 3..5 {
+            for x in 2..6 {
+                map.insert((x, y), (x * 10 + y, (0, 0)));
+            }
+        }
+        assert_eq!(map.get(&(5, 4)), Some(&(54, (0, 0))));
+
+        // smaller, keeps the allocation
+        map.reset((0, 0), (2, 3));
+        assert!((0..2).all(|x| (0..3).all(|y| !map.contains_key(&(x, y)))));
+        map.insert((1, 2), (1, (1, 1)));
+        assert_eq!(map[&(1, 2)], (1, (1, 1)));
+
+        // larger, needs a new allocation
+        map.reset((10, 10), (20, 20));
+        assert!(!map.contains_key(&(11, 12)));
+        map.insert((29, 29), (5, (10, 10)));
+        assert_eq!(map.get(&(29, 29)), Some(&(5, (10, 10))));
+        assert!(!map.contains_key(&(10, 10)));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the rectangle")]
+    fn outside_of_rectangle() {
+        let mut map = VisitedMap::<Point, usize>::default();
+        map.reset((0, 0), (4, 4));
+        // would be (0, 1) without the check
+        map.insert((4, 0), (1, (0, 0)));
+    }
+
+    #[test]
+    fn large_rectangle() {
+        let mut map = VisitedMap::<Point, usize>::default();
+        map.reset((0, 0), (1024, 1024));
+        assert!(map.entries.is_empty());
+        map.insert((1000, 3), (2, (999, 3)));
+        assert_eq!(map.get(&(1000, 3)), Some(&(2, (999, 3))));
+        map.get_mut(&(1000, 3)).unwrap().0 = 1;
+        assert_eq!(map[&(1000, 3)], (1, (999, 3)));
+        assert!(map.entries.is_empty());
+
+        // the entries of the large area don't show up in a small one, and the other way around
+        map.reset((998, 0), (4, 4));
+        assert!(!map.contains_key(&(1000, 3)));
+        map.insert((999, 3), (4, (998, 3)));
+
+        // the map is reused, so the second large area is stored in the flat array
+        map.reset((0, 0), (1024, 1024));
+        assert_eq!(map.entries.len(), 1024 * 1024);
+        assert!(!map.contains_key(&(1000, 3)));
+        assert!(!map.contains_key(&(999, 3)));
+        map.insert((1000, 3), (2, (999, 3)));
+        assert_eq!(map[&(1000, 3)], (2, (999, 3)));
+    }
+
+    #[test]
+    fn large_search() {
+        let mut map = VisitedMap::<Point, usize>::default();
+        map.reset((0, 0), (256, 256));
+        for x in 0..256 {
+            for y in 0..32 {
+                map.insert((x, y), (x + y, (0, 0)));
+            }
+        }
+        // the search visited too much of the area for the HashMap
+        assert!(!map.entries.is_empty());
+        assert_eq!(map[&(0, 0)], (0, (0, 0)));
+        assert_eq!(map[&(255, 31)], (286, (0, 0)));
+        assert!(!map.contains_key(&(0, 32)));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the rectangle")]
+    fn outside_of_large_rectangle() {
+        let mut map = VisitedMap::<Point, usize>::default();
+        map.reset((0, 0), (1024, 1024));
+        map.insert((1024, 0), (1, (0, 0)));
+    }
+
+    #[test]
+    fn generation_wraparound() {
+        let mut buffers = SearchBuffers::<NodeID, usize>::default();
+        buffers.reset((0, 0), (8, 1));
+        buffers.visited.insert(3, (7, 2));
+
+        buffers.visited.generation = u32::MAX - 1;
+        buffers.visited.insert(5, (1, 5));
+        buffers.reset((0, 0), (8, 1));
+        assert_eq!(buffers.visited.generation, u32::MAX);
+        assert!(!buffers.visited.contains_key(&5));
+        buffers.visited.insert(6, (2, 6));
+
+        // wraps around, but entries from before don't come back
+        buffers.reset((0, 0), (8, 1));
+        assert_eq!(buffers.visited.generation, 1);
+        assert!((0..8).all(|id| !buffers.visited.contains_key(&id)));
+        buffers.visited.insert(3, (4, 3));
+        assert_eq!(buffers.visited[&3], (4, 3));
+    }
+}
@@ -10,8 +10,8 @@ fn new() {
         [0, 0, 0, 2, 0],
     ];
     let (width, height) = (grid[0].len(), grid.len());
-    fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Fn((usize, usize)) -> isize {
-        move |(x, y)| [1, 10, -1][grid[y][x]]
+    fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Fn((usize, usize)) -> Option<usize> {
+        move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
     }
     let pathfinding = PathCache::new(
         (width, height),
@@ -41,8 +41,8 @@ fn update_path() {
         [0, 0, 0, 2, 0],
     ];
     let (width, height) = (grid[0].len(), grid.len());
-    fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Fn((usize, usize)) -> isize {
-        move |(x, y)| [1, 10, -1][grid[y][x]]
+    fn cost_fn(grid: &[[usize; 5]; 5]) -> impl '_ + Fn((usize, usize)) -> Option<usize> {
+        move |(x, y)| [Some(1), Some(10), None][grid[y][x]]
     }
 
     let mut pathfinding = PathCache::new(
@@ -130,8 +130,8 @@ fn skip_first_and_last() {
     // but: old path optimizations would add the shortcut S->2 and 0->G, which would create
     //      an invalid path
     let (width, height) = (grid[0].len(), grid.len());
-    fn cost_fn(grid: &[[usize; 4]]) -> impl '_ + Fn((usize, usize)) -> isize {
-        move |(x, y)| [1, -1][grid[y][x]]
+    fn cost_fn(grid: &[[usize; 4]]) -> impl '_ + Fn((usize, usize)) -> Option<usize> {
+        move |(x, y)| [Some(1), None][grid[y][x]]
     }
     let pathfinding = PathCache::new(
         (width, height),
@@ -156,10 +156,10 @@ fn from_github_issue_7_example_1() {
 
     type Grid = [[usize; 7]; 7];
 
-    const COST_MAP: [isize; 3] = [1, 10, -1]; // now const for ownership reasons
+    const COST_MAP: [Option<usize>; 3] = [Some(1), Some(10), None]; // now const for ownership reasons
 
     // only borrows the Grid when called
-    fn cost_fn(grid: &Grid) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
+    fn cost_fn(grid: &Grid) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
         move |(x, y)| COST_MAP[grid[y][x]]
     }
 
@@ -202,10 +202,10 @@ fn from_github_issue_7_example_2() {
 
     type Grid = [[usize; 7]; 7];
 
-    const COST_MAP: [isize; 3] = [1, 10, -1]; // now const for ownership reasons
+    const COST_MAP: [Option<usize>; 3] = [Some(1), Some(10), None]; // now const for ownership reasons
 
     // only borrows the Grid when called
-    fn cost_fn(grid: &Grid) -> impl '_ + Sync + Fn((usize, usize)) -> isize {
+    fn cost_fn(grid: &Grid) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
         move |(x, y)| COST_MAP[grid[y][x]]
     }
 
@@ -265,7 +265,9 @@ fn from_github_issue_7_example_2() {
         let valid_neighbors = neighbors(goal)
             .iter()
             .cloned()
-            .filter(|n| n.0 >= 0 && n.1 >= 0 && cost_fn(&grid)((n.0 as usize, n.1 as usize)) != -1)
+            .filter(|n| {
+                n.0 >= 0 && n.1 >= 0 && cost_fn(&grid)((n.0 as usize, n.1 as usize)).is_some()
+            })
             .map(|n| (n.0 as usize, n.1 as usize))
             .collect::<Vec<_>>();
         println!("valid_neighbors: {:?}", valid_neighbors);
@@ -276,3 +278,1646 @@ fn from_github_issue_7_example_2() {
 
     main();
 }
+
+#[test]
+fn find_paths_many_goals() {
+    let (width, height) = (30, 30);
+    // walls in a regular pattern with a few gaps
+    let grid: Vec<Vec<Option<usize>>> = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| match (x % 7, y % 5) {
+                    (3, 0..=3) => None,
+                    (_, 2) if x % 11 == 0 => None,
+                    _ => Some(1 + (x * 3 + y) % 4),
+                })
+                .collect()
+        })
+        .collect();
+    fn cost_fn(
+        grid: &[Vec<Option<usize>>],
+    ) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+        move |(x, y)| grid[y][x]
+    }
+    let pathfinding = PathCache::new(
+        (width, height),
+        cost_fn(&grid),
+        MooreNeighborhood::new(width, height),
+        PathCacheConfig::with_chunk_size(4),
+    );
+
+    let start = (0, 0);
+    let goals: Vec<_> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|&(x, y)| (x + y) % 3 == 0)
+        .collect();
+
+    let paths = pathfinding.find_paths(start, &goals, cost_fn(&grid));
+    let sequential = pathfinding.find_paths_with_fn_mut(start, &goals, cost_fn(&grid));
+    assert!(paths.len() > goals.len() / 2);
+    assert_eq!(paths.len(), sequential.len());
+
+    for (goal, path) in paths {
+        let other = &sequential[&goal];
+        assert_eq!(path.cost(), other.cost());
+        assert_eq!(path.len(), other.len());
+
+        let points: Vec<_> = path.collect();
+        let other_points: Vec<_> = other.clone().collect();
+        assert_eq!(points, other_points);
+        assert_eq!(points.last(), Some(&goal));
+    }
+}
+
+#[test]
+fn cost_types() {
+    let grid = [
+        [0, 2, 0, 0, 0],
+        [0, 2, 2, 2, 2],
+        [0, 1, 0, 0, 0],
+        [0, 1, 0, 2, 0],
+        [0, 0, 0, 2, 0],
+    ];
+    fn find<C: Cost>(grid: &[[usize; 5]; 5], cost_map: [Option<C>; 3]) -> (Vec<(usize, usize)>, C) {
+        let cost_fn = |(x, y): (usize, usize)| cost_map[grid[y][x]];
+        let pathfinding = PathCache::new(
+            (5, 5),
+            cost_fn,
+            ManhattanNeighborhood::new(5, 5),
+            PathCacheConfig::with_chunk_size(3),
+        );
+        let path = pathfinding.find_path((0, 0), (4, 4), cost_fn).unwrap();
+        let cost = path.cost();
+        (path.collect(), cost)
+    }
+    let (points, cost) = find(&grid, [Some(1usize), Some(10), None]);
+    let (points_u32, cost_u32) = find(&grid, [Some(1u32), Some(10), None]);
+    assert_eq!(points, points_u32);
+    assert_eq!(cost, cost_u32 as usize);
+
+    let (points_float, cost_float) = find(
+        &grid,
+        [Some(OrderedFloat(0.5)), Some(OrderedFloat(5.0)), None],
+    );
+    assert_eq!(points, points_float);
+    assert_eq!(cost_float, OrderedFloat(cost as f64 / 2.0));
+}
+
+#[test]
+fn cost_overflow() {
+    // a 12x1 corridor that starts with 3 Tiles whose total cost is exactly u32::MAX
+    let (width, height) = (12, 1);
+    let cost_fn = |(x, _): (usize, usize)| Some(if x < 3 { u32::MAX / 3 } else { 1 });
+    let pathfinding = PathCache::new(
+        (width, height),
+        cost_fn,
+        ManhattanNeighborhood::new(width, height),
+        PathCacheConfig::with_chunk_size(3),
+    );
+
+    let path = pathfinding.find_path((0, 0), (3, 0), cost_fn).unwrap();
+    assert_eq!(path.cost(), u32::MAX);
+
+    // walking over the expensive Tiles and then some more would overflow
+    assert!(pathfinding.find_path((0, 0), (4, 0), cost_fn).is_none());
+    assert!(pathfinding.find_path((0, 0), (11, 0), cost_fn).is_none());
+
+    // the other way around only walks over 2 expensive Tiles
+    let path = pathfinding.find_path((11, 0), (0, 0), cost_fn).unwrap();
+    assert_eq!(path.cost(), u32::MAX / 3 * 2 + 9);
+}
+
+#[test]
+fn fractional_costs() {
+    // row 0 is a detour compared to the cheaper Tiles below, which A* with an unscaled heuristic
+    // would never explore
+    let (width, height) = (12, 12);
+    let cost_fn = |row_1: f64| {
+        move |(_, y): (usize, usize)| {
+            Some(OrderedFloat(match y {
+                0 => 0.5,
+                1 => row_1,
+                _ => 0.1,
+            }))
+        }
+    };
+    let assert_cost = |cost: Option<OrderedFloat>, expected: f64| {
+        let cost = cost.unwrap().0;
+        assert!((cost - expected).abs() < 1e-9, "{} != {}", cost, expected);
+    };
+
+    for chunk_size in [12, 4] {
+        let mut pathfinding = PathCache::new(
+            (width, height),
+            cost_fn(0.1),
+            ManhattanNeighborhood::new(width, height),
+            PathCacheConfig::with_chunk_size(chunk_size),
+        );
+        let path = pathfinding.find_path((0, 0), (11, 0), cost_fn(0.1));
+        assert_cost(path.map(|path| path.cost()), 1.7);
+
+        // the changed Tiles are cheaper than any Tile before
+        let changed: Vec<_> = (0..width).map(|x| (x, 1)).collect();
+        pathfinding.tiles_changed(&changed, cost_fn(0.05));
+        let path = pathfinding.find_path((0, 0), (11, 0), cost_fn(0.05));
+        assert_cost(path.map(|path| path.cost()), 1.1);
+    }
+}
+
+#[test]
+fn reachable_within() {
+    let (width, height) = (48, 40);
+    let mut initial_grid = vec![vec![0usize; width]; height];
+    for (y, row) in initial_grid.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            *cell = match (x % 11, y % 8) {
+                (5, 0..=5) | (0..=7, 4) if y != 17 => 0,
+                _ => 2 + (x * 5 + y * 3) % 4,
+            };
+        }
+    }
+    fn cost_fn(grid: &[Vec<usize>]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+        move |(x, y)| Some(grid[y][x]).filter(|&cost| cost != 0)
+    }
+
+    // a plain Dijkstra on the Grid, without any Chunks
+    fn grid_costs(
+        grid: &[Vec<usize>],
+        neighborhood: &impl Neighborhood,
+        start: (usize, usize),
+        max_cost: usize,
+    ) -> std::collections::HashMap<(usize, usize), usize> {
+        let mut costs = std::collections::HashMap::new();
+        let mut next = std::collections::BinaryHeap::new();
+        if cost_fn(grid)(start).is_some() {
+            next.push(std::cmp::Reverse((0, start)));
+        }
+        let mut neighbors = vec![];
+        while let Some(std::cmp::Reverse((cost, pos))) = next.pop() {
+            if costs.contains_key(&pos) {
+                continue;
+            }
+            costs.insert(pos, cost);
+            let step = cost_fn(grid)(pos).unwrap();
+            neighbors.clear();
+            neighborhood.get_all_neighbors(pos, &mut neighbors);
+            for &other in &neighbors {
+                if cost + step <= max_cost && cost_fn(grid)(other).is_some() {
+                    next.push(std::cmp::Reverse((cost + step, other)));
+                }
+            }
+        }
+        costs
+    }
+
+    let neighborhood = ManhattanNeighborhood::new(width, height);
+    for chunk_size in [4, 7, 16] {
+        let mut grid = initial_grid.clone();
+        let mut pathfinding = PathCache::new(
+            (width, height),
+            cost_fn(&grid),
+            neighborhood.clone(),
+            PathCacheConfig::with_chunk_size(chunk_size),
+        );
+        let starts = [(0, 0), (23, 18), (47, 9), (12, 39)];
+        let max_costs = [0, 2, 9, 40, 150, 1000];
+        for &start in &starts {
+            for &max_cost in &max_costs {
+                let expected = grid_costs(&grid, &neighborhood, start, max_cost);
+                let reached = pathfinding.reachable_within(start, max_cost, cost_fn(&grid));
+                assert_eq!(reached.len(), expected.len(), "{:?} {}", start, max_cost);
+                for (pos, cost) in reached {
+                    assert_eq!(cost, expected[&pos], "{:?} -> {:?}", start, pos);
+                }
+            }
+        }
+
+        // a cheap corridor makes Chunks reachable that were out of range before
+        for x in 0..width {
+            grid[17][x] = 1;
+        }
+        let changed: Vec<_> = (0..width).map(|x| (x, 17)).collect();
+        pathfinding.tiles_changed(&changed, cost_fn(&grid));
+        for &start in &starts {
+            let expected = grid_costs(&grid, &neighborhood, start, 60);
+            let reached = pathfinding.reachable_within(start, 60, cost_fn(&grid));
+            assert_eq!(reached.len(), expected.len(), "{:?}", start);
+            for (pos, cost) in reached {
+                assert_eq!(cost, expected[&pos], "{:?} -> {:?}", start, pos);
+            }
+        }
+    }
+}
+
+#[test]
+fn compact_paths() {
+    let (width, height) = (30, 30);
+    let grid: Vec<Vec<Option<usize>>> = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| match (x % 6, y % 4) {
+                    (2, 0..=2) => None,
+                    _ => Some(1 + (x + 2 * y) % 3),
+                })
+                .collect()
+        })
+        .collect();
+    let cost_fn = |(x, y): (usize, usize)| grid[y][x];
+
+    fn check<N: Neighborhood + Sync>(
+        neighborhood: N,
+        cost_fn: impl Sync + Copy + Fn((usize, usize)) -> Option<usize>,
+    ) {
+        let config = PathCacheConfig::with_chunk_size(5);
+        let pathfinding = PathCache::new((30, 30), cost_fn, neighborhood.clone(), config);
+        let compact = PathCache::new(
+            (30, 30),
+            cost_fn,
+            neighborhood,
+            PathCacheConfig {
+                compact_paths: true,
+                ..config
+            },
+        );
+
+        for (start, goal) in [((0, 0), (29, 29)), ((29, 0), (0, 29)), ((15, 3), (1, 28))] {
+            // the way back uses the reversed Paths
+            for (start, goal) in [(start, goal), (goal, start)] {
+                let expected = pathfinding.find_path(start, goal, cost_fn).unwrap();
+                let path = compact.find_path(start, goal, cost_fn).unwrap();
+                assert_eq!(path.cost(), expected.cost());
+                assert_eq!(path.collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+            }
+        }
+    }
+    check(ManhattanNeighborhood::new(width, height), cost_fn);
+    check(MooreNeighborhood::new(width, height), cost_fn);
+}
+
+#[test]
+fn memory_usage() {
+    let (width, height) = (20, 20);
+    let cost_fn = |(x, y): (usize, usize)| if (x + y) % 7 == 3 { None } else { Some(1usize) };
+    let create = |config| {
+        PathCache::new(
+            (width, height),
+            cost_fn,
+            MooreNeighborhood::new(width, height),
+            config,
+        )
+        .memory_usage()
+    };
+    let default = create(PathCacheConfig::with_chunk_size(5));
+    let compact = create(PathCacheConfig {
+        compact_paths: true,
+        ..PathCacheConfig::with_chunk_size(5)
+    });
+    let uncached = create(PathCacheConfig {
+        cache_paths: false,
+        ..PathCacheConfig::with_chunk_size(5)
+    });
+
+    assert!(default.node_count > 0);
+    assert_eq!(default.path_count * 2, default.edge_count);
+    assert_eq!(
+        default.average_degree(),
+        default.edge_count as f64 / default.node_count as f64
+    );
+
+    // only the storage of the Paths differs
+    for report in [compact, uncached] {
+        assert_eq!(report.node_count, default.node_count);
+        assert_eq!(report.edge_count, default.edge_count);
+        assert_eq!(report.edge_bytes, default.edge_bytes);
+    }
+    assert!(compact.path_bytes < default.path_bytes);
+    assert_eq!(uncached.path_bytes, 0);
+    assert_eq!(uncached.path_count, 0);
+}
+
+#[test]
+fn path_lru() {
+    let (width, height) = (30, 30);
+    let mut grid: Vec<Vec<Option<usize>>> = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| match (x % 6, y % 4) {
+                    (2, 0..=2) => None,
+                    _ => Some(1 + (x + 2 * y) % 3),
+                })
+                .collect()
+        })
+        .collect();
+    let neighborhood = MooreNeighborhood::new(width, height);
+    let uncached_config = PathCacheConfig {
+        cache_paths: false,
+        ..PathCacheConfig::with_chunk_size(5)
+    };
+    let create = |grid: &Vec<Vec<Option<usize>>>, config| {
+        PathCache::new(
+            (width, height),
+            |(x, y)| grid[y][x],
+            neighborhood.clone(),
+            config,
+        )
+    };
+    let mut uncached = create(&grid, uncached_config);
+    let mut lru = create(
+        &grid,
+        PathCacheConfig {
+            path_lru_budget: 1 << 20,
+            ..uncached_config
+        },
+    );
+    let mut small_lru = create(
+        &grid,
+        PathCacheConfig {
+            path_lru_budget: 1000,
+            ..uncached_config
+        },
+    );
+    assert_eq!(lru.memory_usage().lru_path_bytes, 0);
+
+    let queries = [((0, 0), (29, 29)), ((29, 0), (0, 29)), ((15, 3), (1, 28))];
+    let check = |uncached: &PathCache<_>, lru: &PathCache<_>, grid: &Vec<Vec<Option<usize>>>| {
+        let cost_fn = |(x, y): (usize, usize)| grid[y][x];
+        for (start, goal) in queries {
+            // the way back uses the reversed Paths
+            for (start, goal) in [(start, goal), (goal, start)] {
+                let expected = uncached.find_path(start, goal, cost_fn).unwrap();
+                // the second time, the Paths are taken from the LRU
+                for _ in 0..2 {
+                    let path = lru.find_path(start, goal, cost_fn).unwrap();
+                    assert_eq!(path.cost(), expected.cost());
+                    assert_eq!(path.resolve(cost_fn), expected.clone().resolve(cost_fn));
+                }
+            }
+        }
+    };
+
+    check(&uncached, &lru, &grid);
+    let used = lru.memory_usage().lru_path_bytes;
+    assert!(used > 0);
+    assert_eq!(lru.memory_usage().path_bytes, 0);
+
+    check(&uncached, &small_lru, &grid);
+    let small_used = small_lru.memory_usage().lru_path_bytes;
+    assert!(small_used > 0 && small_used <= 1000);
+
+    // a clone has its own, empty LRU
+    assert_eq!(lru.clone().memory_usage().lru_path_bytes, 0);
+
+    // changing the Grid discards the Paths in the changed Chunks
+    grid[12][12] = None;
+    grid[13][14] = Some(5);
+    let changes = [(10, 11), (14, 14)];
+    for cache in [&mut uncached, &mut lru, &mut small_lru] {
+        cache.tiles_changed(&changes, |(x, y)| grid[y][x]);
+    }
+    assert!(lru.memory_usage().lru_path_bytes < used);
+    check(&uncached, &lru, &grid);
+    check(&uncached, &small_lru, &grid);
+}
+
+#[test]
+fn find_nearest_matching() {
+    let (width, height) = (40, 40);
+    let cost_fn = |(x, y): (usize, usize)| match (x % 9, y % 7) {
+        (4, 0..=4) | (0..=5, 3) if x != 22 => None,
+        _ => Some(1 + (x * 3 + y) % 4),
+    };
+    let pathfinding = PathCache::new(
+        (width, height),
+        cost_fn,
+        MooreNeighborhood::new(width, height),
+        PathCacheConfig::with_chunk_size(6),
+    );
+    let all_tiles: Vec<_> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .collect();
+
+    let predicates: [&dyn Fn((usize, usize)) -> bool; 4] = [
+        &|(x, y)| x == 33 && y == 35,
+        &|(x, y)| (x * 7 + y * 13) % 97 == 5,
+        // solid Tiles
+        &|(x, y)| x == 31 && y == 3,
+        &|_| false,
+    ];
+    for start in [(0, 0), (20, 21), (39, 10)] {
+        for predicate in predicates {
+            let goals: Vec<_> = all_tiles
+                .iter()
+                .copied()
+                .filter(|&p| predicate(p))
+                .collect();
+            let expected = pathfinding.find_closest_goal(start, &goals, cost_fn);
+
+            let nearest = pathfinding.find_nearest_matching(start, predicate, cost_fn, usize::MAX);
+            // find_closest_goal only considers the goals near the first Node that it reaches
+            assert_eq!(nearest.is_some(), expected.is_some());
+            if let (Some((_, nearest)), Some((_, expected))) = (&nearest, &expected) {
+                assert!(nearest.cost() <= expected.cost());
+            }
+            if let Some((goal, path)) = nearest {
+                assert!(predicate(goal));
+                assert_eq!(path.clone().last(), Some(goal));
+
+                let limited =
+                    pathfinding.find_nearest_matching(start, predicate, cost_fn, path.cost());
+                assert_eq!(limited.unwrap().1.cost(), path.cost());
+                if path.cost() > 0 {
+                    let limited = pathfinding.find_nearest_matching(
+                        start,
+                        predicate,
+                        cost_fn,
+                        path.cost() - 1,
+                    );
+                    assert!(limited.is_none());
+                }
+            }
+        }
+    }
+
+    // matching the start
+    let (goal, path) = pathfinding
+        .find_nearest_matching((20, 21), |_| true, cost_fn, 0)
+        .unwrap();
+    assert_eq!(goal, (20, 21));
+    assert_eq!(path.cost(), 0);
+}
+
+#[test]
+fn find_nearest_matching_across_chunks() {
+    // the matching Tile is part of a wall on the border of the Chunks, right next to start
+    let (width, height) = (6, 3);
+    let cost_fn = |(x, _): (usize, usize)| if x == 3 { None } else { Some(1) };
+    let pathfinding = PathCache::new(
+        (width, height),
+        cost_fn,
+        ManhattanNeighborhood::new(width, height),
+        PathCacheConfig::with_chunk_size(3),
+    );
+    let predicate = |pos| pos == (3, 1) || pos == (0, 2);
+
+    let (goal, path) = pathfinding
+        .find_nearest_matching((2, 1), predicate, cost_fn, usize::MAX)
+        .unwrap();
+    assert_eq!(goal, (3, 1));
+    assert_eq!(path.cost(), 1);
+    assert_eq!(path.collect::<Vec<_>>(), [(3, 1)]);
+
+    let (goal, _) = pathfinding
+        .find_nearest_matching((2, 1), predicate, cost_fn, 1)
+        .unwrap();
+    assert_eq!(goal, (3, 1));
+    assert!(pathfinding
+        .find_nearest_matching((2, 1), predicate, cost_fn, 0)
+        .is_none());
+}
+
+#[test]
+fn find_nearest_tagged() {
+    let (width, height) = (40, 40);
+    let cost_fn = |(x, y): (usize, usize)| match (x % 9, y % 7) {
+        (4, 0..=4) | (0..=5, 3) if x != 22 => None,
+        _ => Some(1 + (x * 3 + y) % 4),
+    };
+    let mut pathfinding = PathCache::new(
+        (width, height),
+        cost_fn,
+        MooreNeighborhood::new(width, height),
+        PathCacheConfig::with_chunk_size(6),
+    );
+    let all_tiles: Vec<_> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .collect();
+
+    const WATER: u64 = 1 << 0;
+    const ENEMY: u64 = 1 << 5;
+    let initial_tags = |(x, y): (usize, usize)| {
+        let mut tags = 0;
+        if (x * 7 + y * 13) % 97 == 5 {
+            tags |= WATER;
+        }
+        if (x, y) == (33, 35) || (x, y) == (31, 3) {
+            tags |= ENEMY;
+        }
+        tags
+    };
+    pathfinding.tag_tiles(&all_tiles, initial_tags);
+
+    let check = |pathfinding: &PathCache<_>, get_tags: &dyn Fn((usize, usize)) -> u64| {
+        for start in [(0, 0), (20, 21), (39, 10)] {
+            for tags in [WATER, ENEMY, WATER | ENEMY, 1 << 10] {
+                let predicate = |pos| get_tags(pos) & tags != 0;
+                let expected =
+                    pathfinding.find_nearest_matching(start, predicate, cost_fn, usize::MAX);
+                let nearest = pathfinding.find_nearest_tagged(start, tags, cost_fn, usize::MAX);
+                assert_eq!(
+                    nearest.as_ref().map(|(_, path)| path.cost()),
+                    expected.as_ref().map(|(_, path)| path.cost())
+                );
+                if let Some((goal, path)) = nearest {
+                    assert!(predicate(goal));
+                    assert_eq!(path.clone().last(), Some(goal));
+                }
+            }
+        }
+    };
+    check(&pathfinding, &initial_tags);
+    for &pos in &all_tiles {
+        assert_eq!(pathfinding.tags_at(pos), initial_tags(pos));
+    }
+
+    // untag some of the Tiles
+    let changed: Vec<_> = all_tiles.iter().copied().filter(|&(x, _)| x < 25).collect();
+    let new_tags = |(x, y): (usize, usize)| {
+        if x < 25 {
+            initial_tags((x, y)) & !WATER
+        } else {
+            initial_tags((x, y))
+        }
+    };
+    pathfinding.tag_tiles(&changed, new_tags);
+    check(&pathfinding, &new_tags);
+
+    let memory = pathfinding.memory_usage();
+    pathfinding.tag_tiles(&all_tiles, |_| 0);
+    assert_eq!(pathfinding.tags_at((33, 35)), 0);
+    assert!(pathfinding
+        .find_nearest_tagged((0, 0), u64::MAX, cost_fn, usize::MAX)
+        .is_none());
+    assert!(memory.hash_map_bytes > 0);
+}
+
+#[test]
+fn find_nearest_tagged_many_chunks() {
+    // every 8x8 Chunk has a tagged Tile that is walled in except for one expensive Tile, so
+    // that the far away (90, 5) is closer than any of them
+    let (width, height) = (96, 96);
+    let cost_fn = |(x, y): (usize, usize)| match (x % 8, y % 8) {
+        (3, 3) => Some(1),
+        (3, 4) => Some(500),
+        (2..=4, 2..=4) => None,
+        _ => Some(1 + (x + y) % 3),
+    };
+    let mut pathfinding = PathCache::new(
+        (width, height),
+        cost_fn,
+        ManhattanNeighborhood::new(width, height),
+        PathCacheConfig::with_chunk_size(8),
+    );
+    const CHEST: u64 = 1 << 3;
+    let tagged: Vec<_> = (0..height / 8)
+        .flat_map(|y| (0..width / 8).map(move |x| (x * 8 + 3, y * 8 + 3)))
+        .chain(std::iter::once((90, 5)))
+        .collect();
+    pathfinding.tag_tiles(&tagged, |_| CHEST);
+
+    for start in [(0, 0), (47, 40), (95, 95)] {
+        let (goal, path) = pathfinding
+            .find_nearest_tagged(start, CHEST, cost_fn, usize::MAX)
+            .unwrap();
+        assert_eq!(goal, (90, 5));
+
+        let points: Vec<_> = path.clone().collect();
+        assert_eq!(points[points.len() - 1], goal);
+        let mut previous = start;
+        let mut cost = 0;
+        for &pos in &points {
+            assert_eq!(previous.0.abs_diff(pos.0) + previous.1.abs_diff(pos.1), 1);
+            cost += cost_fn(previous).unwrap();
+            previous = pos;
+        }
+        assert_eq!(path.cost(), cost);
+
+        let direct = pathfinding.find_path(start, goal, cost_fn).unwrap();
+        assert!(path.cost() <= direct.cost());
+
+        // too far away
+        assert!(pathfinding
+            .find_nearest_tagged(start, CHEST, cost_fn, path.cost() - 1)
+            .is_none());
+    }
+}
+
+#[test]
+fn find_nearest_tagged_across_chunks() {
+    // the tagged Tile below start is part of the wall between the upper and lower Chunks
+    let (width, height) = (3, 6);
+    let cost_fn = |(_, y): (usize, usize)| if y == 3 { None } else { Some(1) };
+    let mut pathfinding = PathCache::new(
+        (width, height),
+        cost_fn,
+        MooreNeighborhood::new(width, height),
+        PathCacheConfig::with_chunk_size(3),
+    );
+    const CHEST: u64 = 1 << 3;
+    pathfinding.tag_tiles(&[(1, 3), (0, 0), (2, 5)], |_| CHEST);
+
+    let (goal, path) = pathfinding
+        .find_nearest_tagged((1, 2), CHEST, cost_fn, usize::MAX)
+        .unwrap();
+    assert_eq!(goal, (1, 3));
+    assert_eq!(path.cost(), 1);
+    assert_eq!(path.collect::<Vec<_>>(), [(1, 3)]);
+
+    let (goal, _) = pathfinding
+        .find_nearest_tagged((1, 2), CHEST, cost_fn, 1)
+        .unwrap();
+    assert_eq!(goal, (1, 3));
+    assert!(pathfinding
+        .find_nearest_tagged((1, 2), CHEST, cost_fn, 0)
+        .is_none());
+}
+
+#[test]
+fn estimate_cost() {
+    let (width, height) = (40, 40);
+    let cost_fn = |(x, y): (usize, usize)| match (x % 9, y % 7) {
+        (4, 0..=4) | (0..=5, 3) if x != 22 => None,
+        _ => Some(1 + (x * 3 + y) % 4),
+    };
+    for a_star_fallback in [true, false] {
+        let pathfinding = PathCache::new(
+            (width, height),
+            cost_fn,
+            MooreNeighborhood::new(width, height),
+            PathCacheConfig {
+                chunk_size: 6,
+                a_star_fallback,
+                ..Default::default()
+            },
+        );
+        let points = [
+            (0, 0),
+            (20, 21),
+            (39, 10),
+            (4, 5),
+            (31, 3),
+            (22, 38),
+            (13, 0),
+        ];
+        for start in points {
+            for goal in points {
+                let estimate = pathfinding.estimate_cost(start, goal, cost_fn);
+                let path = pathfinding.find_path(start, goal, cost_fn);
+                assert_eq!(
+                    estimate.is_some(),
+                    path.is_some(),
+                    "{:?} -> {:?}",
+                    start,
+                    goal
+                );
+                if let (Some(estimate), Some(path)) = (estimate, path) {
+                    assert!(estimate >= path.cost(), "{:?} -> {:?}", start, goal);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn cost_matrix() {
+    let (width, height) = (40, 40);
+    let cost_fn = |(x, y): (usize, usize)| match (x % 9, y % 7) {
+        (4, 0..=4) | (0..=5, 3) if x != 22 => None,
+        _ => Some(1 + (x * 3 + y) % 4),
+    };
+    let pathfinding = PathCache::new(
+        (width, height),
+        cost_fn,
+        MooreNeighborhood::new(width, height),
+        PathCacheConfig::with_chunk_size(6),
+    );
+    let sources = [(0, 0), (20, 21), (39, 10), (4, 5), (31, 3), (21, 22)];
+    let mut targets = vec![
+        (22, 38),
+        (13, 0),
+        (20, 21),
+        (4, 0),
+        (31, 3),
+        (0, 1),
+        (50, 50),
+    ];
+    // Nodes as targets
+    targets.extend(pathfinding.inspect_nodes().take(3).map(|node| node.pos()));
+
+    let matrix = pathfinding.cost_matrix(&sources, &targets, cost_fn);
+    let sequential = pathfinding.cost_matrix_with_fn_mut(&sources, &targets, cost_fn);
+    assert_eq!(matrix, sequential);
+    assert_eq!(matrix.len(), sources.len());
+
+    for (&source, row) in sources.iter().zip(&matrix) {
+        assert_eq!(row.len(), targets.len());
+        let exact = pathfinding.reachable_within(source, usize::MAX, cost_fn);
+        for (&target, &cost) in targets.iter().zip(row) {
+            let path = pathfinding.find_path(source, target, cost_fn);
+            assert_eq!(
+                cost.is_some(),
+                path.is_some(),
+                "{:?} -> {:?}",
+                source,
+                target
+            );
+            if let Some(cost) = cost {
+                let estimate = pathfinding.estimate_cost(source, target, cost_fn).unwrap();
+                assert!(cost <= estimate, "{:?} -> {:?}", source, target);
+                if let Some(&exact) = exact.get(&target) {
+                    assert!(cost >= exact, "{:?} -> {:?}", source, target);
+                }
+            }
+        }
+    }
+    assert_eq!(matrix[1][2], Some(0));
+}
+
+/// A Grid with walls that only have gaps at `x == 22`, where walls cost 0, and some points on
+/// either side of them
+fn walled_grid() -> (Vec<Vec<usize>>, [(usize, usize); 7]) {
+    let (width, height) = (30, 30);
+    let mut grid = vec![vec![0usize; width]; height];
+    for (y, row) in grid.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            *cell = match (x % 9, y % 7) {
+                (4, 0..=4) | (0..=5, 3) if x != 22 => 0,
+                _ => 1 + (x * 7 + y * 3) % 11,
+            };
+        }
+    }
+    let points = [
+        (0, 0),
+        (20, 21),
+        (29, 10),
+        (4, 5),
+        (25, 3),
+        (22, 28),
+        (13, 0),
+    ];
+    (grid, points)
+}
+
+fn grid_cost_fn(grid: &[Vec<usize>]) -> impl '_ + Sync + Fn((usize, usize)) -> Option<usize> {
+    move |(x, y)| Some(grid[y][x]).filter(|&cost| cost != 0)
+}
+
+#[test]
+fn distance_index() {
+    let (mut grid, points) = walled_grid();
+    let (width, height) = (grid[0].len(), grid.len());
+    let mut pathfinding = PathCache::new(
+        (width, height),
+        grid_cost_fn(&grid),
+        MooreNeighborhood::new(width, height),
+        PathCacheConfig {
+            chunk_size: 6,
+            a_star_fallback: false,
+            ..Default::default()
+        },
+    );
+
+    let check = |pathfinding: &PathCache<_>, grid: &[Vec<usize>]| {
+        for start in points {
+            for goal in points {
+                let path = pathfinding.find_path(start, goal, grid_cost_fn(grid));
+                let estimate = pathfinding.estimate_cost(start, goal, grid_cost_fn(grid));
+                assert_eq!(path.is_some(), estimate.is_some());
+                if let (Some(path), Some(estimate)) = (path, estimate) {
+                    assert!(estimate >= path.cost());
+                    let points: Vec<_> = path.collect();
+                    assert_eq!(points.last(), Some(&goal));
+                }
+            }
+        }
+    };
+
+    let without_index = points
+        .iter()
+        .flat_map(|&start| points.iter().map(move |&goal| (start, goal)))
+        .map(|(start, goal)| pathfinding.estimate_cost(start, goal, grid_cost_fn(&grid)))
+        .collect::<Vec<_>>();
+
+    assert_eq!(pathfinding.memory_usage().distance_index_bytes, 0);
+    pathfinding.build_distance_index();
+    assert!(pathfinding.has_distance_index());
+    assert!(pathfinding.memory_usage().distance_index_bytes > 0);
+    check(&pathfinding, &grid);
+
+    let with_index = points
+        .iter()
+        .flat_map(|&start| points.iter().map(move |&goal| (start, goal)))
+        .map(|(start, goal)| pathfinding.estimate_cost(start, goal, grid_cost_fn(&grid)))
+        .collect::<Vec<_>>();
+    for (with, without) in with_index.iter().zip(&without_index) {
+        assert_eq!(with.is_some(), without.is_some());
+        // the index always finds the cheapest Path between the Nodes
+        assert!(with <= without);
+    }
+
+    grid[3][22] = 0;
+    pathfinding.tiles_changed(&[(22, 3)], grid_cost_fn(&grid));
+    // only rebuilt once a query needs it
+    assert!(pathfinding.has_distance_index());
+    assert_eq!(pathfinding.memory_usage().distance_index_bytes, 0);
+    check(&pathfinding, &grid);
+    assert!(pathfinding.memory_usage().distance_index_bytes > 0);
+
+    pathfinding.clear_distance_index();
+    assert!(!pathfinding.has_distance_index());
+    assert_eq!(pathfinding.memory_usage().distance_index_bytes, 0);
+}
+
+#[test]
+fn contraction_hierarchy() {
+    let (mut grid, points) = walled_grid();
+    let (width, height) = (grid[0].len(), grid.len());
+    let mut pathfinding = PathCache::new(
+        (width, height),
+        grid_cost_fn(&grid),
+        MooreNeighborhood::new(width, height),
+        PathCacheConfig {
+            chunk_size: 5,
+            a_star_fallback: false,
+            ..Default::default()
+        },
+    );
+    let pairs: Vec<_> = points
+        .iter()
+        .flat_map(|&start| points.iter().map(move |&goal| (start, goal)))
+        .collect();
+
+    let estimates = |pathfinding: &PathCache<_>, grid: &[Vec<usize>]| {
+        pairs
+            .iter()
+            .map(|&(start, goal)| pathfinding.estimate_cost(start, goal, grid_cost_fn(grid)))
+            .collect::<Vec<_>>()
+    };
+    let check = |pathfinding: &PathCache<_>, grid: &[Vec<usize>]| {
+        for &(start, goal) in &pairs {
+            let path = pathfinding.find_path(start, goal, grid_cost_fn(grid));
+            let estimate = pathfinding.estimate_cost(start, goal, grid_cost_fn(grid));
+            assert_eq!(path.is_some(), estimate.is_some());
+            if let (Some(path), Some(estimate)) = (path, estimate) {
+                assert!(estimate >= path.cost());
+                assert_eq!(path.last(), Some(goal));
+            }
+        }
+    };
+
+    let without_hierarchy = estimates(&pathfinding, &grid);
+
+    pathfinding.build_contraction_hierarchy();
+    assert!(pathfinding.has_contraction_hierarchy());
+    assert!(pathfinding.memory_usage().contraction_hierarchy_bytes > 0);
+    check(&pathfinding, &grid);
+
+    // the hierarchy finds the cheapest Path between the Nodes, just like the distance index
+    let with_hierarchy = estimates(&pathfinding, &grid);
+    pathfinding.clear_contraction_hierarchy();
+    pathfinding.build_distance_index();
+    assert_eq!(with_hierarchy, estimates(&pathfinding, &grid));
+    pathfinding.clear_distance_index();
+    for (with, without) in with_hierarchy.iter().zip(&without_hierarchy) {
+        assert_eq!(with.is_some(), without.is_some());
+        assert!(with <= without);
+    }
+
+    // rebuilt by the first query after the change
+    pathfinding.build_contraction_hierarchy();
+    grid[3][22] = 0;
+    pathfinding.tiles_changed(&[(22, 3)], grid_cost_fn(&grid));
+    assert!(pathfinding.has_contraction_hierarchy());
+    assert_eq!(pathfinding.memory_usage().contraction_hierarchy_bytes, 0);
+    check(&pathfinding, &grid);
+    assert!(pathfinding.memory_usage().contraction_hierarchy_bytes > 0);
+
+    pathfinding.clear_contraction_hierarchy();
+    assert_eq!(pathfinding.memory_usage().contraction_hierarchy_bytes, 0);
+}
+
+#[test]
+fn landmarks() {
+    let (mut grid, points) = walled_grid();
+    let (width, height) = (grid[0].len(), grid.len());
+    let config = PathCacheConfig {
+        chunk_size: 5,
+        a_star_fallback: false,
+        ..Default::default()
+    };
+    let create = |grid: &[Vec<usize>], landmarks| {
+        PathCache::new(
+            (width, height),
+            grid_cost_fn(grid),
+            MooreNeighborhood::new(width, height),
+            PathCacheConfig {
+                landmarks,
+                ..config
+            },
+        )
+    };
+    let mut plain = create(&grid, 0);
+    let mut pathfinding = create(&grid, 6);
+    // only calculated once a search needs them
+    assert_eq!(pathfinding.memory_usage().landmark_bytes, 0);
+
+    // the landmarks only guide the search, so the costs are the same
+    let check = |plain: &PathCache<_>, pathfinding: &PathCache<_>, grid: &[Vec<usize>]| {
+        for &start in &points {
+            for &goal in &points {
+                let expected = plain.estimate_cost(start, goal, grid_cost_fn(grid));
+                assert_eq!(
+                    pathfinding.estimate_cost(start, goal, grid_cost_fn(grid)),
+                    expected
+                );
+                let path = pathfinding.find_path(start, goal, grid_cost_fn(grid));
+                assert_eq!(path.is_some(), expected.is_some());
+                if let Some(path) = path {
+                    assert_eq!(path.last(), Some(goal));
+                }
+            }
+        }
+    };
+    check(&plain, &pathfinding, &grid);
+    assert_eq!(plain.memory_usage().landmark_bytes, 0);
+    assert!(pathfinding.memory_usage().landmark_bytes > 0);
+
+    // the landmarks are recalculated for the new Graph by the next search, so that a batch of
+    // changes doesn't pay for them every time
+    grid[3][22] = 0;
+    grid[10][13] = 0;
+    plain.tiles_changed(&[(22, 3), (13, 10)], grid_cost_fn(&grid));
+    for &pos in &[(22, 3), (13, 10)] {
+        pathfinding.tiles_changed(&[pos], grid_cost_fn(&grid));
+        assert_eq!(pathfinding.memory_usage().landmark_bytes, 0);
+    }
+    check(&plain, &pathfinding, &grid);
+    assert!(pathfinding.memory_usage().landmark_bytes > 0);
+}
+
+#[test]
+fn heuristic_weight() {
+    let (width, height) = (30, 30);
+    let cost_fn = |(x, y): (usize, usize)| match (x % 9, y % 7) {
+        (4, 0..=4) | (0..=5, 3) if x != 22 => None,
+        _ => Some(1 + (x * 7 + y * 3) % 11),
+    };
+    let create = |heuristic_weight, landmarks| {
+        PathCache::new(
+            (width, height),
+            cost_fn,
+            MooreNeighborhood::new(width, height),
+            PathCacheConfig {
+                chunk_size: 5,
+                heuristic_weight,
+                landmarks,
+                ..Default::default()
+            },
+        )
+    };
+    let optimal = create(1.0, 0);
+
+    let points = [(0, 0), (20, 21), (29, 10), (4, 5), (25, 3), (22, 28)];
+    // the weight also applies to the bounds from the landmarks, with the same limit
+    for landmarks in [0, 6] {
+        let weighted = create(2.0, landmarks);
+        for &start in &points {
+            for &goal in &points {
+                let expected = optimal.estimate_cost(start, goal, cost_fn);
+                let estimate = weighted.estimate_cost(start, goal, cost_fn);
+                assert_eq!(estimate.is_some(), expected.is_some());
+                if let (Some(estimate), Some(expected)) = (estimate, expected) {
+                    assert!(estimate >= expected);
+                    assert!(estimate <= 2 * expected);
+                }
+
+                let path = weighted.find_path(start, goal, cost_fn);
+                assert_eq!(path.is_some(), expected.is_some());
+                if let Some(path) = path {
+                    assert_eq!(path.last(), Some(goal));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn path_query() {
+    use hierarchical_pathfinding::QueryStatus;
+
+    let (width, height) = (30, 30);
+    let cost_fn = |(x, y): (usize, usize)| match (x % 9, y % 7) {
+        (4, 0..=4) | (0..=5, 3) if x != 22 => None,
+        (8, 5) => None,
+        _ => Some(1 + (x * 7 + y * 3) % 11),
+    };
+    let mut pathfinding = PathCache::new(
+        (width, height),
+        cost_fn,
+        MooreNeighborhood::new(width, height),
+        PathCacheConfig::with_chunk_size(5),
+    );
+
+    let points = [
+        (0, 0),
+        (20, 21),
+        (29, 10),
+        (4, 5),
+        (25, 3),
+        (8, 5),
+        (23, 18),
+    ];
+    let check = |pathfinding: &PathCache<_>| {
+        let mut max_steps = 0;
+        for &start in &points {
+            for &goal in &points {
+                let expected = pathfinding.find_path(start, goal, cost_fn);
+
+                let mut query = pathfinding.query_path(start, goal, cost_fn);
+                let mut steps = 0;
+                let path = loop {
+                    steps += 1;
+                    match query.step(3) {
+                        QueryStatus::InProgress => continue,
+                        QueryStatus::Found(path) => break Some(path),
+                        QueryStatus::NoPath => break None,
+                    }
+                };
+                max_steps = max_steps.max(steps);
+                assert_eq!(path.is_some(), expected.is_some());
+                if let (Some(path), Some(expected)) = (path, expected) {
+                    assert_eq!(path.cost(), expected.cost());
+                    assert_eq!(path.collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+                }
+            }
+        }
+        max_steps
+    };
+    // long Paths can't be found with only a few expansions
+    assert!(check(&pathfinding) > 1);
+
+    // the distance index replaces the search on the Graph
+    pathfinding.build_distance_index();
+    check(&pathfinding);
+
+    // a query doesn't rebuild a discarded index, since that could take longer than its budget
+    pathfinding.tiles_changed(&[(22, 3)], cost_fn);
+    let expected = pathfinding.find_path((20, 21), (4, 5), cost_fn).unwrap();
+    pathfinding.tiles_changed(&[(22, 3)], cost_fn);
+    let mut query = pathfinding.query_path((20, 21), (4, 5), cost_fn);
+    let path = loop {
+        if let QueryStatus::Found(path) = query.step(3) {
+            break path;
+        }
+    };
+    assert_eq!(path.cost(), expected.cost());
+    assert_eq!(pathfinding.memory_usage().distance_index_bytes, 0);
+
+    // short Paths are searched on the Grid, which is limited as well
+    let pathfinding: PathCache<_> = PathCache::new(
+        (width, height),
+        |_| Some(1),
+        MooreNeighborhood::new(width, height),
+        PathCacheConfig::with_chunk_size(5),
+    );
+    let expected = pathfinding.find_path((2, 3), (8, 7), |_| Some(1)).unwrap();
+    let mut query = pathfinding.query_path((2, 3), (8, 7), |_| Some(1));
+    assert!(matches!(query.step(3), QueryStatus::InProgress));
+    let path = loop {
+        if let QueryStatus::Found(path) = query.step(3) {
+            break path;
+        }
+    };
+    assert_eq!(path.cost(), expected.cost());
+    assert_eq!(path.collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic(expected = "only supports GoalMode::Exact")]
+fn path_query_goal_mode() {
+    // the other goal modes search for multiple Tiles at once, which can't be split up
+    let (width, height) = (10, 10);
+    let cost_fn = |_| Some(1);
+    let pathfinding: PathCache<_> = PathCache::new(
+        (width, height),
+        cost_fn,
+        MooreNeighborhood::new(width, height),
+        PathCacheConfig {
+            goal_mode: GoalMode::AdjacentToGoal,
+            ..PathCacheConfig::with_chunk_size(5)
+        },
+    );
+    pathfinding.query_path((0, 0), (8, 8), cost_fn);
+}
+
+#[test]
+fn find_path_or_closest() {
+    let (width, height) = (20, 20);
+    let cost_fn = |(x, y): (usize, usize)| {
+        // a walled off room in the bottom right corner and some walls in between
+        if (x == 13 && y >= 13) || (y == 13 && x >= 13) || (x == 6 && y < 15) {
+            None
+        } else {
+            Some(1 + (x + y) % 3)
+        }
+    };
+    let neighborhood = ManhattanNeighborhood::new(width, height);
+    let pathfinding = PathCache::new(
+        (width, height),
+        cost_fn,
+        neighborhood.clone(),
+        PathCacheConfig::with_chunk_size(5),
+    );
+
+    // all Tiles that can be reached from start
+    let reachable = |start: (usize, usize)| {
+        let mut seen = vec![start];
+        let mut next = vec![start];
+        let mut neighbors = vec![];
+        while let Some(pos) = next.pop() {
+            neighbors.clear();
+            neighborhood.get_all_neighbors(pos, &mut neighbors);
+            for &other in &neighbors {
+                if cost_fn(other).is_some() && !seen.contains(&other) {
+                    seen.push(other);
+                    next.push(other);
+                }
+            }
+        }
+        seen
+    };
+
+    for (start, goal) in [
+        ((0, 0), (17, 16)),
+        ((2, 18), (19, 19)),
+        ((15, 15), (0, 0)),
+        ((15, 15), (18, 19)),
+        ((10, 2), (19, 14)),
+    ] {
+        let (tile, path) = pathfinding
+            .find_path_or_closest(start, goal, cost_fn)
+            .unwrap();
+        let closest = reachable(start)
+            .into_iter()
+            .map(|pos| neighborhood.heuristic(pos, goal))
+            .min()
+            .unwrap();
+        assert_eq!(neighborhood.heuristic(tile, goal), closest);
+
+        let expected = pathfinding.find_path(start, tile, cost_fn).unwrap();
+        assert_eq!(path.cost(), expected.cost());
+        let points = path.collect::<Vec<_>>();
+        assert_eq!(points.last().copied().unwrap_or(start), tile);
+    }
+
+    // cannot start on a wall
+    assert!(pathfinding
+        .find_path_or_closest((13, 15), (0, 0), cost_fn)
+        .is_none());
+}
+
+#[test]
+fn goal_mode() {
+    let (width, height) = (20, 20);
+    let cost_fn = |(x, y): (usize, usize)| {
+        if (x == 10 && y != 3) || (x == 4 && y >= 6 && y <= 8) {
+            None
+        } else {
+            Some(1 + (x * 3 + y) % 4)
+        }
+    };
+    let neighborhood = ManhattanNeighborhood::new(width, height);
+    let exact = PathCache::new(
+        (width, height),
+        cost_fn,
+        neighborhood.clone(),
+        PathCacheConfig::with_chunk_size(5),
+    );
+    let new_cache = |goal_mode| {
+        PathCache::new(
+            (width, height),
+            cost_fn,
+            neighborhood.clone(),
+            PathCacheConfig {
+                goal_mode,
+                ..PathCacheConfig::with_chunk_size(5)
+            },
+        )
+    };
+
+    // the cheapest Path to any of the given Tiles, ending on the returned Tile
+    let check = |pathfinding: &PathCache<ManhattanNeighborhood>,
+                 start: (usize, usize),
+                 goal: (usize, usize),
+                 tiles: Vec<(usize, usize)>| {
+        let expected = exact
+            .find_paths(start, &tiles, cost_fn)
+            .into_iter()
+            .map(|(_, path)| path.cost())
+            .min()
+            .unwrap();
+        let path = pathfinding.find_path(start, goal, cost_fn).unwrap();
+        assert_eq!(path.cost(), expected, "{:?} -> {:?}", start, goal);
+        let end = path.collect::<Vec<_>>().last().copied().unwrap_or(start);
+        assert!(tiles.contains(&end), "{:?} ends on {:?}", goal, end);
+    };
+
+    let adjacent = new_cache(GoalMode::AdjacentToGoal);
+    let range = new_cache(GoalMode::WithinRange(2));
+    for (start, goal) in [
+        ((0, 0), (4, 7)),
+        ((0, 0), (10, 12)),
+        ((15, 18), (2, 2)),
+        ((3, 19), (4, 6)),
+    ] {
+        let mut neighbors = vec![];
+        neighborhood.get_all_neighbors(goal, &mut neighbors);
+        neighbors.retain(|&pos| cost_fn(pos).is_some());
+        check(&adjacent, start, goal, neighbors);
+
+        let in_range = (0..width)
+            .flat_map(|x| (0..height).map(move |y| (x, y)))
+            .filter(|&pos| neighborhood.heuristic(pos, goal) <= 2 && cost_fn(pos).is_some())
+            .collect();
+        check(&range, start, goal, in_range);
+    }
+
+    // with Exact, the Path walks into a solid goal
+    let path = exact.find_path((0, 0), (10, 12), cost_fn).unwrap();
+    assert_eq!(path.collect::<Vec<_>>().last(), Some(&(10, 12)));
+
+    // start is already next to the goal
+    let path = adjacent.find_path((3, 7), (4, 7), cost_fn).unwrap();
+    assert_eq!(path.cost(), 0);
+    assert_eq!(path.collect::<Vec<_>>(), [(3, 7)]);
+
+    // every goal maps to its cheapest Tile
+    let goals = [(10, 12), (4, 7), (18, 0)];
+    let paths = adjacent.find_paths((0, 0), &goals, cost_fn);
+    assert_eq!(paths.len(), goals.len());
+    for (goal, path) in paths {
+        let expected = adjacent.find_path((0, 0), goal, cost_fn).unwrap();
+        assert_eq!(path.cost(), expected.cost());
+    }
+    let (goal, path) = adjacent.find_closest_goal((0, 0), &goals, cost_fn).unwrap();
+    assert_eq!(goal, (4, 7));
+    let end = path.collect::<Vec<_>>().last().copied().unwrap();
+    assert_eq!(neighborhood.heuristic(end, goal), 1);
+
+    // the gap in the wall only has walkable neighbors to the sides
+    let path = adjacent.find_path((0, 0), (10, 3), cost_fn).unwrap();
+    assert_eq!(path.collect::<Vec<_>>().last(), Some(&(9, 3)));
+
+    // goals outside of the Grid have no Tiles next to them
+    assert!(adjacent.find_path((0, 0), (25, 3), cost_fn).is_none());
+
+    // the mode of a single search overrides the one of the config
+    for (mode, cache) in [
+        (GoalMode::AdjacentToGoal, &adjacent),
+        (GoalMode::WithinRange(2), &range),
+    ] {
+        for goal in goals {
+            let path = exact
+                .find_path_with_goal_mode((0, 0), goal, mode, cost_fn)
+                .unwrap();
+            let expected = cache.find_path((0, 0), goal, cost_fn).unwrap();
+            assert_eq!(path.cost(), expected.cost());
+        }
+        let paths = exact.find_paths_with_goal_mode((0, 0), &goals, mode, cost_fn);
+        let expected = cache.find_paths((0, 0), &goals, cost_fn);
+        assert_eq!(paths.len(), expected.len());
+        for (goal, path) in paths {
+            assert_eq!(path.cost(), expected[&goal].cost());
+        }
+        let (goal, path) = exact
+            .find_closest_goal_with_goal_mode((0, 0), &goals, mode, cost_fn)
+            .unwrap();
+        let (expected_goal, expected) = cache.find_closest_goal((0, 0), &goals, cost_fn).unwrap();
+        assert_eq!(goal, expected_goal);
+        assert_eq!(path.cost(), expected.cost());
+    }
+    let path = adjacent
+        .find_path_with_goal_mode((0, 0), (10, 12), GoalMode::Exact, cost_fn)
+        .unwrap();
+    assert_eq!(path.collect::<Vec<_>>().last(), Some(&(10, 12)));
+}
+
+#[test]
+fn find_path_to_region() {
+    let (width, height) = (24, 24);
+    let cost_fn = |(x, y): (usize, usize)| {
+        if (x == 8 && y != 20) || (y == 12 && x > 8 && x != 17) {
+            None
+        } else {
+            Some(1 + (x + 2 * y) % 3)
+        }
+    };
+    let neighborhood = ManhattanNeighborhood::new(width, height);
+    let pathfinding = PathCache::new(
+        (width, height),
+        cost_fn,
+        neighborhood.clone(),
+        PathCacheConfig::with_chunk_size(5),
+    );
+
+    // the cost of the cheapest Path from start to every Tile
+    let costs = |start: (usize, usize)| {
+        let mut costs = std::collections::HashMap::new();
+        let mut next = std::collections::BinaryHeap::new();
+        next.push(std::cmp::Reverse((0, start)));
+        let mut neighbors = vec![];
+        while let Some(std::cmp::Reverse((cost, pos))) = next.pop() {
+            if costs.contains_key(&pos) {
+                continue;
+            }
+            costs.insert(pos, cost);
+            let step = if let Some(step) = cost_fn(pos) {
+                step
+            } else {
+                continue; // walked into a wall
+            };
+            neighbors.clear();
+            neighborhood.get_all_neighbors(pos, &mut neighbors);
+            for &other in &neighbors {
+                if !costs.contains_key(&other) {
+                    next.push(std::cmp::Reverse((cost + step, other)));
+                }
+            }
+        }
+        costs
+    };
+
+    let points = [(20, 20), (3, 22), (14, 2), (17, 12)];
+    let odd = |(x, y): (usize, usize)| x > 18 && (x + y) % 7 == 0;
+    let regions = [
+        Region::Rect {
+            pos: (15, 15),
+            size: (4, 3),
+        },
+        Region::Rect {
+            pos: (7, 3),
+            size: (3, 3),
+        },
+        Region::Points(&points),
+        Region::Predicate(&odd),
+    ];
+
+    for start in [(0, 0), (12, 3), (22, 14), (2, 21)] {
+        let costs = costs(start);
+        for region in &regions {
+            let (tile, path) = pathfinding
+                .find_path_to_region(start, *region, cost_fn)
+                .unwrap();
+            assert!(region.contains(tile));
+
+            let best = costs
+                .iter()
+                .filter(|(pos, _)| region.contains(**pos))
+                .map(|(_, cost)| *cost)
+                .min()
+                .unwrap();
+            assert!(path.cost() >= best);
+            assert!(path.cost() <= best * 3 / 2, "{:?} {:?}", start, region);
+
+            // the Path stops at the first Region Tile
+            let points = path.collect::<Vec<_>>();
+            assert_eq!(points.last().copied().unwrap_or(start), tile);
+            let entered = points.iter().position(|&pos| region.contains(pos));
+            assert_eq!(entered, Some(points.len() - 1));
+        }
+    }
+
+    // starting inside
+    let (tile, path) = pathfinding
+        .find_path_to_region((16, 16), regions[0], cost_fn)
+        .unwrap();
+    assert_eq!(tile, (16, 16));
+    assert_eq!(path.cost(), 0);
+
+    // nothing in the Grid
+    let outside = Region::Rect {
+        pos: (30, 5),
+        size: (2, 2),
+    };
+    assert!(pathfinding
+        .find_path_to_region((0, 0), outside, cost_fn)
+        .is_none());
+    assert!(pathfinding
+        .find_path_to_region((0, 0), Region::Points(&[]), cost_fn)
+        .is_none());
+}
+
+#[test]
+fn find_path_to_region_over_nodes() {
+    // 0 = solid
+    let grid: [[usize; 8]; 8] = [
+        [4, 2, 4, 2, 0, 0, 1, 4],
+        [0, 0, 1, 2, 2, 2, 2, 2],
+        [2, 3, 2, 3, 3, 0, 2, 4],
+        [1, 1, 4, 0, 0, 1, 1, 2],
+        [3, 3, 1, 0, 2, 4, 0, 2],
+        [1, 1, 4, 3, 3, 4, 1, 3],
+        [1, 2, 1, 1, 2, 1, 2, 4],
+        [4, 4, 1, 2, 1, 1, 2, 4],
+    ];
+    let (width, height) = (8, 8);
+    let cost_fn = |(x, y): (usize, usize)| (grid[y][x] > 0).then_some(grid[y][x]);
+    let pathfinding = PathCache::new(
+        (width, height),
+        cost_fn,
+        MooreNeighborhood::new(width, height),
+        PathCacheConfig::with_chunk_size(4),
+    );
+
+    // the Path over the abstract Graph crosses the Region before the Tile that it leads to
+    let region = Region::Rect {
+        pos: (1, 2),
+        size: (2, 2),
+    };
+    let (tile, path) = pathfinding
+        .find_path_to_region((1, 5), region, cost_fn)
+        .unwrap();
+    assert_eq!(tile, (1, 3));
+    assert_eq!(path.cost(), 2);
+    assert_eq!(path.collect::<Vec<_>>(), [(2, 4), (1, 3)]);
+}
+
+#[test]
+fn find_closest_start() {
+    let (width, height) = (24, 24);
+    // steep costs, so that the way back is much cheaper or more expensive than the way there
+    let cost_fn = |(x, y): (usize, usize)| {
+        if (x == 11 && y < 18) || (y == 5 && x > 11 && x != 20) {
+            None
+        } else if (1..4).contains(&x) && (1..4).contains(&y) && (x, y) != (2, 2) {
+            None // a cave around (2, 2)
+        } else {
+            Some(1 + x / 2 + (y % 4) * 3)
+        }
+    };
+    let neighborhood = ManhattanNeighborhood::new(width, height);
+    let pathfinding = PathCache::new(
+        (width, height),
+        cost_fn,
+        neighborhood,
+        PathCacheConfig::with_chunk_size(5),
+    );
+
+    let starts = [
+        (0, 0),
+        (22, 22),
+        (15, 2),
+        (2, 2),
+        (11, 3),
+        (6, 20),
+        (23, 10),
+    ];
+    for goal in [(16, 16), (5, 1), (21, 0), (0, 23), (12, 12)] {
+        let (start, path) = pathfinding
+            .find_closest_start(&starts, goal, cost_fn)
+            .unwrap();
+
+        let naive = starts
+            .iter()
+            .filter_map(|&start| pathfinding.find_path(start, goal, cost_fn))
+            .map(|path| path.cost())
+            .min()
+            .unwrap();
+        assert_eq!(path.cost(), naive, "{:?}", goal);
+
+        let expected = pathfinding.find_path(start, goal, cost_fn).unwrap();
+        assert_eq!(path.cost(), expected.cost());
+        let points = path.collect::<Vec<_>>();
+        assert_eq!(points.last(), Some(&goal));
+
+        // searching from the goal to the starts would use the costs of the opposite direction
+        let (_, back) = pathfinding
+            .find_closest_goal(goal, &starts, cost_fn)
+            .unwrap();
+        assert_ne!(back.cost(), expected.cost());
+    }
+
+    // (2, 2) can only reach itself
+    let (start, path) = pathfinding
+        .find_closest_start(&starts, (2, 2), cost_fn)
+        .unwrap();
+    assert_eq!(start, (2, 2));
+    assert_eq!(path.cost(), 0);
+    assert!(pathfinding
+        .find_closest_start(&[(2, 2), (11, 3)], (0, 0), cost_fn)
+        .is_none());
+    assert!(pathfinding
+        .find_closest_start(&starts, (30, 0), cost_fn)
+        .is_none());
+}
+
+#[test]
+fn find_closest_start_resolved() {
+    // 0 = solid
+    let grid: [[usize; 12]; 12] = [
+        [1, 3, 2, 1, 0, 2, 4, 0, 5, 2, 0, 1],
+        [5, 4, 5, 5, 1, 5, 1, 0, 0, 1, 3, 0],
+        [1, 1, 4, 3, 1, 2, 3, 5, 5, 4, 1, 3],
+        [3, 0, 0, 0, 0, 4, 2, 3, 4, 2, 3, 2],
+        [3, 4, 1, 4, 5, 5, 4, 0, 2, 4, 4, 1],
+        [0, 1, 2, 4, 5, 4, 4, 2, 3, 2, 0, 0],
+        [5, 1, 1, 4, 5, 4, 5, 2, 1, 5, 3, 2],
+        [2, 0, 1, 0, 0, 3, 0, 1, 4, 4, 2, 5],
+        [0, 2, 5, 5, 3, 0, 0, 5, 1, 1, 1, 2],
+        [3, 4, 1, 3, 0, 5, 1, 2, 0, 4, 0, 5],
+        [4, 0, 1, 5, 5, 1, 1, 5, 1, 3, 4, 5],
+        [3, 5, 2, 3, 2, 5, 3, 4, 2, 1, 2, 1],
+    ];
+    let (width, height) = (12, 12);
+    let cost_fn = |(x, y): (usize, usize)| (grid[y][x] > 0).then_some(grid[y][x]);
+    let pathfinding = PathCache::new(
+        (width, height),
+        cost_fn,
+        ManhattanNeighborhood::new(width, height),
+        PathCacheConfig::with_chunk_size(4),
+    );
+
+    // (1, 8) is the closest in the abstract Graph, but the resolved Path from (0, 6) is cheaper
+    let starts = [(2, 3), (0, 6), (1, 8), (9, 7)];
+    let goal = (5, 5);
+    let (start, path) = pathfinding
+        .find_closest_start(&starts, goal, cost_fn)
+        .unwrap();
+    assert_eq!(start, (0, 6));
+    assert_eq!(path.cost(), 20);
+    assert_eq!(path.clone().last(), Some(goal));
+
+    let naive = starts
+        .iter()
+        .filter_map(|&start| pathfinding.find_path(start, goal, cost_fn))
+        .map(|path| path.cost())
+        .min()
+        .unwrap();
+    assert_eq!(path.cost(), naive);
+}
+
+#[test]
+fn shared_search_context() {
+    let cost_fn = |(x, y): (usize, usize)| {
+        if (x == 7 && y != 2 && y != 13) || (y == 9 && x < 5) {
+            None
+        } else {
+            Some(1 + (x * y) % 5)
+        }
+    };
+    let small = PathCache::new(
+        (12, 10),
+        cost_fn,
+        ManhattanNeighborhood::new(12, 10),
+        PathCacheConfig::with_chunk_size(4),
+    );
+    // without the fallback, long Paths use only the Graph
+    let large = PathCache::new(
+        (30, 25),
+        cost_fn,
+        ManhattanNeighborhood::new(30, 25),
+        PathCacheConfig {
+            a_star_fallback: false,
+            ..PathCacheConfig::with_chunk_size(6)
+        },
+    );
+
+    let mut context = SearchContext::new();
+    let queries = [
+        (true, (0, 0), (11, 9)),
+        (false, (0, 0), (29, 24)),
+        (true, (3, 3), (4, 4)),
+        (false, (8, 2), (9, 3)),
+        (true, (2, 8), (1, 0)),
+        (false, (0, 24), (29, 0)),
+        (false, (0, 0), (7, 5)),
+        (true, (10, 1), (10, 1)),
+        (false, (28, 3), (1, 20)),
+        (true, (0, 0), (7, 5)),
+    ];
+    // twice, so that every query runs after every other kind of query
+    for &(use_small, start, goal) in queries.iter().chain(queries.iter().rev()) {
+        let pathfinding = if use_small { &small } else { &large };
+        let shared = pathfinding.find_path_with_context(start, goal, cost_fn, &mut context);
+        let fresh = pathfinding.find_path(start, goal, cost_fn);
+        match (shared, fresh) {
+            (Some(shared), Some(fresh)) => {
+                assert_eq!(shared.cost(), fresh.cost(), "{:?} -> {:?}", start, goal);
+                assert_eq!(
+                    shared.collect::<Vec<_>>(),
+                    fresh.collect::<Vec<_>>(),
+                    "{:?} -> {:?}",
+                    start,
+                    goal
+                );
+            }
+            (None, None) => {}
+            (shared, fresh) => panic!(
+                "{:?} -> {:?}: {:?} with context, {:?} without",
+                start,
+                goal,
+                shared.map(|p| p.cost()),
+                fresh.map(|p| p.cost())
+            ),
+        }
+    }
+}